@@ -1,3 +1,3 @@
 #!/bin/bash
 set -Eeuo pipefail
-hurl --json tests_failed/assert_template_variable_not_found.hurl
+hurl --json --variable content_type=text/plain tests_failed/assert_template_variable_not_found.hurl
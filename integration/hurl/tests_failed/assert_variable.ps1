@@ -1,3 +1,3 @@
 Set-StrictMode -Version latest
 $ErrorActionPreference = 'Stop'
-hurl tests_failed/assert_variable.hurl
+hurl --variable unknown=404 tests_failed/assert_variable.hurl
@@ -1,3 +1,3 @@
 #!/bin/bash
 set -Eeuo pipefail
-hurl tests_failed/assert_variable.hurl
+hurl --variable unknown=404 tests_failed/assert_variable.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --continue-on-error tests_failed/assert_xml_schema.hurl
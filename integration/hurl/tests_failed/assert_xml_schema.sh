@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --continue-on-error tests_failed/assert_xml_schema.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --test --jobs 2 tests_ok/test.1.hurl tests_error_parser/base64.hurl tests_ok/test.2.hurl
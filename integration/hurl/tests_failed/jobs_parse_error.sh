@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --test --jobs 2 tests_ok/test.1.hurl tests_error_parser/base64.hurl tests_ok/test.2.hurl
@@ -0,0 +1,17 @@
+from app import app
+from flask import Response
+
+
+@app.route("/json-match/user-failed")
+def json_match_user_failed():
+    return Response(
+        """{
+  "id": "6f1c1b3a-2d4e-4f7a-9b3c-8e2f1a0d5c47",
+  "name": "Bob",
+  "age": 32,
+  "created": "2024-01-15T10:00:00Z",
+  "roles": ["admin", "user"],
+  "address": {"city": "Paris", "zip": "75001"}
+}""",
+        mimetype="application/json",
+    )
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --json-match --variable id=12abc tests_failed/json_match_invalid_variable.hurl
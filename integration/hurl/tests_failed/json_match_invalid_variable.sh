@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --json-match --variable id=12abc tests_failed/json_match_invalid_variable.hurl
@@ -1,3 +1,3 @@
 Set-StrictMode -Version latest
 $ErrorActionPreference = 'Stop'
-hurl tests_failed/key_template.hurl
+hurl --variable name=X-Name tests_failed/key_template.hurl
@@ -1,3 +1,3 @@
 #!/bin/bash
 set -Eeuo pipefail
-hurl tests_failed/key_template.hurl
+hurl --variable name=X-Name tests_failed/key_template.hurl
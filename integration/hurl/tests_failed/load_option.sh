@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --load --jobs 2 tests_ok/load_test.hurl
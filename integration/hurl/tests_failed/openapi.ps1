@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --continue-on-error --openapi tests_failed/openapi.yaml tests_failed/openapi.hurl
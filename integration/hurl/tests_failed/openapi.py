@@ -0,0 +1,21 @@
+from app import app
+from flask import Response
+
+
+@app.route("/openapi-failed/users/2")
+def openapi_failed_user_invalid_body():
+    return Response(
+        '{"id": "2", "email": "alice@example.org"}',
+        mimetype="application/json",
+        headers={"X-Rate-Limit": "1000"},
+    )
+
+
+@app.route("/openapi-failed/users/3")
+def openapi_failed_user_invalid_content_type():
+    return Response("<user>Bob</user>", mimetype="application/xml")
+
+
+@app.route("/openapi-failed/users/4")
+def openapi_failed_user_server_error():
+    return Response("Internal error", status=500, mimetype="text/plain")
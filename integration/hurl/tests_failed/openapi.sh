@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --continue-on-error --openapi tests_failed/openapi.yaml tests_failed/openapi.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --replay tests_failed/replay.json tests_failed/replay.hurl
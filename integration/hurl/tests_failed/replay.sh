@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --replay tests_failed/replay.json tests_failed/replay.hurl
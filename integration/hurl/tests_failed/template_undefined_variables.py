@@ -0,0 +1,8 @@
+# coding=utf-8
+from app import app
+
+
+@app.route("/undefined-variables")
+def undefined_variables():
+    # No request is sent: undefined variables are checked before running the file.
+    assert False
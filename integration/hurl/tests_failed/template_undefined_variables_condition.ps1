@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --variable env=prod tests_failed/template_undefined_variables_condition.hurl
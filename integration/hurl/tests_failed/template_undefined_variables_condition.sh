@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --variable env=prod tests_failed/template_undefined_variables_condition.hurl
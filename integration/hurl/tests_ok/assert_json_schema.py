@@ -0,0 +1,16 @@
+from app import app
+from flask import Response
+
+
+@app.route("/json-schema")
+def json_schema():
+    return Response(
+        """{
+  "count": 2,
+  "users": [
+    {"id": 1, "name": "Bob", "email": "bob@example.org", "roles": ["admin", "dev"]},
+    {"id": 2, "name": "Alice", "email": null, "roles": []}
+  ]
+}""",
+        mimetype="application/json",
+    )
@@ -0,0 +1,20 @@
+from app import app
+from flask import Response
+
+
+@app.route("/xml-schema")
+def xml_schema():
+    return Response(
+        """<?xml version="1.0" encoding="utf-8"?>
+<users xmlns="https://example.org/users">
+  <user id="1">
+    <name>Bob</name>
+    <email>bob@example.org</email>
+  </user>
+  <user id="2">
+    <name>Alice</name>
+  </user>
+</users>
+""",
+        mimetype="application/xml",
+    )
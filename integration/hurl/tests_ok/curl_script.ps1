@@ -0,0 +1,8 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+if (Test-Path build/curl_script.sh) {
+    Remove-Item build/curl_script.sh
+}
+
+hurl --no-output --curl build/curl_script.sh --variable name=bob --secret token=abc tests_ok/curl_script.hurl
+Write-Host (Get-Content build/curl_script.sh -Raw) -NoNewLine
@@ -0,0 +1,8 @@
+from app import app
+from flask import request
+
+
+@app.route("/curl-script", methods=["POST"])
+def curl_script():
+    assert request.headers["Authorization"] == "Bearer abc"
+    return "Hello " + request.json["name"] + "!"
@@ -0,0 +1,6 @@
+#!/bin/bash
+set -Eeuo pipefail
+rm -f build/curl_script.sh
+
+hurl --no-output --curl build/curl_script.sh --variable name=bob --secret token=abc tests_ok/curl_script.hurl
+cat build/curl_script.sh
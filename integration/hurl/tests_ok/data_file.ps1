@@ -0,0 +1,12 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+if (Test-Path build/data_file_csv.tap) {
+    Remove-Item build/data_file_csv.tap
+}
+if (Test-Path build/data_file_jsonl.tap) {
+    Remove-Item build/data_file_jsonl.tap
+}
+hurl --data tests_ok/data_file.csv --no-output --report-tap build/data_file_csv.tap tests_ok/data_file.hurl
+hurl --data tests_ok/data_file.jsonl --no-output --report-tap build/data_file_jsonl.tap tests_ok/data_file.hurl
+Write-Host (Get-Content build/data_file_csv.tap -Raw) -NoNewLine
+Write-Host (Get-Content build/data_file_jsonl.tap -Raw) -NoNewLine
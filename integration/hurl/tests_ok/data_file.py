@@ -0,0 +1,13 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/data-file/users", methods=["POST"])
+def data_file_users():
+    user = request.json
+    assert isinstance(user["age"], int)
+    assert isinstance(user["admin"], bool)
+    return app.response_class(
+        response=request.data, status=201, mimetype="application/json"
+    )
@@ -0,0 +1,6 @@
+#!/bin/bash
+set -Eeuo pipefail
+rm -f build/data_file_csv.tap build/data_file_jsonl.tap
+hurl --data tests_ok/data_file.csv --no-output --report-tap build/data_file_csv.tap tests_ok/data_file.hurl
+hurl --data tests_ok/data_file.jsonl --no-output --report-tap build/data_file_jsonl.tap tests_ok/data_file.hurl
+cat build/data_file_csv.tap build/data_file_jsonl.tap
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --dry-run --variable token=abc tests_ok/dry_run.hurl
@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --dry-run --variable token=abc tests_ok/dry_run.hurl
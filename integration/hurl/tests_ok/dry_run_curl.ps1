@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --dry-run --dry-run-format curl --secret token=abc tests_ok/dry_run.hurl
@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --dry-run --dry-run-format curl --secret token=abc tests_ok/dry_run.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl tests_ok/entry_from_name.hurl --from-entry search --to-entry 3 --verbose
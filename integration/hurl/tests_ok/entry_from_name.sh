@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl tests_ok/entry_from_name.hurl --from-entry search --to-entry 3 --verbose
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl tests_ok/entry_tag.hurl --tag smoke --exclude-tag slow --verbose
@@ -0,0 +1,7 @@
+# coding=utf-8
+from app import app
+
+
+@app.route("/entry-tag/<entry_count>")
+def entry_tag(entry_count: 1):
+    return f"Reached entry {entry_count}"
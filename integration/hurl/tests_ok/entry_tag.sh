@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl tests_ok/entry_tag.hurl --tag smoke --exclude-tag slow --verbose
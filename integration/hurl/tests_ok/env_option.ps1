@@ -0,0 +1,4 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+Set-Location tests_ok
+hurl --env staging --variable user=Alice env_option.hurl
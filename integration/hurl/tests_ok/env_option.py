@@ -0,0 +1,8 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/env-option")
+def env_option():
+    return f"Hello {request.args['user']} {request.args['id']}"
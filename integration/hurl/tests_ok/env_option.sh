@@ -0,0 +1,4 @@
+#!/bin/bash
+set -Eeuo pipefail
+cd tests_ok
+hurl --env staging --variable user=Alice env_option.hurl
@@ -0,0 +1,14 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/include-file/login")
+def include_file_login():
+    return "abc123"
+
+
+@app.route("/include-file/account")
+def include_file_account():
+    assert request.headers["Authorization"] == "Bearer abc123"
+    return "Welcome Bob"
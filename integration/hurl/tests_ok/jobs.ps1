@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --test --jobs 3 --glob "tests_ok/test.*.hurl"
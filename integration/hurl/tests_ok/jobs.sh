@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --test --jobs 3 --glob "tests_ok/test.*.hurl"
@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --no-output --json-match tests_ok/json_match.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --load --users 2 --duration 1s tests_ok/load_test.hurl
@@ -0,0 +1,14 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/load-test/login", methods=["POST"])
+def load_test_login():
+    return '{"token":"abc123"}'
+
+
+@app.route("/load-test/items")
+def load_test_items():
+    assert request.headers["Authorization"] == "Bearer abc123"
+    return '{"items":[1,2,3]}'
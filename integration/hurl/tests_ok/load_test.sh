@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --load --users 2 --duration 1s tests_ok/load_test.hurl
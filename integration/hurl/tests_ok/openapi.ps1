@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --no-output --openapi tests_ok/openapi.yaml tests_ok/openapi.hurl
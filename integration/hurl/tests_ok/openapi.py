@@ -0,0 +1,21 @@
+from app import app
+from flask import Response
+
+
+@app.route("/openapi/users")
+def openapi_users():
+    return Response(
+        '[{"id": 1, "name": "Bob", "email": null}, {"id": 2, "name": "Alice"}]',
+        mimetype="application/json",
+    )
+
+
+@app.route("/openapi/users/<int:id>")
+def openapi_user(id):
+    if id != 1:
+        return Response("User not found", status=404, mimetype="text/plain")
+    return Response(
+        '{"id": 1, "name": "Bob", "email": "bob@example.org"}',
+        mimetype="application/json",
+        headers={"X-Rate-Limit": "10"},
+    )
@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --no-output --openapi tests_ok/openapi.yaml tests_ok/openapi.hurl
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --variable env=prod --variable features=alpha,beta tests_ok/option_condition.hurl
@@ -0,0 +1,8 @@
+# coding=utf-8
+from app import app
+
+
+@app.route("/condition/<name>")
+def condition(name):
+    assert name != "never"
+    return ""
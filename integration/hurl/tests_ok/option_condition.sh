@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --variable env=prod --variable features=alpha,beta tests_ok/option_condition.hurl
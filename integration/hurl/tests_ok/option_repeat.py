@@ -0,0 +1,42 @@
+# coding=utf-8
+from app import app
+from flask import jsonify
+
+hits = 0
+deleted = []
+
+
+@app.route("/repeat/reset", methods=["POST"])
+def repeat_reset():
+    global hits
+    hits = 0
+    deleted.clear()
+    return ""
+
+
+@app.route("/repeat/hit")
+def repeat_hit():
+    global hits
+    hits += 1
+    return ""
+
+
+@app.route("/repeat/hits")
+def repeat_hits():
+    return str(hits)
+
+
+@app.route("/repeat/items")
+def repeat_items():
+    return jsonify(ids=[3, 7, 12])
+
+
+@app.route("/repeat/items/<int:id>", methods=["DELETE"])
+def repeat_delete_item(id):
+    deleted.append(id)
+    return "", 204
+
+
+@app.route("/repeat/deleted")
+def repeat_deleted():
+    return jsonify(ids=deleted)
@@ -0,0 +1,20 @@
+from app import app
+from flask import redirect, request, Response
+
+
+@app.route("/record-replay/redirect")
+def record_replay_redirect():
+    return redirect("http://localhost:8000/record-replay/redirected")
+
+
+@app.route("/record-replay/redirected")
+def record_replay_redirected():
+    return "Redirected"
+
+
+@app.route("/record-replay", methods=["POST"])
+def record_replay():
+    name = request.json["name"]
+    return Response(
+        '{"name": "' + name + '"}', status=201, mimetype="application/json"
+    )
@@ -0,0 +1,7 @@
+#!/bin/bash
+set -Eeuo pipefail
+rm -f build/cassette.json
+
+hurl --record build/cassette.json tests_ok/record_replay.hurl
+# Requests are not sent when replaying: connecting to port 1 would fail.
+hurl --replay build/cassette.json --connect-to localhost:8000:localhost:1 tests_ok/record_replay.hurl
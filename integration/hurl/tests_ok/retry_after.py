@@ -0,0 +1,18 @@
+# return 429 with a Retry-After header by default
+# or 200 if previous request has been executed less than 2 seconds ago
+from app import app
+from flask import Response
+import time
+
+last = 0
+
+
+@app.route("/retry/after")
+def retry_after():
+    global last
+    current = time.time()
+    interval = current - last
+    last = current
+    if interval < 2:
+        return "OK", 200
+    return Response(status=429, headers={"Retry-After": "1"})
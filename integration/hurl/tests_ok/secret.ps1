@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --secret password=s3cr3t --verbose tests_ok/secret.hurl
@@ -0,0 +1,15 @@
+# coding=utf-8
+from app import app
+from flask import Response, request
+
+
+@app.route("/secret/login")
+def secret_login():
+    assert request.args.get("password") == "s3cr3t"
+    return Response(headers={"X-Token": "t0k3n"})
+
+
+@app.route("/secret/profile")
+def secret_profile():
+    assert request.headers["Authorization"] == "Bearer t0k3n"
+    return "Hello Bob"
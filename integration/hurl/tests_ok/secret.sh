@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --secret password=s3cr3t --verbose tests_ok/secret.hurl
@@ -0,0 +1,22 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+
+$server = Start-Process hurl -ArgumentList '--serve', '--port', '8765', 'tests_ok/serve.hurl' -PassThru -NoNewWindow -RedirectStandardError 'build/serve.err'
+try {
+    # Waits for the mock server to listen.
+    for ($i = 0; $i -lt 50; $i++) {
+        try {
+            (New-Object System.Net.Sockets.TcpClient('localhost', 8765)).Close()
+            break
+        } catch {
+            Start-Sleep -Milliseconds 100
+        }
+    }
+    hurl --test tests_ok/serve_client.hurl 2>$null
+    if ($LASTEXITCODE -ne 0) {
+        exit $LASTEXITCODE
+    }
+    Write-Host "OK"
+} finally {
+    Stop-Process -Id $server.Id
+}
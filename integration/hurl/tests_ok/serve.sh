@@ -0,0 +1,15 @@
+#!/bin/bash
+set -Eeuo pipefail
+
+hurl --serve --port 8765 tests_ok/serve.hurl 2>/dev/null &
+server=$!
+trap 'kill $server' EXIT
+
+# Waits for the mock server to listen.
+for _ in $(seq 1 50); do
+    (echo > /dev/tcp/localhost/8765) 2>/dev/null && break
+    sleep 0.1
+done
+
+hurl --test tests_ok/serve_client.hurl 2>/dev/null
+echo "OK"
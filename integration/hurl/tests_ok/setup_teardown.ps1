@@ -0,0 +1,7 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+if (Test-Path build/setup_teardown.tap) {
+    Remove-Item build/setup_teardown.tap
+}
+hurl --setup tests_ok/setup_teardown.1.hurl --teardown tests_ok/setup_teardown.2.hurl --report-tap build/setup_teardown.tap tests_ok/setup_teardown.hurl
+Write-Host (Get-Content build/setup_teardown.tap -Raw) -NoNewLine
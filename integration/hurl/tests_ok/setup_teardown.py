@@ -0,0 +1,16 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/setup-teardown/login", methods=["POST"])
+def setup_teardown_login():
+    return '{"token":"abc123"}'
+
+
+@app.route("/setup-teardown/data", methods=["GET", "DELETE"])
+def setup_teardown_data():
+    assert request.headers["Authorization"] == "Bearer abc123"
+    if request.method == "DELETE":
+        return ""
+    return "Hello Bob"
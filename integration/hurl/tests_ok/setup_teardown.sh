@@ -0,0 +1,5 @@
+#!/bin/bash
+set -Eeuo pipefail
+rm -f build/setup_teardown.tap
+hurl --setup tests_ok/setup_teardown.1.hurl --teardown tests_ok/setup_teardown.2.hurl --report-tap build/setup_teardown.tap tests_ok/setup_teardown.hurl
+cat build/setup_teardown.tap
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --test --shard 1/2 --glob "tests_ok/test.*.hurl"
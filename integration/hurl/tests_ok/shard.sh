@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --test --shard 1/2 --glob "tests_ok/test.*.hurl"
@@ -0,0 +1,21 @@
+from app import app
+from flask import Response
+
+
+@app.route("/snapshot")
+def snapshot():
+    return Response(
+        """{
+  "count": 2,
+  "users": [
+    {"name": "Bob", "id": 1, "roles": ["admin", "dev"]},
+    {"name": "Alice", "id": 2, "roles": []}
+  ]
+}""",
+        mimetype="application/json",
+    )
+
+
+@app.route("/snapshot/text")
+def snapshot_text():
+    return "Hello World!\nGoodbye World!\n"
@@ -0,0 +1,12 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+if (Test-Path build/snapshots) {
+    Remove-Item -Recurse build/snapshots
+}
+
+# Snapshots are created in the file root...
+hurl --no-output --file-root build --update-snapshots tests_ok/snapshot.hurl
+Write-Host (Get-Content build/snapshots/snapshot_users.json, build/snapshots/snapshot_user.json, build/snapshots/snapshot_count.txt, build/snapshots/snapshot_text.txt -Raw) -NoNewLine
+
+# ... and then match the responses.
+hurl --no-output --file-root build tests_ok/snapshot.hurl
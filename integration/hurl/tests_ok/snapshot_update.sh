@@ -0,0 +1,10 @@
+#!/bin/bash
+set -Eeuo pipefail
+rm -rf build/snapshots
+
+# Snapshots are created in the file root...
+hurl --no-output --file-root build --update-snapshots tests_ok/snapshot.hurl
+cat build/snapshots/snapshot_users.json build/snapshots/snapshot_user.json build/snapshots/snapshot_count.txt build/snapshots/snapshot_text.txt
+
+# ... and then match the responses.
+hurl --no-output --file-root build tests_ok/snapshot.hurl
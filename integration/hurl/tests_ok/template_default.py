@@ -0,0 +1,11 @@
+# coding=utf-8
+from app import app
+from flask import jsonify, request
+
+
+@app.route("/template-default")
+def template_default():
+    assert request.args["page"] == "1"
+    assert request.args["size"] == "50"
+    assert request.args["user"] == "guest"
+    return jsonify(id="abc", page=1, size=50, debug=False)
@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --variable 'name=Bob Smith' --variable date=2024-01-22T10:00:00Z --variable tags=alpha,beta,gamma tests_ok/template_filter.hurl
@@ -0,0 +1,16 @@
+# coding=utf-8
+from app import app
+from flask import request
+
+
+@app.route("/template-filter/<name>")
+def template_filter(name):
+    assert name == "Bob Smith"
+    assert request.headers["X-Date"] == "22/01/2024"
+    assert request.headers["X-Tag"] == "beta"
+    return "Hello <Bob>"
+
+
+@app.route("/template-filter/check")
+def template_filter_check():
+    return request.args["text"]
@@ -0,0 +1,4 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+$env:HURL_TEST_FUNCTION = 'foo'
+hurl --seed 42 tests_ok/template_function.hurl
@@ -0,0 +1,19 @@
+# coding=utf-8
+import re
+from datetime import datetime
+
+from app import app
+from flask import request
+
+
+@app.route("/template-function")
+def template_function():
+    assert re.fullmatch(
+        r"[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}",
+        request.args["uuid"],
+    )
+    assert 1 <= int(request.args["number"]) <= 100
+    assert re.fullmatch(r"[A-Za-z0-9]{12}", request.args["string"])
+    assert request.args["env"] == "foo"
+    datetime.strptime(request.args["date"], "%Y-%m-%dT%H:%M:%S.%fZ")
+    return "Hello"
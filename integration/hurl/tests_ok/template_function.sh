@@ -0,0 +1,4 @@
+#!/bin/bash
+set -Eeuo pipefail
+export HURL_TEST_FUNCTION=foo
+hurl --seed 42 tests_ok/template_function.hurl
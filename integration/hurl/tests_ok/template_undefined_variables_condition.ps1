@@ -0,0 +1,3 @@
+Set-StrictMode -Version latest
+$ErrorActionPreference = 'Stop'
+hurl --variable env=dev tests_ok/template_undefined_variables_condition.hurl
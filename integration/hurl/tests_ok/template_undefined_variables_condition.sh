@@ -0,0 +1,3 @@
+#!/bin/bash
+set -Eeuo pipefail
+hurl --variable env=dev tests_ok/template_undefined_variables_condition.hurl
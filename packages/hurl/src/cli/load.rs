@@ -0,0 +1,117 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Runs `run_iteration` in a loop on `users` threads, until `duration` has elapsed.
+///
+/// Results are passed to `on_completed` on the calling thread, in their order of completion. If
+/// an iteration fails, every user is stopped and the first error is returned.
+pub fn run<R, E, F, C>(
+    users: usize,
+    duration: Duration,
+    run_iteration: F,
+    mut on_completed: C,
+) -> Result<(), E>
+where
+    R: Send,
+    E: Send,
+    F: Fn() -> Result<R, E> + Sync,
+    C: FnMut(R),
+{
+    let start = Instant::now();
+    let stopped = AtomicBool::new(false);
+    let run_iteration = &run_iteration;
+    let stopped = &stopped;
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..users.max(1) {
+            let tx = tx.clone();
+            s.spawn(move || {
+                while !stopped.load(Ordering::Relaxed) && start.elapsed() < duration {
+                    let result = run_iteration();
+                    if result.is_err() {
+                        stopped.store(true, Ordering::Relaxed);
+                    }
+                    if tx.send(result).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        // Users own the remaining senders: the receiving loop ends when every user is done.
+        drop(tx);
+
+        let mut error = None;
+        for result in rx {
+            match result {
+                Ok(result) => on_completed(result),
+                Err(e) => {
+                    if error.is_none() {
+                        error = Some(e);
+                    }
+                }
+            }
+        }
+        match error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use super::*;
+
+    #[test]
+    fn run_until_duration() {
+        let mut count = 0;
+        let result = run(
+            4,
+            Duration::from_millis(50),
+            || {
+                thread::sleep(Duration::from_millis(5));
+                Ok::<_, ()>(1)
+            },
+            |n| count += n,
+        );
+        assert!(result.is_ok());
+        assert!(count >= 4);
+    }
+
+    #[test]
+    fn stop_on_error() {
+        let iterations = AtomicUsize::new(0);
+        let result = run(
+            2,
+            Duration::from_secs(60),
+            || match iterations.fetch_add(1, Ordering::Relaxed) {
+                n if n < 10 => Ok(n),
+                _ => Err("error"),
+            },
+            |_| {},
+        );
+        assert_eq!(result, Err("error"));
+    }
+}
@@ -18,7 +18,11 @@
 mod error;
 mod fs;
 mod interactive;
+pub(crate) mod load;
 pub(crate) mod options;
+pub(crate) mod parallel;
+pub(crate) mod shard;
+pub(crate) mod watch;
 pub use self::error::CliError;
 pub use self::fs::read_to_string;
 pub use self::options::OutputType;
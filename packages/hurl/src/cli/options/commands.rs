@@ -119,6 +119,23 @@ pub fn cookies_output_file() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn curl() -> clap::Arg {
+    clap::Arg::new("curl")
+        .long("curl")
+        .value_name("FILE")
+        .help("Export each executed request to a runnable curl script in FILE")
+        .num_args(1)
+}
+
+pub fn data() -> clap::Arg {
+    clap::Arg::new("data")
+        .long("data")
+        .value_name("FILE")
+        .help("Run each input file once per row of the CSV or JSON lines FILE")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
 pub fn delay() -> clap::Arg {
     clap::Arg::new("delay")
         .long("delay")
@@ -129,6 +146,49 @@ pub fn delay() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn dry_run() -> clap::Arg {
+    clap::Arg::new("dry_run")
+        .long("dry-run")
+        .help("Render the requests without sending them")
+        .action(ArgAction::SetTrue)
+}
+
+pub fn dry_run_format() -> clap::Arg {
+    clap::Arg::new("dry_run_format")
+        .long("dry-run-format")
+        .value_name("FORMAT")
+        .default_value("http")
+        .value_parser(["http", "curl", "json"])
+        .help("Format of the requests rendered by --dry-run")
+        .num_args(1)
+}
+
+pub fn duration() -> clap::Arg {
+    clap::Arg::new("duration")
+        .long("duration")
+        .value_name("DURATION")
+        .default_value("10s")
+        .help("Duration of a load test (for instance 500ms, 60s or 5m)")
+        .num_args(1)
+}
+
+pub fn entry() -> clap::Arg {
+    clap::Arg::new("entry")
+        .long("entry")
+        .value_name("NAME")
+        .help("Execute only the entry named NAME")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
+pub fn env() -> clap::Arg {
+    clap::Arg::new("env")
+        .long("env")
+        .value_name("NAME")
+        .help("Use the variables and options of the environment NAME defined in hurl.toml")
+        .num_args(1)
+}
+
 pub fn error_format() -> clap::Arg {
     clap::Arg::new("error_format")
         .long("error-format")
@@ -139,6 +199,16 @@ pub fn error_format() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn exclude_tag() -> clap::Arg {
+    clap::Arg::new("exclude_tag")
+        .long("exclude-tag")
+        .value_name("TAG")
+        .help("Do not run the entries with tag TAG (can be used multiple times)")
+        .conflicts_with("interactive")
+        .num_args(1)
+        .action(ArgAction::Append)
+}
+
 pub fn fail_at_end() -> clap::Arg {
     clap::Arg::new("fail_at_end")
         .long("fail-at-end")
@@ -163,6 +233,15 @@ pub fn follow_location() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn from_entry() -> clap::Arg {
+    clap::Arg::new("from_entry")
+        .long("from-entry")
+        .value_name("ENTRY")
+        .help("Execute Hurl file from ENTRY, an entry number (starting at 1) or name")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
 pub fn glob() -> clap::Arg {
     clap::Arg::new("glob")
         .long("glob")
@@ -248,6 +327,16 @@ pub fn ipv6() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn jobs() -> clap::Arg {
+    clap::Arg::new("jobs")
+        .long("jobs")
+        .value_name("NUM")
+        .value_parser(value_parser!(u32).range(1..))
+        .help("Maximum number of Hurl files run in parallel")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
 pub fn json() -> clap::Arg {
     clap::Arg::new("json")
         .long("json")
@@ -256,6 +345,21 @@ pub fn json() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn json_match() -> clap::Arg {
+    clap::Arg::new("json_match")
+        .long("json-match")
+        .help("Match JSON bodies and values structurally, allowing extra fields and placeholders")
+        .action(ArgAction::SetTrue)
+}
+
+pub fn load() -> clap::Arg {
+    clap::Arg::new("load")
+        .long("load")
+        .help("Run the input files as a load test")
+        .conflicts_with("interactive")
+        .action(ArgAction::SetTrue)
+}
+
 pub fn max_redirects() -> clap::Arg {
     clap::Arg::new("max_redirects")
         .long("max-redirs")
@@ -278,6 +382,14 @@ pub fn max_time() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn merge_junit() -> clap::Arg {
+    clap::Arg::new("merge_junit")
+        .long("merge-junit")
+        .value_name("FILE")
+        .help("Merge the input JUnit reports into FILE")
+        .num_args(1)
+}
+
 pub fn no_color() -> clap::Arg {
     clap::Arg::new("no_color")
         .long("no-color")
@@ -286,6 +398,14 @@ pub fn no_color() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn no_insecure() -> clap::Arg {
+    clap::Arg::new("no_insecure")
+        .long("no-insecure")
+        .help("Do not allow insecure SSL connections, even if the environment allows them")
+        .conflicts_with("insecure")
+        .action(ArgAction::SetTrue)
+}
+
 pub fn no_output() -> clap::Arg {
     clap::Arg::new("no_output")
         .long("no-output")
@@ -302,6 +422,14 @@ pub fn noproxy() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn openapi() -> clap::Arg {
+    clap::Arg::new("openapi")
+        .long("openapi")
+        .value_name("FILE")
+        .help("Check the HTTP calls against an OpenAPI specification FILE")
+        .num_args(1)
+}
+
 pub fn output() -> clap::Arg {
     clap::Arg::new("output")
         .long("output")
@@ -318,6 +446,16 @@ pub fn path_as_is() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn port() -> clap::Arg {
+    clap::Arg::new("port")
+        .long("port")
+        .value_name("PORT")
+        .default_value("8080")
+        .value_parser(value_parser!(u16))
+        .help("Port of the mock server started with --serve")
+        .num_args(1)
+}
+
 pub fn proxy() -> clap::Arg {
     clap::Arg::new("proxy")
         .long("proxy")
@@ -327,6 +465,22 @@ pub fn proxy() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn record() -> clap::Arg {
+    clap::Arg::new("record")
+        .long("record")
+        .value_name("FILE")
+        .help("Record the HTTP calls of the run to a cassette FILE")
+        .num_args(1)
+}
+
+pub fn replay() -> clap::Arg {
+    clap::Arg::new("replay")
+        .long("replay")
+        .value_name("FILE")
+        .help("Replay the responses of a cassette FILE instead of sending requests")
+        .num_args(1)
+}
+
 pub fn report_html() -> clap::Arg {
     clap::Arg::new("report_html")
         .long("report-html")
@@ -371,6 +525,20 @@ pub fn retry() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn retry_after() -> clap::Arg {
+    clap::Arg::new("retry_after")
+        .long("retry-after")
+        .help("Honour the Retry-After header of 429 and 503 responses when retrying")
+        .action(ArgAction::SetTrue)
+}
+
+pub fn retry_backoff() -> clap::Arg {
+    clap::Arg::new("retry_backoff")
+        .long("retry-backoff")
+        .help("Double the retry interval after each retry")
+        .action(ArgAction::SetTrue)
+}
+
 pub fn retry_interval() -> clap::Arg {
     clap::Arg::new("retry_interval")
         .long("retry-interval")
@@ -381,6 +549,81 @@ pub fn retry_interval() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn retry_jitter() -> clap::Arg {
+    clap::Arg::new("retry_jitter")
+        .long("retry-jitter")
+        .help("Randomize the interval between retries")
+        .action(ArgAction::SetTrue)
+}
+
+pub fn retry_max_interval() -> clap::Arg {
+    clap::Arg::new("retry_max_interval")
+        .long("retry-max-interval")
+        .value_name("MILLISECONDS")
+        .value_parser(value_parser!(u64))
+        .help("Maximum interval in milliseconds between retries")
+        .num_args(1)
+}
+
+pub fn retry_max_time() -> clap::Arg {
+    clap::Arg::new("retry_max_time")
+        .long("retry-max-time")
+        .value_name("MILLISECONDS")
+        .value_parser(value_parser!(u64))
+        .help("Maximum time in milliseconds spent retrying an entry")
+        .num_args(1)
+}
+
+pub fn secret() -> clap::Arg {
+    clap::Arg::new("secret")
+        .long("secret")
+        .value_name("NAME=VALUE")
+        .help("Define a secret variable")
+        .num_args(1)
+        .action(ArgAction::Append)
+}
+
+pub fn seed() -> clap::Arg {
+    clap::Arg::new("seed")
+        .long("seed")
+        .value_name("NUM")
+        .value_parser(value_parser!(u64))
+        .help("Set the seed of the random template functions")
+        .num_args(1)
+}
+
+pub fn serve() -> clap::Arg {
+    clap::Arg::new("serve")
+        .long("serve")
+        .help("Serve the input file as a local mock server")
+        .action(ArgAction::SetTrue)
+}
+
+pub fn setup() -> clap::Arg {
+    clap::Arg::new("setup")
+        .long("setup")
+        .value_name("FILE")
+        .help("Run the Hurl FILE once before all the input files")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
+pub fn shard() -> clap::Arg {
+    clap::Arg::new("shard")
+        .long("shard")
+        .value_name("INDEX/TOTAL")
+        .help("Run only the shard INDEX of TOTAL shards of the input files")
+        .num_args(1)
+}
+
+pub fn shard_timings() -> clap::Arg {
+    clap::Arg::new("shard_timings")
+        .long("shard-timings")
+        .value_name("FILE")
+        .help("Balance shards with the durations of a previous JSON or JUnit report FILE")
+        .num_args(1)
+}
+
 pub fn ssl_no_revoke() -> clap::Arg {
     clap::Arg::new("ssl_no_revoke")
         .long("ssl-no-revoke")
@@ -388,6 +631,25 @@ pub fn ssl_no_revoke() -> clap::Arg {
         .action(ArgAction::SetTrue)
 }
 
+pub fn tag() -> clap::Arg {
+    clap::Arg::new("tag")
+        .long("tag")
+        .value_name("TAG")
+        .help("Run only the entries with tag TAG (can be used multiple times)")
+        .conflicts_with("interactive")
+        .num_args(1)
+        .action(ArgAction::Append)
+}
+
+pub fn teardown() -> clap::Arg {
+    clap::Arg::new("teardown")
+        .long("teardown")
+        .value_name("FILE")
+        .help("Run the Hurl FILE once after all the input files, even if they have failed")
+        .conflicts_with("interactive")
+        .num_args(1)
+}
+
 pub fn test() -> clap::Arg {
     clap::Arg::new("test")
         .long("test")
@@ -405,6 +667,13 @@ pub fn to_entry() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn update_snapshots() -> clap::Arg {
+    clap::Arg::new("update_snapshots")
+        .long("update-snapshots")
+        .help("Rewrite the snapshot files of matchesSnapshot asserts")
+        .action(ArgAction::SetTrue)
+}
+
 pub fn user() -> clap::Arg {
     clap::Arg::new("user")
         .long("user")
@@ -423,6 +692,16 @@ pub fn user_agent() -> clap::Arg {
         .num_args(1)
 }
 
+pub fn users() -> clap::Arg {
+    clap::Arg::new("users")
+        .long("users")
+        .value_name("NUM")
+        .default_value("1")
+        .value_parser(value_parser!(u32).range(1..))
+        .help("Number of concurrent users of a load test")
+        .num_args(1)
+}
+
 pub fn variable() -> clap::Arg {
     clap::Arg::new("variable")
         .long("variable")
@@ -454,3 +733,11 @@ pub fn very_verbose() -> clap::Arg {
         .help("Turn on verbose output, including HTTP response and libcurl logs")
         .action(ArgAction::SetTrue)
 }
+
+pub fn watch() -> clap::Arg {
+    clap::Arg::new("watch")
+        .long("watch")
+        .help("Watch input files and run them again on change")
+        .conflicts_with("interactive")
+        .action(ArgAction::SetTrue)
+}
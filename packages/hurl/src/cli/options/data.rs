@@ -0,0 +1,222 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::variables::parse_value;
+use super::OptionsError;
+use crate::runner::Value;
+
+/// Parses the data file `filename` and returns its rows of variables.
+///
+/// The data file is either a JSON lines file (one JSON object per line), or a CSV file whose
+/// first line is the list of the variable names. CSV values are typed with the `--variable` rules.
+pub fn parse(filename: &str) -> Result<Vec<HashMap<String, Value>>, OptionsError> {
+    let path = Path::new(filename);
+    if !path.exists() {
+        return Err(OptionsError::Error(format!(
+            "Data file {} does not exist",
+            path.display()
+        )));
+    }
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            return Err(OptionsError::Error(format!(
+                "Data file {} can not be read: {e}",
+                path.display()
+            )))
+        }
+    };
+    let content = content.strip_prefix('\u{feff}').unwrap_or(&content);
+    if content.trim_start().starts_with('{') {
+        parse_json_lines(content)
+    } else {
+        parse_csv(content)
+    }
+}
+
+/// Parses a JSON lines `content`, where each non-empty line is a JSON object.
+fn parse_json_lines(content: &str) -> Result<Vec<HashMap<String, Value>>, OptionsError> {
+    let mut rows = vec![];
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let object = match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(serde_json::Value::Object(object)) => object,
+            _ => {
+                return Err(OptionsError::Error(format!(
+                    "Data line {} is not a JSON object",
+                    index + 1
+                )))
+            }
+        };
+        let row = object
+            .iter()
+            .map(|(name, value)| (name.clone(), Value::from_json(value)))
+            .collect();
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Parses a CSV `content`, whose first record is the list of the variable names.
+fn parse_csv(content: &str) -> Result<Vec<HashMap<String, Value>>, OptionsError> {
+    let mut records = csv_records(content)?.into_iter();
+    let names = match records.next() {
+        Some((_, names)) => names,
+        None => return Ok(vec![]),
+    };
+    let mut rows = vec![];
+    for (line, values) in records {
+        if values.len() != names.len() {
+            return Err(OptionsError::Error(format!(
+                "Data line {line} has {} values, expecting {}",
+                values.len(),
+                names.len()
+            )));
+        }
+        let mut row = HashMap::new();
+        for (name, value) in names.iter().zip(values.iter()) {
+            row.insert(name.clone(), parse_value(value)?);
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Splits a CSV `content` in records of fields, each record coming with its line number.
+///
+/// Fields can be enclosed in double quotes (a double quote being escaped with another double
+/// quote) to contain commas or line breaks. Empty lines are ignored.
+fn csv_records(content: &str) -> Result<Vec<(usize, Vec<String>)>, OptionsError> {
+    let mut records = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => quoted = false,
+                _ => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' => {}
+            '\n' => {
+                if !fields.is_empty() || !field.is_empty() {
+                    fields.push(std::mem::take(&mut field));
+                    records.push((record_line, std::mem::take(&mut fields)));
+                }
+                line += 1;
+                record_line = line;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(OptionsError::Error(format!(
+            "Data line {record_line} has an unterminated quoted value"
+        )));
+    }
+    if !fields.is_empty() || !field.is_empty() {
+        fields.push(field);
+        records.push((record_line, fields));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::Number;
+
+    #[test]
+    fn test_parse_csv() {
+        let content =
+            "name,age,admin\r\nBob,30,true\n\n\"Smith, \"\"Jr\"\"\",\"\"\"42\"\"\",null\n";
+        assert_eq!(
+            parse_csv(content).unwrap(),
+            vec![
+                HashMap::from([
+                    ("name".to_string(), Value::String("Bob".to_string())),
+                    ("age".to_string(), Value::Number(Number::Integer(30))),
+                    ("admin".to_string(), Value::Bool(true)),
+                ]),
+                HashMap::from([
+                    (
+                        "name".to_string(),
+                        Value::String("Smith, \"Jr\"".to_string())
+                    ),
+                    ("age".to_string(), Value::String("42".to_string())),
+                    ("admin".to_string(), Value::Null),
+                ]),
+            ]
+        );
+        assert_eq!(parse_csv("").unwrap(), vec![]);
+        assert_eq!(
+            parse_csv("name,age\nBob\n").unwrap_err(),
+            OptionsError::Error("Data line 2 has 1 values, expecting 2".to_string())
+        );
+        assert_eq!(
+            parse_csv("name\n\"Bob\n").unwrap_err(),
+            OptionsError::Error("Data line 2 has an unterminated quoted value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_json_lines() {
+        let content =
+            "{\"name\": \"Bob\", \"age\": 30}\n\n{\"name\": \"Alice\", \"age\": \"30\"}\n";
+        assert_eq!(
+            parse_json_lines(content).unwrap(),
+            vec![
+                HashMap::from([
+                    ("name".to_string(), Value::String("Bob".to_string())),
+                    ("age".to_string(), Value::Number(Number::Integer(30))),
+                ]),
+                HashMap::from([
+                    ("name".to_string(), Value::String("Alice".to_string())),
+                    ("age".to_string(), Value::String("30".to_string())),
+                ]),
+            ]
+        );
+        assert_eq!(
+            parse_json_lines("{\"name\": \"Bob\"}\n[1, 2]\n").unwrap_err(),
+            OptionsError::Error("Data line 2 is not a JSON object".to_string())
+        );
+    }
+}
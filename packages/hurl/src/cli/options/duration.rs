@@ -0,0 +1,56 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::time::Duration;
+
+/// Parses a duration like `500ms`, `60s`, `5m` or `1h`. A value without unit is in seconds.
+pub fn parse(s: &str) -> Result<Duration, String> {
+    let index = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(index);
+    let value = match value.parse::<u64>() {
+        Ok(v) => v,
+        Err(_) => return Err(format!("Invalid duration {s}")),
+    };
+    match unit {
+        "ms" => Ok(Duration::from_millis(value)),
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        "h" => Ok(Duration::from_secs(value * 3600)),
+        _ => Err(format!(
+            "Invalid duration {s}, unit must be one of ms, s, m or h"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("500ms").unwrap(), Duration::from_millis(500));
+        assert_eq!(parse("60s").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse("60").unwrap(), Duration::from_secs(60));
+        assert_eq!(parse("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse("1h").unwrap(), Duration::from_secs(3600));
+        assert_eq!(parse("s").unwrap_err(), "Invalid duration s");
+        assert_eq!(
+            parse("10d").unwrap_err(),
+            "Invalid duration 10d, unit must be one of ms, s, m or h"
+        );
+    }
+}
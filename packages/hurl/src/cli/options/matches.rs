@@ -22,18 +22,28 @@ use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::{env, io};
 
+use clap::parser::ValueSource;
 use clap::ArgMatches;
-use hurl::runner::Value;
+use hurl::http::Cassette;
+use hurl::openapi::OpenApi;
+use hurl::output::DryRunFormat;
+use hurl::runner::{FromEntry, Value};
 use hurl_core::ast::Retry;
 
+use super::profile::{self, Profile};
 use super::variables::{parse as parse_variable, parse_value};
 use super::OptionsError;
+use super::{data, duration};
 use crate::cli::options::{ErrorFormat, HttpVersion, IpResolve};
+use crate::cli::shard::Shard;
 use crate::cli::OutputType;
 
-pub fn cacert_file(arg_matches: &ArgMatches) -> Result<Option<String>, OptionsError> {
+pub fn cacert_file(
+    arg_matches: &ArgMatches,
+    profile: Option<&Profile>,
+) -> Result<Option<String>, OptionsError> {
     match get_string(arg_matches, "cacert_file") {
-        None => Ok(None),
+        None => Ok(profile.and_then(|p| p.cacert_file.clone())),
         Some(filename) => {
             let path = Path::new(&filename);
             if path.exists() {
@@ -105,8 +115,11 @@ pub fn connect_timeout(arg_matches: &ArgMatches) -> Duration {
     Duration::from_secs(value)
 }
 
-pub fn connects_to(arg_matches: &ArgMatches) -> Vec<String> {
-    get_strings(arg_matches, "connect_to").unwrap_or_default()
+pub fn connects_to(arg_matches: &ArgMatches, profile: Option<&Profile>) -> Vec<String> {
+    match get_strings(arg_matches, "connect_to") {
+        Some(connects_to) => connects_to,
+        None => profile.map(|p| p.connects_to.clone()).unwrap_or_default(),
+    }
 }
 
 pub fn continue_on_error(arg_matches: &ArgMatches) -> bool {
@@ -125,11 +138,58 @@ pub fn cookie_output_file(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "cookies_output_file")
 }
 
+pub fn curl_file(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "curl")
+}
+
+pub fn data(arg_matches: &ArgMatches) -> Result<Option<Vec<HashMap<String, Value>>>, OptionsError> {
+    match get::<String>(arg_matches, "data") {
+        None => Ok(None),
+        Some(filename) => {
+            let rows = data::parse(&filename)?;
+            Ok(Some(rows))
+        }
+    }
+}
+
 pub fn delay(arg_matches: &ArgMatches) -> Duration {
     let millis = get::<u64>(arg_matches, "delay").unwrap();
     Duration::from_millis(millis)
 }
 
+/// Returns true if the requests are rendered without being sent, an error if `--dry-run` is used
+/// with `--watch`.
+pub fn dry_run(arg_matches: &ArgMatches) -> Result<bool, OptionsError> {
+    if !has_flag(arg_matches, "dry_run") {
+        return Ok(false);
+    }
+    check_conflicts(arg_matches, "--dry-run", &[("watch", "--watch")])?;
+    Ok(true)
+}
+
+pub fn dry_run_format(arg_matches: &ArgMatches) -> DryRunFormat {
+    let format = get::<String>(arg_matches, "dry_run_format");
+    match format.as_deref() {
+        Some("curl") => DryRunFormat::Curl,
+        Some("json") => DryRunFormat::Json,
+        _ => DryRunFormat::Http,
+    }
+}
+
+/// Returns the duration of a load test.
+pub fn duration(arg_matches: &ArgMatches) -> Result<Duration, OptionsError> {
+    let value = get::<String>(arg_matches, "duration").unwrap();
+    match duration::parse(&value) {
+        Ok(duration) => Ok(duration),
+        Err(message) => Err(OptionsError::Error(message)),
+    }
+}
+
+/// Returns the name of the only entry to run.
+pub fn entry_name(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "entry")
+}
+
 pub fn error_format(arg_matches: &ArgMatches) -> ErrorFormat {
     let error_format = get::<String>(arg_matches, "error_format");
     match error_format.as_deref() {
@@ -139,6 +199,10 @@ pub fn error_format(arg_matches: &ArgMatches) -> ErrorFormat {
     }
 }
 
+pub fn exclude_tags(arg_matches: &ArgMatches) -> Vec<String> {
+    get_strings(arg_matches, "exclude_tag").unwrap_or_default()
+}
+
 pub fn file_root(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "file_root")
 }
@@ -147,6 +211,20 @@ pub fn follow_location(arg_matches: &ArgMatches) -> bool {
     has_flag(arg_matches, "follow_location")
 }
 
+/// Returns the first entry to run, given by its index (starting at 1) or by its name.
+pub fn from_entry(arg_matches: &ArgMatches) -> Result<Option<FromEntry>, OptionsError> {
+    let Some(value) = get::<String>(arg_matches, "from_entry") else {
+        return Ok(None);
+    };
+    match value.parse::<usize>() {
+        Ok(0) => Err(OptionsError::Error(
+            "--from-entry index must be greater than 0".to_string(),
+        )),
+        Ok(index) => Ok(Some(FromEntry::Index(index))),
+        Err(_) => Ok(Some(FromEntry::Name(value))),
+    }
+}
+
 pub fn html_dir(arg_matches: &ArgMatches) -> Result<Option<PathBuf>, OptionsError> {
     if let Some(dir) = get::<String>(arg_matches, "report_html") {
         let path = Path::new(&dir);
@@ -218,8 +296,11 @@ pub fn input_files(arg_matches: &ArgMatches) -> Result<Vec<String>, OptionsError
     Ok(files)
 }
 
-pub fn insecure(arg_matches: &ArgMatches) -> bool {
-    has_flag(arg_matches, "insecure")
+pub fn insecure(arg_matches: &ArgMatches, profile: Option<&Profile>) -> bool {
+    if has_flag(arg_matches, "no_insecure") {
+        return false;
+    }
+    has_flag(arg_matches, "insecure") || profile.and_then(|p| p.insecure) == Some(true)
 }
 
 pub fn interactive(arg_matches: &ArgMatches) -> bool {
@@ -236,10 +317,40 @@ pub fn ip_resolve(arg_matches: &ArgMatches) -> Option<IpResolve> {
     }
 }
 
+/// Returns the maximum number of Hurl files run in parallel (1 if files are run sequentially).
+pub fn jobs(arg_matches: &ArgMatches) -> usize {
+    get::<u32>(arg_matches, "jobs").map_or(1, |x| x as usize)
+}
+
+pub fn json_match(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "json_match")
+}
+
 pub fn junit_file(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "report_junit")
 }
 
+/// Returns `true` if the input files are run as a load test.
+///
+/// The options running the input files with other variables or in another way are rejected with
+/// a load test.
+pub fn load(arg_matches: &ArgMatches) -> Result<bool, OptionsError> {
+    if !has_flag(arg_matches, "load") {
+        return Ok(false);
+    }
+    let options = [
+        ("data", "--data"),
+        ("dry_run", "--dry-run"),
+        ("jobs", "--jobs"),
+        ("serve", "--serve"),
+        ("setup", "--setup"),
+        ("teardown", "--teardown"),
+        ("watch", "--watch"),
+    ];
+    check_conflicts(arg_matches, "--load", &options)?;
+    Ok(true)
+}
+
 pub fn max_redirect(arg_matches: &ArgMatches) -> Option<usize> {
     match get::<i32>(arg_matches, "max_redirects").unwrap() {
         m if m == -1 => None,
@@ -247,10 +358,35 @@ pub fn max_redirect(arg_matches: &ArgMatches) -> Option<usize> {
     }
 }
 
+pub fn merge_junit(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "merge_junit")
+}
+
 pub fn no_proxy(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "noproxy")
 }
 
+pub fn openapi(arg_matches: &ArgMatches) -> Result<Option<OpenApi>, OptionsError> {
+    let filename = match get::<String>(arg_matches, "openapi") {
+        None => return Ok(None),
+        Some(filename) => filename,
+    };
+    let content = match std::fs::read_to_string(&filename) {
+        Ok(content) => content,
+        Err(e) => {
+            return Err(OptionsError::Error(format!(
+                "OpenAPI file {filename} can not be read: {e}"
+            )))
+        }
+    };
+    match OpenApi::parse(&content) {
+        Ok(openapi) => Ok(Some(openapi)),
+        Err(e) => Err(OptionsError::Error(format!(
+            "OpenAPI file {filename} is invalid: {e}"
+        ))),
+    }
+}
+
 pub fn output(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "output")
 }
@@ -269,24 +405,74 @@ pub fn path_as_is(arg_matches: &ArgMatches) -> bool {
     has_flag(arg_matches, "path_as_is")
 }
 
+pub fn port(arg_matches: &ArgMatches) -> u16 {
+    get::<u16>(arg_matches, "port").unwrap()
+}
+
+pub fn profile(arg_matches: &ArgMatches) -> Result<Option<Profile>, OptionsError> {
+    match get::<String>(arg_matches, "env") {
+        None => Ok(None),
+        Some(name) => {
+            let dir = match env::current_dir() {
+                Ok(dir) => dir,
+                Err(e) => return Err(OptionsError::Error(e.to_string())),
+            };
+            let profile = profile::load(&name, &dir)?;
+            Ok(Some(profile))
+        }
+    }
+}
+
 pub fn progress_bar(arg_matches: &ArgMatches) -> bool {
     let verbose = verbose(arg_matches) || very_verbose(arg_matches);
     test(arg_matches)
         && !verbose
         && !interactive(arg_matches)
+        && jobs(arg_matches) == 1
         && !is_ci()
         && io::stderr().is_terminal()
 }
 
-pub fn proxy(arg_matches: &ArgMatches) -> Option<String> {
-    get::<String>(arg_matches, "proxy")
+pub fn proxy(arg_matches: &ArgMatches, profile: Option<&Profile>) -> Option<String> {
+    get::<String>(arg_matches, "proxy").or_else(|| profile.and_then(|p| p.proxy.clone()))
+}
+
+pub fn record_file(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "record")
+}
+
+pub fn replay(arg_matches: &ArgMatches) -> Result<Option<Cassette>, OptionsError> {
+    let filename = match get::<String>(arg_matches, "replay") {
+        None => return Ok(None),
+        Some(filename) => filename,
+    };
+    let content = match std::fs::read_to_string(&filename) {
+        Ok(content) => content,
+        Err(e) => {
+            return Err(OptionsError::Error(format!(
+                "Cassette file {filename} can not be read: {e}"
+            )))
+        }
+    };
+    match Cassette::parse(&content) {
+        Ok(cassette) => Ok(Some(cassette)),
+        Err(e) => Err(OptionsError::Error(format!(
+            "Cassette file {filename} is invalid: {e}"
+        ))),
+    }
 }
 
 pub fn resolves(arg_matches: &ArgMatches) -> Vec<String> {
     get_strings(arg_matches, "resolve").unwrap_or_default()
 }
 
-pub fn retry(arg_matches: &ArgMatches) -> Retry {
+pub fn retry(arg_matches: &ArgMatches, profile: Option<&Profile>) -> Retry {
+    // `--retry` has a default value: the profile is only used if the option is not explicitly set.
+    if arg_matches.value_source("retry") != Some(ValueSource::CommandLine) {
+        if let Some(retry) = profile.and_then(|p| p.retry) {
+            return retry;
+        }
+    }
     match get::<i32>(arg_matches, "retry").unwrap() {
         -1 => Retry::Infinite,
         0 => Retry::None,
@@ -294,19 +480,101 @@ pub fn retry(arg_matches: &ArgMatches) -> Retry {
     }
 }
 
+pub fn retry_after(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "retry_after")
+}
+
+pub fn retry_backoff(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "retry_backoff")
+}
+
 pub fn retry_interval(arg_matches: &ArgMatches) -> Duration {
     let value = get::<u64>(arg_matches, "retry_interval").unwrap();
     Duration::from_millis(value)
 }
 
+pub fn retry_jitter(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "retry_jitter")
+}
+
+pub fn retry_max_interval(arg_matches: &ArgMatches) -> Option<Duration> {
+    get::<u64>(arg_matches, "retry_max_interval").map(Duration::from_millis)
+}
+
+pub fn retry_max_time(arg_matches: &ArgMatches) -> Option<Duration> {
+    get::<u64>(arg_matches, "retry_max_time").map(Duration::from_millis)
+}
+
+/// Returns the secret variables defined with `--secret`.
+///
+/// Contrary to `--variable`, a secret value is never typed: it's always a string, so it can be
+/// redacted as it has been written.
+pub fn secrets(matches: &ArgMatches) -> Result<HashMap<String, Value>, OptionsError> {
+    let mut secrets = HashMap::new();
+    if let Some(input) = get_strings(matches, "secret") {
+        for s in input {
+            match s.split_once('=') {
+                Some((name, value)) => {
+                    secrets.insert(name.to_string(), Value::String(value.to_string()));
+                }
+                None => {
+                    return Err(OptionsError::Error(format!(
+                        "Missing value for secret {s}!"
+                    )))
+                }
+            }
+        }
+    }
+    Ok(secrets)
+}
+
+pub fn seed(arg_matches: &ArgMatches) -> Option<u64> {
+    get::<u64>(arg_matches, "seed")
+}
+
+pub fn serve(arg_matches: &ArgMatches) -> Result<bool, OptionsError> {
+    if !has_flag(arg_matches, "serve") {
+        return Ok(false);
+    }
+    let options = [("dry_run", "--dry-run"), ("watch", "--watch")];
+    check_conflicts(arg_matches, "--serve", &options)?;
+    Ok(true)
+}
+
+pub fn setup(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "setup")
+}
+
+pub fn shard(arg_matches: &ArgMatches) -> Result<Option<Shard>, OptionsError> {
+    match get::<String>(arg_matches, "shard") {
+        None => Ok(None),
+        Some(value) => match Shard::parse(&value) {
+            Ok(shard) => Ok(Some(shard)),
+            Err(message) => Err(OptionsError::Error(message)),
+        },
+    }
+}
+
+pub fn shard_timings(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "shard_timings")
+}
+
 pub fn ssl_no_revoke(arg_matches: &ArgMatches) -> bool {
     has_flag(arg_matches, "ssl_no_revoke")
 }
 
+pub fn tags(arg_matches: &ArgMatches) -> Vec<String> {
+    get_strings(arg_matches, "tag").unwrap_or_default()
+}
+
 pub fn tap_file(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "report_tap")
 }
 
+pub fn teardown(arg_matches: &ArgMatches) -> Option<String> {
+    get::<String>(arg_matches, "teardown")
+}
+
 pub fn test(arg_matches: &ArgMatches) -> bool {
     has_flag(arg_matches, "test")
 }
@@ -320,6 +588,10 @@ pub fn to_entry(arg_matches: &ArgMatches) -> Option<usize> {
     get::<u32>(arg_matches, "to_entry").map(|x| x as usize)
 }
 
+pub fn update_snapshots(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "update_snapshots")
+}
+
 pub fn user(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "user")
 }
@@ -328,9 +600,21 @@ pub fn user_agent(arg_matches: &ArgMatches) -> Option<String> {
     get::<String>(arg_matches, "user_agent")
 }
 
+/// Returns the number of concurrent users of a load test.
+pub fn users(arg_matches: &ArgMatches) -> usize {
+    get::<u32>(arg_matches, "users").unwrap() as usize
+}
+
 /// Returns a map of variables from the command line options `matches`.
-pub fn variables(matches: &ArgMatches) -> Result<HashMap<String, Value>, OptionsError> {
-    let mut variables = HashMap::new();
+pub fn variables(
+    matches: &ArgMatches,
+    profile: Option<&Profile>,
+) -> Result<HashMap<String, Value>, OptionsError> {
+    // Variables of the profile have the lowest precedence.
+    let mut variables = match profile {
+        Some(profile) => profile.variables.clone(),
+        None => HashMap::new(),
+    };
 
     // Use environment variables prefix by HURL_
     for (env_name, env_value) in env::vars() {
@@ -380,6 +664,9 @@ pub fn variables(matches: &ArgMatches) -> Result<HashMap<String, Value>, Options
         }
     }
 
+    // Secrets are variables too, with the highest precedence.
+    variables.extend(secrets(matches)?);
+
     Ok(variables)
 }
 
@@ -391,6 +678,27 @@ pub fn very_verbose(arg_matches: &ArgMatches) -> bool {
     has_flag(arg_matches, "very_verbose")
 }
 
+pub fn watch(arg_matches: &ArgMatches) -> bool {
+    has_flag(arg_matches, "watch")
+}
+
+/// Returns an error if one of the `options` (an id and a name) is used on the command line with
+/// the option `name`.
+fn check_conflicts(
+    arg_matches: &ArgMatches,
+    name: &str,
+    options: &[(&str, &str)],
+) -> Result<(), OptionsError> {
+    for (id, option) in options {
+        if arg_matches.value_source(id) == Some(ValueSource::CommandLine) {
+            return Err(OptionsError::Error(format!(
+                "{option} can not be used with {name}"
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Returns a list of path names from the command line options `matches`.
 fn glob_files(matches: &ArgMatches) -> Result<Vec<String>, OptionsError> {
     let mut filenames = vec![];
@@ -16,7 +16,10 @@
  *
  */
 mod commands;
+mod data;
+mod duration;
 mod matches;
+mod profile;
 mod variables;
 
 use std::collections::HashMap;
@@ -26,13 +29,18 @@ use std::time::Duration;
 
 use clap::ArgMatches;
 use hurl::http;
-use hurl::http::RequestedHttpVersion;
+use hurl::http::{Cassette, RequestedHttpVersion};
+use hurl::openapi::OpenApi;
+use hurl::output::DryRunFormat;
 use hurl::util::logger::{LoggerOptions, LoggerOptionsBuilder, Verbosity};
 use hurl::util::path::ContextDir;
+use hurl::util::term::Stderr;
 use hurl_core::ast::{Entry, Retry};
 
+pub use self::profile::Profile;
 use crate::cli;
-use crate::runner::{RunnerOptions, RunnerOptionsBuilder, Value};
+use crate::cli::shard::Shard;
+use crate::runner::{FromEntry, RunnerOptions, RunnerOptionsBuilder, Value};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Options {
@@ -47,10 +55,18 @@ pub struct Options {
     pub continue_on_error: bool,
     pub cookie_input_file: Option<String>,
     pub cookie_output_file: Option<String>,
+    pub curl_file: Option<String>,
+    pub data: Option<Vec<HashMap<String, Value>>>,
     pub delay: Duration,
+    pub dry_run: bool,
+    pub dry_run_format: DryRunFormat,
+    pub duration: Duration,
+    pub entry_name: Option<String>,
     pub error_format: ErrorFormat,
+    pub exclude_tags: Vec<String>,
     pub file_root: Option<String>,
     pub follow_location: bool,
+    pub from_entry: Option<FromEntry>,
     pub html_dir: Option<PathBuf>,
     pub http_version: Option<HttpVersion>,
     pub ignore_asserts: bool,
@@ -59,27 +75,52 @@ pub struct Options {
     pub insecure: bool,
     pub interactive: bool,
     pub ip_resolve: Option<IpResolve>,
+    pub jobs: usize,
+    pub json_match: bool,
     pub junit_file: Option<String>,
+    pub load: bool,
     pub max_redirect: Option<usize>,
+    pub merge_junit: Option<String>,
     pub no_proxy: Option<String>,
+    pub openapi: Option<OpenApi>,
     pub output: Option<String>,
     pub output_type: OutputType,
     pub path_as_is: bool,
+    pub port: u16,
+    pub profile: Option<Profile>,
     pub progress_bar: bool,
     pub proxy: Option<String>,
+    pub record_file: Option<String>,
+    pub replay: Option<Cassette>,
     pub resolves: Vec<String>,
     pub retry: Retry,
+    pub retry_after: bool,
+    pub retry_backoff: bool,
     pub retry_interval: Duration,
+    pub retry_jitter: bool,
+    pub retry_max_interval: Option<Duration>,
+    pub retry_max_time: Option<Duration>,
+    pub secrets: HashMap<String, Value>,
+    pub seed: Option<u64>,
+    pub serve: bool,
+    pub setup: Option<String>,
+    pub shard: Option<Shard>,
+    pub shard_timings: Option<String>,
     pub ssl_no_revoke: bool,
+    pub tags: Vec<String>,
     pub tap_file: Option<String>,
+    pub teardown: Option<String>,
     pub test: bool,
     pub timeout: Duration,
     pub to_entry: Option<usize>,
+    pub update_snapshots: bool,
     pub user: Option<String>,
     pub user_agent: Option<String>,
+    pub users: usize,
     pub variables: HashMap<String, Value>,
     pub verbose: bool,
     pub very_verbose: bool,
+    pub watch: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -175,11 +216,20 @@ pub fn parse() -> Result<Options, OptionsError> {
         .arg(commands::continue_on_error())
         .arg(commands::cookies_input_file())
         .arg(commands::cookies_output_file())
+        .arg(commands::curl())
+        .arg(commands::data())
         .arg(commands::delay())
+        .arg(commands::dry_run())
+        .arg(commands::dry_run_format())
+        .arg(commands::duration())
+        .arg(commands::entry())
+        .arg(commands::env())
         .arg(commands::error_format())
+        .arg(commands::exclude_tag())
         .arg(commands::fail_at_end())
         .arg(commands::file_root())
         .arg(commands::follow_location())
+        .arg(commands::from_entry())
         .arg(commands::glob())
         .arg(commands::http10())
         .arg(commands::http11())
@@ -192,33 +242,58 @@ pub fn parse() -> Result<Options, OptionsError> {
         .arg(commands::interactive())
         .arg(commands::ipv4())
         .arg(commands::ipv6())
+        .arg(commands::jobs())
         .arg(commands::json())
+        .arg(commands::json_match())
+        .arg(commands::load())
         .arg(commands::max_redirects())
         .arg(commands::max_time())
+        .arg(commands::merge_junit())
         .arg(commands::no_color())
+        .arg(commands::no_insecure())
         .arg(commands::no_output())
         .arg(commands::noproxy())
+        .arg(commands::openapi())
         .arg(commands::output())
         .arg(commands::path_as_is())
+        .arg(commands::port())
         .arg(commands::proxy())
+        .arg(commands::record())
+        .arg(commands::replay())
         .arg(commands::report_html())
         .arg(commands::report_junit())
         .arg(commands::report_tap())
         .arg(commands::resolve())
         .arg(commands::retry())
+        .arg(commands::retry_after())
+        .arg(commands::retry_backoff())
         .arg(commands::retry_interval())
+        .arg(commands::retry_jitter())
+        .arg(commands::retry_max_interval())
+        .arg(commands::retry_max_time())
+        .arg(commands::secret())
+        .arg(commands::seed())
+        .arg(commands::serve())
+        .arg(commands::setup())
+        .arg(commands::shard())
+        .arg(commands::shard_timings())
         .arg(commands::ssl_no_revoke())
+        .arg(commands::tag())
+        .arg(commands::teardown())
         .arg(commands::test())
         .arg(commands::to_entry())
+        .arg(commands::update_snapshots())
         .arg(commands::user_agent())
         .arg(commands::user())
+        .arg(commands::users())
         .arg(commands::variable())
         .arg(commands::variables_file())
         .arg(commands::verbose())
-        .arg(commands::very_verbose());
+        .arg(commands::very_verbose())
+        .arg(commands::watch());
 
     let arg_matches = command.try_get_matches_from_mut(env::args_os())?;
-    let opts = parse_matches(&arg_matches)?;
+    let mut opts = parse_matches(&arg_matches)?;
 
     // If we've no file input (either from the standard input or from the command line arguments),
     // we just print help and exit.
@@ -232,54 +307,111 @@ pub fn parse() -> Result<Options, OptionsError> {
             "Only save cookies for a unique session".to_string(),
         ));
     }
+
+    if opts.serve && opts.input_files.len() > 1 {
+        return Err(OptionsError::Error(
+            "Only one file can be served".to_string(),
+        ));
+    }
+
+    if opts.watch && opts.input_files.iter().any(|f| f == "-") {
+        return Err(OptionsError::Error(
+            "Standard input can not be watched".to_string(),
+        ));
+    }
+
+    if let Some(shard) = opts.shard {
+        let times = match &opts.shard_timings {
+            Some(filename) => match cli::shard::read_times(filename) {
+                Ok(times) => times,
+                Err(e) => return Err(OptionsError::Error(e.message)),
+            },
+            None => HashMap::new(),
+        };
+        opts.input_files = shard.select(&opts.input_files, &times);
+    }
     Ok(opts)
 }
 
 fn parse_matches(arg_matches: &ArgMatches) -> Result<Options, OptionsError> {
+    // The `--env` profile is loaded first, as it provides default values for other options.
+    let profile = matches::profile(arg_matches)?;
     let aws_sigv4 = matches::aws_sigv4(arg_matches);
-    let cacert_file = matches::cacert_file(arg_matches)?;
+    let cacert_file = matches::cacert_file(arg_matches, profile.as_ref())?;
     let client_cert_file = matches::client_cert_file(arg_matches)?;
     let client_key_file = matches::client_key_file(arg_matches)?;
     let color = matches::color(arg_matches);
     let compressed = matches::compressed(arg_matches);
     let connect_timeout = matches::connect_timeout(arg_matches);
-    let connects_to = matches::connects_to(arg_matches);
+    let connects_to = matches::connects_to(arg_matches, profile.as_ref());
     let continue_on_error = matches::continue_on_error(arg_matches);
     let cookie_input_file = matches::cookie_input_file(arg_matches);
     let cookie_output_file = matches::cookie_output_file(arg_matches);
+    let curl_file = matches::curl_file(arg_matches);
+    let data = matches::data(arg_matches)?;
     let delay = matches::delay(arg_matches);
+    let dry_run = matches::dry_run(arg_matches)?;
+    let dry_run_format = matches::dry_run_format(arg_matches);
+    let duration = matches::duration(arg_matches)?;
+    let entry_name = matches::entry_name(arg_matches);
     let error_format = matches::error_format(arg_matches);
+    let exclude_tags = matches::exclude_tags(arg_matches);
     let file_root = matches::file_root(arg_matches);
     let follow_location = matches::follow_location(arg_matches);
+    let from_entry = matches::from_entry(arg_matches)?;
     let html_dir = matches::html_dir(arg_matches)?;
     let http_version = matches::http_version(arg_matches);
     let ignore_asserts = matches::ignore_asserts(arg_matches);
     let include = matches::include(arg_matches);
     let input_files = matches::input_files(arg_matches)?;
-    let insecure = matches::insecure(arg_matches);
+    let insecure = matches::insecure(arg_matches, profile.as_ref());
     let interactive = matches::interactive(arg_matches);
     let ip_resolve = matches::ip_resolve(arg_matches);
+    let jobs = matches::jobs(arg_matches);
+    let json_match = matches::json_match(arg_matches);
     let junit_file = matches::junit_file(arg_matches);
+    let load = matches::load(arg_matches)?;
     let max_redirect = matches::max_redirect(arg_matches);
+    let merge_junit = matches::merge_junit(arg_matches);
     let no_proxy = matches::no_proxy(arg_matches);
+    let openapi = matches::openapi(arg_matches)?;
     let progress_bar = matches::progress_bar(arg_matches);
     let path_as_is = matches::path_as_is(arg_matches);
-    let proxy = matches::proxy(arg_matches);
+    let port = matches::port(arg_matches);
+    let proxy = matches::proxy(arg_matches, profile.as_ref());
+    let record_file = matches::record_file(arg_matches);
+    let replay = matches::replay(arg_matches)?;
     let output = matches::output(arg_matches);
     let output_type = matches::output_type(arg_matches);
     let resolves = matches::resolves(arg_matches);
-    let retry = matches::retry(arg_matches);
+    let retry = matches::retry(arg_matches, profile.as_ref());
+    let retry_after = matches::retry_after(arg_matches);
+    let retry_backoff = matches::retry_backoff(arg_matches);
     let retry_interval = matches::retry_interval(arg_matches);
+    let retry_jitter = matches::retry_jitter(arg_matches);
+    let retry_max_interval = matches::retry_max_interval(arg_matches);
+    let retry_max_time = matches::retry_max_time(arg_matches);
+    let secrets = matches::secrets(arg_matches)?;
+    let seed = matches::seed(arg_matches);
+    let serve = matches::serve(arg_matches)?;
+    let setup = matches::setup(arg_matches);
+    let shard = matches::shard(arg_matches)?;
+    let shard_timings = matches::shard_timings(arg_matches);
     let ssl_no_revoke = matches::ssl_no_revoke(arg_matches);
+    let tags = matches::tags(arg_matches);
     let tap_file = matches::tap_file(arg_matches);
+    let teardown = matches::teardown(arg_matches);
     let test = matches::test(arg_matches);
     let timeout = matches::timeout(arg_matches);
     let to_entry = matches::to_entry(arg_matches);
+    let update_snapshots = matches::update_snapshots(arg_matches);
     let user = matches::user(arg_matches);
     let user_agent = matches::user_agent(arg_matches);
-    let variables = matches::variables(arg_matches)?;
+    let users = matches::users(arg_matches);
+    let variables = matches::variables(arg_matches, profile.as_ref())?;
     let verbose = matches::verbose(arg_matches);
     let very_verbose = matches::very_verbose(arg_matches);
+    let watch = matches::watch(arg_matches);
     Ok(Options {
         aws_sigv4,
         cacert_file,
@@ -292,10 +424,18 @@ fn parse_matches(arg_matches: &ArgMatches) -> Result<Options, OptionsError> {
         continue_on_error,
         cookie_input_file,
         cookie_output_file,
+        curl_file,
+        data,
         delay,
+        dry_run,
+        dry_run_format,
+        duration,
+        entry_name,
         error_format,
+        exclude_tags,
         file_root,
         follow_location,
+        from_entry,
         html_dir,
         http_version,
         ignore_asserts,
@@ -304,27 +444,52 @@ fn parse_matches(arg_matches: &ArgMatches) -> Result<Options, OptionsError> {
         insecure,
         interactive,
         ip_resolve,
+        jobs,
+        json_match,
         junit_file,
+        load,
         max_redirect,
+        merge_junit,
         no_proxy,
+        openapi,
         path_as_is,
+        port,
+        profile,
         progress_bar,
         proxy,
+        record_file,
+        replay,
         output,
         output_type,
         resolves,
         retry,
+        retry_after,
+        retry_backoff,
         retry_interval,
+        retry_jitter,
+        retry_max_interval,
+        retry_max_time,
+        secrets,
+        seed,
+        serve,
+        setup,
+        shard,
+        shard_timings,
         ssl_no_revoke,
+        tags,
         tap_file,
+        teardown,
         test,
         timeout,
         to_entry,
+        update_snapshots,
         user,
         user_agent,
+        users,
         variables,
         verbose,
         very_verbose,
+        watch,
     })
 }
 
@@ -344,22 +509,14 @@ impl Options {
         let compressed = self.compressed;
         let connect_timeout = self.connect_timeout;
         let connects_to = self.connects_to.clone();
-        let file_root = match self.file_root {
-            Some(ref filename) => Path::new(filename),
-            None => {
-                if filename == "-" {
-                    current_dir
-                } else {
-                    let path = Path::new(filename);
-                    path.parent().unwrap()
-                }
-            }
-        };
-        let context_dir = ContextDir::new(current_dir, file_root);
+        let context_dir = self.context_dir(filename, current_dir);
         let continue_on_error = self.continue_on_error;
         let cookie_input_file = self.cookie_input_file.clone();
         let delay = self.delay;
+        let entry_name = self.entry_name.clone();
+        let exclude_tags = self.exclude_tags.clone();
         let follow_location = self.follow_location;
+        let from_entry = self.from_entry.clone();
         let http_version = match self.http_version {
             Some(version) => version.into(),
             None => RequestedHttpVersion::default(),
@@ -370,8 +527,10 @@ impl Options {
             Some(ip) => ip.into(),
             None => http::IpResolve::default(),
         };
+        let json_match = self.json_match;
         let max_redirect = self.max_redirect;
         let no_proxy = self.no_proxy.clone();
+        let openapi = self.openapi.clone();
         // FIXME:
         // When used globally (on the command line), `--output` writes the last successful request
         // to `output` file. We don't want to output every entry's response, so we initialise
@@ -415,12 +574,21 @@ impl Options {
             None
         };
         let proxy = self.proxy.clone();
+        let replay = self.replay.clone();
         let resolves = self.resolves.clone();
         let retry = self.retry;
+        let retry_after = self.retry_after;
+        let retry_backoff = self.retry_backoff;
         let retry_interval = self.retry_interval;
+        let retry_jitter = self.retry_jitter;
+        let retry_max_interval = self.retry_max_interval;
+        let retry_max_time = self.retry_max_time;
+        let seed = self.seed;
         let ssl_no_revoke = self.ssl_no_revoke;
+        let tags = self.tags.clone();
         let timeout = self.timeout;
         let to_entry = self.to_entry;
+        let update_snapshots = self.update_snapshots;
         let user = self.user.clone();
         let user_agent = self.user_agent.clone();
 
@@ -436,36 +604,73 @@ impl Options {
             .continue_on_error(continue_on_error)
             .context_dir(&context_dir)
             .cookie_input_file(cookie_input_file)
+            .entry_name(entry_name)
+            .exclude_tags(&exclude_tags)
             .follow_location(follow_location)
+            .from_entry(from_entry)
             .http_version(http_version)
             .ignore_asserts(ignore_asserts)
             .insecure(insecure)
             .ip_resolve(ip_resolve)
+            .json_match(json_match)
             .max_redirect(max_redirect)
             .no_proxy(no_proxy)
+            .openapi(openapi)
             .output(output)
             .path_as_is(path_as_is)
             .post_entry(post_entry)
             .pre_entry(pre_entry)
             .proxy(proxy)
+            .replay(replay)
             .resolves(&resolves)
             .retry(retry)
+            .retry_after(retry_after)
+            .retry_backoff(retry_backoff)
             .retry_interval(retry_interval)
+            .retry_jitter(retry_jitter)
+            .retry_max_interval(retry_max_interval)
+            .retry_max_time(retry_max_time)
+            .seed(seed)
             .ssl_no_revoke(ssl_no_revoke)
+            .tags(&tags)
             .timeout(timeout)
             .to_entry(to_entry)
+            .update_snapshots(update_snapshots)
             .user(user)
             .user_agent(user_agent)
             .build()
     }
 
-    pub fn to_logger_options(&self, filename: &str) -> LoggerOptions {
+    /// Returns the context directory used to run the Hurl file `filename`.
+    pub fn context_dir(&self, filename: &str, current_dir: &Path) -> ContextDir {
+        let file_root = match self.file_root {
+            Some(ref filename) => Path::new(filename),
+            None => {
+                if filename == "-" {
+                    current_dir
+                } else {
+                    let path = Path::new(filename);
+                    path.parent().unwrap()
+                }
+            }
+        };
+        ContextDir::new(current_dir, file_root)
+    }
+
+    pub fn to_logger_options(&self, filename: &str, stderr: &Stderr) -> LoggerOptions {
         let verbosity = Verbosity::from(self.verbose, self.very_verbose);
+        let secrets = self
+            .secrets
+            .values()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
         LoggerOptionsBuilder::new()
             .color(self.color)
             .error_format(self.error_format.into())
             .filename(filename)
             .progress_bar(self.progress_bar)
+            .secrets(&secrets)
+            .stderr(stderr)
             .test(self.test)
             .verbosity(verbosity)
             .build()
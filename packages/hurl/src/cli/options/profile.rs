@@ -0,0 +1,219 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use hurl_core::ast::Retry;
+
+use super::OptionsError;
+use crate::runner::{Number, Value};
+
+/// Name of the project config file declaring the environments.
+pub const CONFIG_FILENAME: &str = "hurl.toml";
+
+/// An environment of the project config file, selected with `--env`.
+///
+/// Values of an environment have the lowest precedence: they're overridden by the command line
+/// options, and its variables by the `HURL_*` environment variables, the `--variables-file`
+/// files and the `--variable` options.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    /// Name of the environment
+    pub name: String,
+    /// Path of the config file declaring the environment
+    pub file: PathBuf,
+    pub variables: HashMap<String, Value>,
+    pub cacert_file: Option<String>,
+    pub connects_to: Vec<String>,
+    pub insecure: Option<bool>,
+    pub proxy: Option<String>,
+    pub retry: Option<Retry>,
+}
+
+/// Loads the environment `name` from the project config file, looked up in the directory `dir`
+/// and its parents.
+pub fn load(name: &str, dir: &Path) -> Result<Profile, OptionsError> {
+    let file = match dir
+        .ancestors()
+        .map(|d| d.join(CONFIG_FILENAME))
+        .find(|f| f.exists())
+    {
+        Some(f) => f,
+        None => {
+            return Err(OptionsError::Error(format!(
+                "Environment {name} can not be loaded, no {CONFIG_FILENAME} file found"
+            )))
+        }
+    };
+    let content = match std::fs::read_to_string(&file) {
+        Ok(c) => c,
+        Err(e) => {
+            return Err(OptionsError::Error(format!(
+                "Config file {} can not be read: {e}",
+                file.display()
+            )))
+        }
+    };
+    parse(&content, name, &file)
+}
+
+/// Parses the environment `name` of the config file `file` whose content is `content`.
+///
+/// An environment is a `[env.<name>]` table, with a `variables` sub-table.
+fn parse(content: &str, name: &str, file: &Path) -> Result<Profile, OptionsError> {
+    let error = |message: String| OptionsError::Error(format!("{}: {message}", file.display()));
+    let config = match content.parse::<toml::Value>() {
+        Ok(c) => c,
+        Err(e) => return Err(error(e.to_string())),
+    };
+    let table = match config.get("env").and_then(|envs| envs.get(name)) {
+        Some(toml::Value::Table(table)) => table,
+        _ => return Err(error(format!("environment {name} is not defined"))),
+    };
+
+    let mut profile = Profile {
+        name: name.to_string(),
+        file: file.to_path_buf(),
+        variables: HashMap::new(),
+        cacert_file: None,
+        connects_to: vec![],
+        insecure: None,
+        proxy: None,
+        retry: None,
+    };
+    for (key, value) in table {
+        let invalid = || error(format!("invalid value for {key} in environment {name}"));
+        match (key.as_str(), value) {
+            ("variables", toml::Value::Table(variables)) => {
+                for (name, value) in variables {
+                    profile.variables.insert(name.clone(), eval_value(value));
+                }
+            }
+            ("cacert", toml::Value::String(cacert)) => {
+                // Relative paths are relative to the config file.
+                let dir = file.parent().unwrap_or(Path::new(""));
+                let path = dir.join(cacert);
+                if !path.exists() {
+                    return Err(error(format!(
+                        "cacert file {} does not exist",
+                        path.display()
+                    )));
+                }
+                profile.cacert_file = Some(path.display().to_string());
+            }
+            ("connect-to", toml::Value::Array(values)) => {
+                for value in values {
+                    match value.as_str() {
+                        Some(s) => profile.connects_to.push(s.to_string()),
+                        None => return Err(invalid()),
+                    }
+                }
+            }
+            ("insecure", toml::Value::Boolean(insecure)) => profile.insecure = Some(*insecure),
+            ("proxy", toml::Value::String(proxy)) => profile.proxy = Some(proxy.clone()),
+            ("retry", toml::Value::Integer(retry)) => {
+                profile.retry = match *retry {
+                    -1 => Some(Retry::Infinite),
+                    0 => Some(Retry::None),
+                    r if r > 0 => Some(Retry::Finite(r as usize)),
+                    _ => return Err(invalid()),
+                }
+            }
+            ("variables" | "cacert" | "connect-to" | "insecure" | "proxy" | "retry", _) => {
+                return Err(invalid())
+            }
+            _ => return Err(error(format!("unknown option {key} in environment {name}"))),
+        }
+    }
+    Ok(profile)
+}
+
+/// Converts a TOML `value` to a variable value.
+fn eval_value(value: &toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s.clone()),
+        toml::Value::Integer(i) => Value::Number(Number::Integer(*i)),
+        toml::Value::Float(f) => Value::Number(Number::Float(*f)),
+        toml::Value::Boolean(b) => Value::Bool(*b),
+        toml::Value::Datetime(d) => Value::String(d.to_string()),
+        toml::Value::Array(values) => Value::List(values.iter().map(eval_value).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .iter()
+                .map(|(name, value)| (name.clone(), eval_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let content = r#"
+[env.dev]
+insecure = true
+
+[env.staging]
+proxy = "http://localhost:3128"
+retry = 3
+connect-to = ["example.com:443:staging.local:443"]
+
+[env.staging.variables]
+host = "https://staging.example.com"
+user_id = 42
+"#;
+        let file = Path::new("hurl.toml");
+        assert_eq!(
+            parse(content, "staging", file).unwrap(),
+            Profile {
+                name: "staging".to_string(),
+                file: file.to_path_buf(),
+                variables: HashMap::from([
+                    (
+                        "host".to_string(),
+                        Value::String("https://staging.example.com".to_string())
+                    ),
+                    ("user_id".to_string(), Value::Number(Number::Integer(42))),
+                ]),
+                cacert_file: None,
+                connects_to: vec!["example.com:443:staging.local:443".to_string()],
+                insecure: None,
+                proxy: Some("http://localhost:3128".to_string()),
+                retry: Some(Retry::Finite(3)),
+            }
+        );
+        assert_eq!(parse(content, "dev", file).unwrap().insecure, Some(true));
+        assert_eq!(
+            parse(content, "prod", file).unwrap_err(),
+            OptionsError::Error("hurl.toml: environment prod is not defined".to_string())
+        );
+        assert_eq!(
+            parse("[env.dev]\nproxy = 1\n", "dev", file).unwrap_err(),
+            OptionsError::Error(
+                "hurl.toml: invalid value for proxy in environment dev".to_string()
+            )
+        );
+        assert_eq!(
+            parse("[env.dev]\nverbose = true\n", "dev", file).unwrap_err(),
+            OptionsError::Error("hurl.toml: unknown option verbose in environment dev".to_string())
+        );
+    }
+}
@@ -0,0 +1,100 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::BTreeMap;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// Runs `jobs` on a pool of `workers` threads.
+///
+/// Each job is processed by `run_job` on the first available worker. Results are passed to
+/// `on_completed` on the calling thread, in the order of `jobs`, whatever the order of completion
+/// of each job: the result of a job is kept until all the previous jobs have been completed.
+pub fn run<J, R, F, C>(jobs: Vec<J>, workers: usize, run_job: F, mut on_completed: C)
+where
+    J: Send,
+    R: Send,
+    F: Fn(J) -> R + Sync,
+    C: FnMut(R),
+{
+    let queue = Mutex::new(jobs.into_iter().enumerate());
+    let run_job = &run_job;
+    let queue = &queue;
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..workers.max(1) {
+            let tx = tx.clone();
+            s.spawn(move || loop {
+                // We only keep the lock on the queue while taking the next job.
+                let job = queue.lock().unwrap().next();
+                let (index, job) = match job {
+                    Some(job) => job,
+                    None => break,
+                };
+                let result = run_job(job);
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        // Workers own the remaining senders: the receiving loop ends when every worker is done.
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in rx {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                on_completed(result);
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn results_are_ordered() {
+        // Jobs are sleeping durations: the first jobs are the last to complete.
+        let jobs = vec![40, 30, 20, 10, 0];
+        let mut results = vec![];
+        run(
+            jobs,
+            5,
+            |ms| {
+                thread::sleep(Duration::from_millis(ms));
+                ms
+            },
+            |ms| results.push(ms),
+        );
+        assert_eq!(results, vec![40, 30, 20, 10, 0]);
+    }
+
+    #[test]
+    fn run_with_one_worker() {
+        let jobs = vec!["a", "b", "c"];
+        let mut results = vec![];
+        run(jobs, 1, |s| s.to_uppercase(), |s| results.push(s));
+        assert_eq!(results, vec!["A", "B", "C"]);
+    }
+}
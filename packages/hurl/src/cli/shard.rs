@@ -0,0 +1,187 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::HashMap;
+use std::fmt;
+
+use hurl::report::junit;
+
+use crate::cli::{self, CliError};
+
+/// A subset of the input files, for splitting a run over several machines.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// Index of this shard, starting at 1.
+    pub index: usize,
+    /// Total number of shards.
+    pub total: usize,
+}
+
+impl Shard {
+    /// Parses a shard from a string `INDEX/TOTAL`, like `2/5`.
+    pub fn parse(value: &str) -> Result<Shard, String> {
+        let error = format!("invalid shard <{value}>, expected INDEX/TOTAL (like 2/5)");
+        let (index, total) = match value.split_once('/') {
+            Some((index, total)) => (index.trim(), total.trim()),
+            None => return Err(error),
+        };
+        let (index, total) = match (index.parse::<usize>(), total.parse::<usize>()) {
+            (Ok(index), Ok(total)) => (index, total),
+            _ => return Err(error),
+        };
+        if total == 0 || index == 0 || index > total {
+            return Err(format!(
+                "invalid shard <{value}>, INDEX must be between 1 and TOTAL"
+            ));
+        }
+        Ok(Shard { index, total })
+    }
+
+    /// Returns the files of `files` belonging to this shard, in the `files` order.
+    ///
+    /// Without `times`, files are dealt round-robin between the shards. With `times` (durations
+    /// in milliseconds by filename), each file, from the longest to the shortest, is given to the
+    /// shard with the lowest total duration. Files without known duration are given the average
+    /// duration. In both cases, the selection only depends on `files` and `times`, so every
+    /// machine computes the same shards.
+    pub fn select(&self, files: &[String], times: &HashMap<String, u64>) -> Vec<String> {
+        let shards = if times.is_empty() {
+            (0..files.len()).map(|i| i % self.total).collect::<Vec<_>>()
+        } else {
+            balance(files, times, self.total)
+        };
+        files
+            .iter()
+            .zip(shards)
+            .filter(|(_, shard)| *shard == self.index - 1)
+            .map(|(file, _)| file.clone())
+            .collect()
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.total)
+    }
+}
+
+/// Returns the shard (starting at 0) of each file of `files`, balancing the total durations of
+/// the `total` shards.
+fn balance(files: &[String], times: &HashMap<String, u64>, total: usize) -> Vec<usize> {
+    let average = times.values().sum::<u64>() / times.len() as u64;
+    let mut by_time = files
+        .iter()
+        .enumerate()
+        .map(|(i, f)| (i, times.get(f).copied().unwrap_or(average)))
+        .collect::<Vec<_>>();
+    // Longest files first, ties are kept in the input order.
+    by_time.sort_by(|(i1, t1), (i2, t2)| t2.cmp(t1).then(i1.cmp(i2)));
+
+    let mut loads = vec![0; total];
+    let mut shards = vec![0; files.len()];
+    for (i, time) in by_time {
+        // The first shard with the lowest load.
+        let (shard, _) = loads
+            .iter()
+            .enumerate()
+            .min_by(|(s1, l1), (s2, l2)| l1.cmp(l2).then(s1.cmp(s2)))
+            .unwrap();
+        loads[shard] += time;
+        shards[i] = shard;
+    }
+    shards
+}
+
+/// Reads the durations of a previous run from the report `filename`, by input filename.
+///
+/// The report is either a JUnit XML report, or the JSON output of Hurl (one JSON object by line).
+pub fn read_times(filename: &str) -> Result<HashMap<String, u64>, CliError> {
+    let content = cli::read_to_string(filename)?;
+    if content.trim_start().starts_with('<') {
+        let times = junit::read_times(filename)?;
+        return Ok(times.into_iter().collect());
+    }
+    let mut times = HashMap::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let value = match serde_json::from_str::<serde_json::Value>(line) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(CliError {
+                    message: format!("Issue parsing JSON report {filename}: {e}"),
+                })
+            }
+        };
+        if let (Some(name), Some(time)) = (value["filename"].as_str(), value["time"].as_u64()) {
+            times.insert(name.to_string(), time);
+        }
+    }
+    Ok(times)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(count: usize) -> Vec<String> {
+        (1..=count).map(|i| format!("test{i}.hurl")).collect()
+    }
+
+    #[test]
+    fn parse_shard() {
+        assert_eq!(Shard::parse("2/5").unwrap(), Shard { index: 2, total: 5 });
+        assert_eq!(Shard::parse("1/1").unwrap(), Shard { index: 1, total: 1 });
+        assert!(Shard::parse("0/5").is_err());
+        assert!(Shard::parse("6/5").is_err());
+        assert!(Shard::parse("1/0").is_err());
+        assert!(Shard::parse("2").is_err());
+        assert!(Shard::parse("a/b").is_err());
+    }
+
+    #[test]
+    fn select_by_count() {
+        let files = files(7);
+        let times = HashMap::new();
+        let shard = |index| Shard { index, total: 3 }.select(&files, &times);
+        assert_eq!(shard(1), vec!["test1.hurl", "test4.hurl", "test7.hurl"]);
+        assert_eq!(shard(2), vec!["test2.hurl", "test5.hurl"]);
+        assert_eq!(shard(3), vec!["test3.hurl", "test6.hurl"]);
+    }
+
+    #[test]
+    fn select_by_times() {
+        let files = files(5);
+        let times = HashMap::from([
+            ("test1.hurl".to_string(), 100),
+            ("test2.hurl".to_string(), 1000),
+            ("test3.hurl".to_string(), 300),
+            ("test4.hurl".to_string(), 500),
+        ]);
+        // test5.hurl has the average duration (475 ms).
+        let shard = |index| Shard { index, total: 2 }.select(&files, &times);
+        assert_eq!(shard(1), vec!["test1.hurl", "test2.hurl"]);
+        assert_eq!(shard(2), vec!["test3.hurl", "test4.hurl", "test5.hurl"]);
+    }
+
+    #[test]
+    fn more_shards_than_files() {
+        let files = files(2);
+        let times = HashMap::new();
+        assert!(Shard { index: 3, total: 3 }
+            .select(&files, &times)
+            .is_empty());
+    }
+}
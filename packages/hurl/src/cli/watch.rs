@@ -0,0 +1,314 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use hurl::util::path::ContextDir;
+use hurl_core::ast::{Body, Bytes, HurlFile, MultipartParam};
+use hurl_core::parser;
+
+use crate::cli;
+use crate::cli::CliError;
+
+/// Interval between two checks of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Watches Hurl input files and the files they reference.
+///
+/// Changes are detected by polling the modification time of each file.
+#[derive(Default)]
+pub struct Watcher {
+    inputs: Vec<WatchedInput>,
+}
+
+/// An input file, with all the files that can affect its run.
+struct WatchedInput {
+    filename: String,
+    /// The input file itself and its referenced files, with their last modification time
+    /// (`None` if the file doesn't exist).
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+}
+
+impl Watcher {
+    /// Returns a new empty watcher.
+    pub fn new() -> Self {
+        Watcher::default()
+    }
+
+    /// Watches the input file `filename`, and the files it references, resolved with `context_dir`.
+    ///
+    /// If `filename` is already watched, its list of referenced files is updated.
+    pub fn watch(&mut self, filename: &str, context_dir: &ContextDir) {
+        let mut paths = vec![];
+        // Included files are Hurl files too: we watch the files they reference and include.
+        let mut hurl_files = vec![PathBuf::from(filename)];
+        while let Some(path) = hurl_files.pop() {
+            if paths.contains(&path) {
+                continue;
+            }
+            paths.push(path.clone());
+            // If the file can't be read or parsed, we only watch the file itself, until it has
+            // been fixed.
+            let content = match cli::read_to_string(&path.display().to_string()) {
+                Ok(content) => content,
+                Err(_) => continue,
+            };
+            let hurl_file = match parser::parse_hurl_file(&content) {
+                Ok(hurl_file) => hurl_file,
+                Err(_) => continue,
+            };
+            for file in referenced_files(&hurl_file) {
+                let path = context_dir.get_path(&file);
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+            hurl_files.extend(
+                included_files(&hurl_file)
+                    .iter()
+                    .rev()
+                    .map(|f| context_dir.get_path(f)),
+            );
+        }
+        let files = paths
+            .into_iter()
+            .map(|p| {
+                let modified = modified(&p);
+                (p, modified)
+            })
+            .collect();
+        let input = WatchedInput {
+            filename: filename.to_string(),
+            files,
+        };
+        match self.inputs.iter_mut().find(|i| i.filename == filename) {
+            Some(i) => *i = input,
+            None => self.inputs.push(input),
+        }
+    }
+
+    /// Blocks until some watched files have changed, and returns the input files affected by
+    /// these changes, in the watch order.
+    pub fn wait_for_changes(&mut self) -> Vec<String> {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let changed = self.changed();
+            if !changed.is_empty() {
+                return changed;
+            }
+        }
+    }
+
+    /// Returns the input files affected by a change since the last check.
+    fn changed(&mut self) -> Vec<String> {
+        let mut changed = vec![];
+        for input in self.inputs.iter_mut() {
+            let mut has_changed = false;
+            for (path, last_modified) in input.files.iter_mut() {
+                let modified = modified(path);
+                if modified != *last_modified {
+                    *last_modified = modified;
+                    has_changed = true;
+                }
+            }
+            if has_changed {
+                changed.push(input.filename.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// Returns the last modification time of the file at `path`, `None` if it doesn't exist.
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Returns the filenames referenced by a `hurl_file`: files used as request and response bodies,
+/// and files of multipart form data.
+fn referenced_files(hurl_file: &HurlFile) -> Vec<String> {
+    let mut files = vec![];
+    for entry in &hurl_file.entries {
+        if let Some(Body {
+            value: Bytes::File(file),
+            ..
+        }) = &entry.request.body
+        {
+            files.push(file.filename.value.clone());
+        }
+        for param in entry.request.multipart_form_data() {
+            if let MultipartParam::FileParam(param) = param {
+                files.push(param.value.filename.value.clone());
+            }
+        }
+        if let Some(response) = &entry.response {
+            if let Some(Body {
+                value: Bytes::File(file),
+                ..
+            }) = &response.body
+            {
+                files.push(file.filename.value.clone());
+            }
+        }
+    }
+    files
+}
+
+/// Returns the filenames of the Hurl files included by a `hurl_file`, with `include` options.
+fn included_files(hurl_file: &HurlFile) -> Vec<String> {
+    hurl_file
+        .entries
+        .iter()
+        .flat_map(|e| e.includes())
+        .map(|f| f.value)
+        .collect()
+}
+
+/// The reports that are appended to on each write (JUnit, TAP and HTML index), with their content
+/// before the first run.
+///
+/// In watch mode, reports are written after each re-run: they're restored to their initial content
+/// first, so that the same runs are not reported many times.
+pub struct Reports {
+    files: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+impl Reports {
+    /// Saves the current content of the report files `paths` (`None` if a file doesn't exist).
+    pub fn save(paths: &[PathBuf]) -> Self {
+        let files = paths
+            .iter()
+            .map(|p| (p.clone(), fs::read(p).ok()))
+            .collect();
+        Reports { files }
+    }
+
+    /// Restores the report files to their saved content, removing the files that didn't exist.
+    pub fn restore(&self) -> Result<(), CliError> {
+        for (path, content) in &self.files {
+            let result = match content {
+                Some(content) => fs::write(path, content),
+                None if path.exists() => fs::remove_file(path),
+                None => Ok(()),
+            };
+            if let Err(e) = result {
+                return Err(CliError {
+                    message: format!("Issue restoring report {}: {e}", path.display()),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_files() {
+        let content = r#"POST http://localhost:8000/data
+file,data.bin;
+HTTP 200
+file,expected.json;
+
+POST http://localhost:8000/upload
+[MultipartFormData]
+field1: value1
+upload1: file,hello.txt;
+upload2: file,hello.html; text/html
+HTTP 200
+"#;
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        assert_eq!(
+            referenced_files(&hurl_file),
+            vec!["data.bin", "expected.json", "hello.txt", "hello.html"]
+        );
+    }
+
+    #[test]
+    fn test_changed() {
+        let dir = std::env::temp_dir().join(format!("hurl_watch_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("test.hurl");
+        let data = dir.join("data.bin");
+        let included = dir.join("included.hurl");
+        let included_data = dir.join("included.bin");
+        fs::write(
+            &input,
+            "POST http://localhost:8000/data\n[Options]\ninclude: included.hurl\nfile,data.bin;\n",
+        )
+        .unwrap();
+        fs::write(
+            &included,
+            "POST http://localhost:8000/included\nfile,included.bin;\n",
+        )
+        .unwrap();
+
+        let context_dir = ContextDir::new(&dir, &dir);
+        let mut watcher = Watcher::new();
+        let filename = input.to_str().unwrap();
+        watcher.watch(filename, &context_dir);
+        assert!(watcher.changed().is_empty());
+
+        // A referenced file is created.
+        fs::write(&data, "hello").unwrap();
+        assert_eq!(watcher.changed(), vec![filename.to_string()]);
+        assert!(watcher.changed().is_empty());
+
+        // A file referenced by an included file is created.
+        fs::write(&included_data, "hello").unwrap();
+        assert_eq!(watcher.changed(), vec![filename.to_string()]);
+        assert!(watcher.changed().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_included_files() {
+        let content = r#"GET http://localhost:8000/a
+[Options]
+include: login.hurl
+include: setup.hurl
+
+GET http://localhost:8000/b
+"#;
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        assert_eq!(included_files(&hurl_file), vec!["login.hurl", "setup.hurl"]);
+    }
+
+    #[test]
+    fn test_restore_reports() {
+        let dir = std::env::temp_dir().join(format!("hurl_watch_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let existing = dir.join("report.xml");
+        let new = dir.join("report.tap");
+        fs::write(&existing, "initial").unwrap();
+
+        let reports = Reports::save(&[existing.clone(), new.clone()]);
+        fs::write(&existing, "initial and run").unwrap();
+        fs::write(&new, "run").unwrap();
+        reports.restore().unwrap();
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "initial");
+        assert!(!new.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use base64::engine::general_purpose;
+use base64::Engine;
+
+use crate::http::{Call, Header, HttpVersion, Request, Response, Timings};
+use crate::util::redact::redact;
+
+/// A list of recorded HTTP calls, whose responses can be replayed instead of sending requests.
+///
+/// Requests are matched on their method, URL and body. A recorded call is replayed only once, in
+/// the recorded order: when all the calls matching a request have been replayed, the last one is
+/// replayed again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cassette {
+    calls: Vec<Call>,
+    /// Marks the calls that have already been replayed
+    replayed: Vec<bool>,
+}
+
+impl Cassette {
+    /// Creates a new cassette from recorded `calls`.
+    pub fn new(calls: Vec<Call>) -> Self {
+        let replayed = vec![false; calls.len()];
+        Cassette { calls, replayed }
+    }
+
+    /// Returns the recorded call matching a request with `method`, `url` and `body`, `None` if
+    /// there is no matching call. If `body` is `None`, the body of the request is not matched.
+    pub fn replay(&mut self, method: &str, url: &str, body: Option<&[u8]>) -> Option<Call> {
+        let matching = self
+            .calls
+            .iter()
+            .enumerate()
+            .filter(|(_, call)| {
+                call.request.method == method
+                    && call.request.url == url
+                    && body.map_or(true, |b| call.request.body == b)
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let index = matching
+            .iter()
+            .find(|index| !self.replayed[**index])
+            .or(matching.last())?;
+        self.replayed[*index] = true;
+        Some(self.calls[*index].clone())
+    }
+
+    /// Parses a cassette from its JSON `content`.
+    pub fn parse(content: &str) -> Result<Cassette, String> {
+        let value = match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value) => value,
+            Err(e) => return Err(e.to_string()),
+        };
+        let calls = match value.get("calls").and_then(|calls| calls.as_array()) {
+            Some(calls) => calls,
+            None => return Err("missing calls array".to_string()),
+        };
+        let calls = calls
+            .iter()
+            .enumerate()
+            .map(|(index, call)| {
+                parse_call(call).map_err(|e| format!("invalid call {}: {e}", index + 1))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Cassette::new(calls))
+    }
+
+    /// Returns the JSON representation of this cassette. Bodies are encoded in base64.
+    ///
+    /// The `secrets` are redacted from the URLs, the headers and the text bodies. Like in the logs,
+    /// a redacted request can't be matched anymore when replayed.
+    pub fn to_json(&self, secrets: &[String]) -> serde_json::Value {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| {
+                let request = serde_json::json!({
+                    "method": call.request.method,
+                    "url": redact(&call.request.url, secrets),
+                    "headers": headers_to_json(&call.request.headers, secrets),
+                    "body": body_to_json(&call.request.body, secrets),
+                });
+                let response = serde_json::json!({
+                    "httpVersion": call.response.version.to_string(),
+                    "status": call.response.status,
+                    "headers": headers_to_json(&call.response.headers, secrets),
+                    "body": body_to_json(&call.response.body, secrets),
+                });
+                serde_json::json!({"request": request, "response": response})
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({ "calls": calls })
+    }
+}
+
+fn headers_to_json(headers: &[Header], secrets: &[String]) -> serde_json::Value {
+    let headers = headers
+        .iter()
+        .map(|h| serde_json::json!({"name": h.name, "value": redact(&h.value, secrets)}))
+        .collect::<Vec<_>>();
+    serde_json::Value::Array(headers)
+}
+
+/// Returns the base64 encoding of a `body`, whose `secrets` are redacted if it's a text.
+fn body_to_json(body: &[u8], secrets: &[String]) -> serde_json::Value {
+    let body = match std::str::from_utf8(body) {
+        Ok(text) => general_purpose::STANDARD.encode(redact(text, secrets)),
+        Err(_) => general_purpose::STANDARD.encode(body),
+    };
+    serde_json::Value::String(body)
+}
+
+fn parse_call(value: &serde_json::Value) -> Result<Call, String> {
+    let request = value.get("request").ok_or("missing request")?;
+    let response = value.get("response").ok_or("missing response")?;
+    let url = get_str(request, "url")?;
+    let request = Request {
+        url: url.to_string(),
+        method: get_str(request, "method")?.to_string(),
+        headers: parse_headers(request)?,
+        body: parse_body(request)?,
+    };
+    let version = match get_str(response, "httpVersion")? {
+        "HTTP/1.0" => HttpVersion::Http10,
+        "HTTP/1.1" => HttpVersion::Http11,
+        "HTTP/2" => HttpVersion::Http2,
+        "HTTP/3" => HttpVersion::Http3,
+        v => return Err(format!("invalid HTTP version {v}")),
+    };
+    let status = match response.get("status").and_then(|s| s.as_u64()) {
+        Some(status) => status as u32,
+        None => return Err("missing status".to_string()),
+    };
+    let response = Response {
+        version,
+        status,
+        headers: parse_headers(response)?,
+        body: parse_body(response)?,
+        url: url.to_string(),
+        ..Default::default()
+    };
+    Ok(Call {
+        request,
+        response,
+        timings: Timings::default(),
+    })
+}
+
+fn get_str<'a>(value: &'a serde_json::Value, name: &str) -> Result<&'a str, String> {
+    match value.get(name).and_then(|v| v.as_str()) {
+        Some(s) => Ok(s),
+        None => Err(format!("missing {name}")),
+    }
+}
+
+fn parse_headers(value: &serde_json::Value) -> Result<Vec<Header>, String> {
+    let headers = match value.get("headers") {
+        None => return Ok(vec![]),
+        Some(headers) => headers.as_array().ok_or("headers must be an array")?,
+    };
+    headers
+        .iter()
+        .map(|h| Ok(Header::new(get_str(h, "name")?, get_str(h, "value")?)))
+        .collect()
+}
+
+fn parse_body(value: &serde_json::Value) -> Result<Vec<u8>, String> {
+    match value.get("body") {
+        None => Ok(vec![]),
+        Some(body) => {
+            let body = body.as_str().ok_or("body must be a base64 string")?;
+            general_purpose::STANDARD
+                .decode(body)
+                .map_err(|e| format!("invalid base64 body: {e}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_call(method: &str, url: &str, body: &str, status: u32) -> Call {
+        Call {
+            request: Request {
+                url: url.to_string(),
+                method: method.to_string(),
+                headers: vec![],
+                body: body.as_bytes().to_vec(),
+            },
+            response: Response {
+                status,
+                headers: vec![Header::new("Content-Type", "text/plain")],
+                body: b"Hello".to_vec(),
+                url: url.to_string(),
+                ..Default::default()
+            },
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut cassette = Cassette::new(vec![
+            new_call("GET", "http://localhost:8000/a", "", 500),
+            new_call("POST", "http://localhost:8000/a", "x", 201),
+            new_call("GET", "http://localhost:8000/a", "", 200),
+        ]);
+        let status = |cassette: &mut Cassette, method, body: Option<&[u8]>| {
+            cassette
+                .replay(method, "http://localhost:8000/a", body)
+                .map(|call| call.response.status)
+        };
+        assert_eq!(status(&mut cassette, "GET", Some(b"")), Some(500));
+        assert_eq!(status(&mut cassette, "GET", Some(b"")), Some(200));
+        assert_eq!(status(&mut cassette, "GET", Some(b"")), Some(200));
+        assert_eq!(status(&mut cassette, "POST", Some(b"y")), None);
+        assert_eq!(status(&mut cassette, "POST", None), Some(201));
+        assert_eq!(status(&mut cassette, "PUT", None), None);
+    }
+
+    #[test]
+    fn test_json() {
+        let cassette = Cassette::new(vec![new_call("POST", "http://localhost:8000/a", "x", 201)]);
+        let json = cassette.to_json(&[]);
+        assert_eq!(
+            json["calls"][0]["response"],
+            serde_json::json!({
+                "httpVersion": "HTTP/1.0",
+                "status": 201,
+                "headers": [{"name": "Content-Type", "value": "text/plain"}],
+                "body": "SGVsbG8=",
+            })
+        );
+        assert_eq!(Cassette::parse(&json.to_string()).unwrap(), cassette);
+
+        let json = cassette.to_json(&["Hello".to_string()]);
+        assert_eq!(json["calls"][0]["response"]["body"], "Kioq");
+
+        assert_eq!(
+            Cassette::parse(r#"{"calls": [{"request": {}}]}"#).unwrap_err(),
+            "invalid call 1: missing response"
+        );
+    }
+}
@@ -20,13 +20,14 @@ use std::str::FromStr;
 
 use base64::engine::general_purpose;
 use base64::Engine;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use curl::easy::{List, SslOpt};
 use curl::{easy, Version};
 use encoding::all::ISO_8859_1;
 use encoding::{DecoderTrap, Encoding};
 use url::Url;
 
+use crate::http::cassette::Cassette;
 use crate::http::certificate::Certificate;
 use crate::http::core::*;
 use crate::http::options::ClientOptions;
@@ -34,7 +35,7 @@ use crate::http::request::*;
 use crate::http::request_spec::*;
 use crate::http::response::*;
 use crate::http::timings::Timings;
-use crate::http::{easy_ext, Call, Header, HttpError, Verbosity};
+use crate::http::{easy_ext, Call, Header, HttpError, ResponseCookie, Verbosity};
 use crate::util::logger::Logger;
 use crate::util::path::ContextDir;
 
@@ -51,6 +52,8 @@ pub struct Client {
     /// HTTP version support
     http2: bool,
     http3: bool,
+    /// Recorded calls to replay instead of sending the requests
+    cassette: Option<Cassette>,
 }
 
 /// Represents the state of the HTTP client.
@@ -88,9 +91,15 @@ impl Client {
             state: ClientState::default(),
             http2: version.feature_http2(),
             http3: version.feature_http3(),
+            cassette: None,
         }
     }
 
+    /// Replays the responses of `cassette` instead of sending the requests.
+    pub fn set_cassette(&mut self, cassette: Cassette) {
+        self.cassette = Some(cassette);
+    }
+
     /// Executes an HTTP request `request_spec`, optionally follows redirection and returns a
     /// list of pair of [`Request`], [`Response`].
     pub fn execute_with_redirect(
@@ -150,6 +159,9 @@ impl Client {
         self.handle.reset();
 
         let (url, method) = self.configure(request_spec, options, logger)?;
+        if self.cassette.is_some() {
+            return self.replay(request_spec, url, method, options, logger);
+        }
 
         let start = Utc::now();
         let verbose = options.verbosity.is_some();
@@ -345,6 +357,61 @@ impl Client {
         })
     }
 
+    /// Returns the recorded call of the cassette matching `request_spec`, sent to `url` with
+    /// `method`.
+    ///
+    /// Multipart bodies are not matched, as their boundary changes on each request.
+    fn replay(
+        &mut self,
+        request_spec: &RequestSpec,
+        url: String,
+        method: Method,
+        options: &ClientOptions,
+        logger: &Logger,
+    ) -> Result<Call, HttpError> {
+        let body = if !request_spec.multipart.is_empty() {
+            None
+        } else if !request_spec.form.is_empty() {
+            Some(self.url_encode_params(&request_spec.form).into_bytes())
+        } else {
+            Some(request_spec.body.bytes())
+        };
+        let start = Utc::now();
+        let method = method.to_string();
+        let cassette = self.cassette.as_mut().unwrap();
+        let call = match cassette.replay(&method, &url, body.as_deref()) {
+            Some(call) => call,
+            None => return Err(HttpError::NoRecordedCall { method, url }),
+        };
+
+        // The replayed cookies are added to the cookie jar, as if they had been received. Without a
+        // transfer, there is no debug function to capture libcurl logs, so we disable them.
+        self.handle.verbose(false)?;
+        for cookie in call.response.cookies() {
+            if let Some(cookie) = replayed_cookie(&cookie, &url) {
+                self.handle.cookie_list(&cookie.to_string())?;
+            }
+        }
+
+        if options.verbosity.is_some() {
+            logger.debug_important("Response: (replayed)");
+            logger.debug("");
+            let status_line = format!("{} {}", call.response.version, call.response.status);
+            logger.debug_status_version_in(&status_line);
+            for header in &call.response.headers {
+                logger.debug_header_in(&header.name, &header.value);
+            }
+            logger.info("<");
+        }
+
+        let timings = Timings {
+            begin_call: start,
+            end_call: Utc::now(),
+            ..Default::default()
+        };
+        Ok(Call { timings, ..call })
+    }
+
     /// Configure libcurl handle to send a `request_spec`, using `options`.
     /// If configuration is successful, returns a tuple of the concrete requested URL and method.
     fn configure(
@@ -815,6 +882,50 @@ pub fn match_cookie(cookie: &Cookie, url: &str) -> bool {
     url.path().starts_with(cookie.path.as_str())
 }
 
+/// Returns the cookie jar entry of a `cookie` received from `url`, `None` if `url` has no host.
+///
+/// Without `Domain` and `Path` attributes, the cookie is bound to the host and the directory of
+/// `url` (see <https://datatracker.ietf.org/doc/html/rfc6265#section-5.3>).
+fn replayed_cookie(cookie: &ResponseCookie, url: &str) -> Option<Cookie> {
+    let url = Url::parse(url).ok()?;
+    let (domain, include_subdomain) = match cookie.domain() {
+        Some(domain) => (domain.trim_start_matches('.').to_string(), "TRUE"),
+        None => (url.host_str()?.to_string(), "FALSE"),
+    };
+    let path = match cookie.path() {
+        Some(path) => path,
+        None => match url.path().rfind('/') {
+            Some(index) if index > 0 => url.path()[..index].to_string(),
+            _ => "/".to_string(),
+        },
+    };
+    // Session cookies have a 0 expiration, and libcurl uses 1 for expired cookies.
+    let expires = if let Some(max_age) = cookie.max_age() {
+        if max_age > 0 {
+            (Utc::now().timestamp() + max_age).to_string()
+        } else {
+            "1".to_string()
+        }
+    } else if let Some(expires) = cookie.expires() {
+        match DateTime::parse_from_rfc2822(&expires) {
+            Ok(date) => date.timestamp().max(1).to_string(),
+            Err(_) => "0".to_string(),
+        }
+    } else {
+        "0".to_string()
+    };
+    Some(Cookie {
+        domain,
+        include_subdomain: include_subdomain.to_string(),
+        path,
+        https: if cookie.has_secure() { "TRUE" } else { "FALSE" }.to_string(),
+        expires,
+        name: cookie.name.clone(),
+        value: cookie.value.clone(),
+        http_only: cookie.has_httponly(),
+    })
+}
+
 impl Header {
     /// Parses an HTTP header line received from the server
     /// It does not panic. Just returns `None` if it can not be parsed.
@@ -996,6 +1107,43 @@ mod tests {
         assert!(!match_cookie(&cookie, "http://example.com/tata"));
     }
 
+    #[test]
+    fn test_replayed_cookie() {
+        let cookie = ResponseCookie::parse("id=a3fWa; HttpOnly".to_string()).unwrap();
+        assert_eq!(
+            replayed_cookie(&cookie, "http://localhost:8000/users/1").unwrap(),
+            Cookie {
+                domain: "localhost".to_string(),
+                include_subdomain: "FALSE".to_string(),
+                path: "/users".to_string(),
+                https: "FALSE".to_string(),
+                expires: "0".to_string(),
+                name: "id".to_string(),
+                value: "a3fWa".to_string(),
+                http_only: true,
+            }
+        );
+
+        let cookie = ResponseCookie::parse(
+            "id=a3fWa; Domain=.example.com; Path=/; Secure; Expires=Wed, 21 Oct 2015 07:28:00 GMT"
+                .to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            replayed_cookie(&cookie, "https://www.example.com/users/1").unwrap(),
+            Cookie {
+                domain: "example.com".to_string(),
+                include_subdomain: "TRUE".to_string(),
+                path: "/".to_string(),
+                https: "TRUE".to_string(),
+                expires: "1445412480".to_string(),
+                name: "id".to_string(),
+                value: "a3fWa".to_string(),
+                http_only: false,
+            }
+        );
+    }
+
     #[test]
     fn test_redirect_url() {
         assert_eq!(
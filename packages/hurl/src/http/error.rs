@@ -38,6 +38,10 @@ pub enum HttpError {
         option: String,
         minimum_version: String,
     },
+    NoRecordedCall {
+        method: String,
+        url: String,
+    },
     StatuslineIsMissing,
     TooManyRedirect,
     UnsupportedContentEncoding {
@@ -16,6 +16,7 @@
  *
  */
 pub use self::call::Call;
+pub use self::cassette::Cassette;
 pub use self::certificate::Certificate;
 pub(crate) use self::client::Client;
 pub use self::cookie::{CookieAttribute, ResponseCookie};
@@ -32,6 +33,7 @@ pub use self::timings::Timings;
 pub use self::version::libcurl_version_info;
 
 mod call;
+mod cassette;
 mod certificate;
 mod client;
 mod cookie;
@@ -17,6 +17,9 @@
  */
 use core::fmt;
 
+use base64::engine::general_purpose;
+use base64::Engine;
+
 use crate::http::core::*;
 use crate::http::{header, Header};
 
@@ -96,6 +99,17 @@ impl fmt::Display for Method {
     }
 }
 
+/// Formats a body with the Hurl notation of its kind: text, `base64,...;` or `file,...;`.
+impl fmt::Display for Body {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Body::Text(s) => write!(f, "{s}"),
+            Body::Binary(bs) => write!(f, "base64,{};", general_purpose::STANDARD.encode(bs)),
+            Body::File(_, filename) => write!(f, "file,{filename};"),
+        }
+    }
+}
+
 impl fmt::Display for MultipartParam {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
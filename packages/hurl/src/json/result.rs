@@ -19,11 +19,14 @@ use chrono::{DateTime, Utc};
 use serde_json::Number;
 
 use crate::http::{
-    Call, Certificate, Cookie, Header, HttpVersion, Param, Request, RequestCookie, Response,
-    ResponseCookie, Timings,
+    Body, Call, Certificate, Cookie, Header, HttpVersion, Param, Request, RequestCookie,
+    RequestSpec, Response, ResponseCookie, Timings,
+};
+use crate::runner::{
+    AssertResult, CaptureResult, DryRunEntry, DryRunResult, EntryResult, HurlResult, RetryAttempt,
 };
-use crate::runner::{AssertResult, CaptureResult, EntryResult, HurlResult};
 use crate::util::logger;
+use crate::util::redact::redact;
 
 impl HurlResult {
     /// Serializes an [`HurlResult`] to a JSON representation.
@@ -50,10 +53,121 @@ impl HurlResult {
         );
         let cookies = self.cookies.iter().map(|e| e.to_json()).collect();
         map.insert("cookies".to_string(), serde_json::Value::Array(cookies));
+        let mut value = serde_json::Value::Object(map);
+        redact_json(&mut value, &self.secrets);
+        value
+    }
+}
+
+impl DryRunResult {
+    /// Serializes a [`DryRunResult`] of the file `filename` to a JSON representation.
+    pub fn to_json(&self, filename: &str) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "filename".to_string(),
+            serde_json::Value::String(filename.to_string()),
+        );
+        let entries = self.entries.iter().map(|e| e.to_json()).collect();
+        map.insert("entries".to_string(), serde_json::Value::Array(entries));
+        map.insert("success".to_string(), serde_json::Value::Bool(self.success));
+        let mut value = serde_json::Value::Object(map);
+        redact_json(&mut value, &self.secrets);
+        value
+    }
+}
+
+impl DryRunEntry {
+    fn to_json(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "index".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(self.entry_index)),
+        );
+        if let Some(iteration) = self.iteration {
+            map.insert(
+                "iteration".to_string(),
+                serde_json::Value::Number(serde_json::Number::from(iteration)),
+            );
+        }
+        map.insert(
+            "line".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(self.source_info.start.line)),
+        );
+        if let Some(file) = &self.file {
+            map.insert(
+                "filename".to_string(),
+                serde_json::Value::String(file.filename.clone()),
+            );
+        }
+        map.insert("request".to_string(), self.request.to_json());
+        map.insert(
+            "curl".to_string(),
+            serde_json::Value::String(self.curl.clone()),
+        );
+        serde_json::Value::Object(map)
+    }
+}
+
+impl RequestSpec {
+    fn to_json(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "method".to_string(),
+            serde_json::Value::String(self.method.to_string()),
+        );
+        map.insert(
+            "url".to_string(),
+            serde_json::Value::String(self.url.clone()),
+        );
+        let headers = self.headers.iter().map(|h| h.to_json()).collect();
+        map.insert("headers".to_string(), headers);
+        let cookies = self.cookies.iter().map(|c| c.to_json()).collect();
+        map.insert("cookies".to_string(), cookies);
+        let query_string = self.querystring.iter().map(|p| p.to_json()).collect();
+        map.insert("queryString".to_string(), query_string);
+        if !self.form.is_empty() {
+            let form = self.form.iter().map(|p| p.to_json()).collect();
+            map.insert("form".to_string(), form);
+        }
+        if !self.multipart.is_empty() {
+            let multipart = self
+                .multipart
+                .iter()
+                .map(|p| serde_json::Value::String(p.to_string()))
+                .collect();
+            map.insert("multipart".to_string(), multipart);
+        }
+        if let Some(content_type) = &self.content_type {
+            map.insert(
+                "contentType".to_string(),
+                serde_json::Value::String(content_type.clone()),
+            );
+        }
+        let empty = matches!(&self.body, Body::Binary(bs) if bs.is_empty());
+        if !empty {
+            map.insert(
+                "body".to_string(),
+                serde_json::Value::String(self.body.to_string()),
+            );
+        }
         serde_json::Value::Object(map)
     }
 }
 
+/// Redacts the `secrets` of every string of a JSON `value`.
+fn redact_json(value: &mut serde_json::Value, secrets: &[String]) {
+    match value {
+        serde_json::Value::String(s) => *s = redact(s, secrets),
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|v| redact_json(v, secrets));
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|v| redact_json(v, secrets));
+        }
+        _ => {}
+    }
+}
+
 impl EntryResult {
     fn to_json(&self, filename: &str, content: &str) -> serde_json::Value {
         let mut map = serde_json::Map::new();
@@ -62,10 +176,38 @@ impl EntryResult {
             "index".to_string(),
             serde_json::Value::Number(serde_json::Number::from(self.entry_index)),
         );
+        if let Some(iteration) = self.iteration {
+            map.insert(
+                "iteration".to_string(),
+                serde_json::Value::Number(serde_json::Number::from(iteration)),
+            );
+        }
         map.insert(
             "line".to_string(),
             serde_json::Value::Number(serde_json::Number::from(self.source_info.start.line)),
         );
+        if let Some(name) = &self.name {
+            map.insert("name".to_string(), serde_json::Value::String(name.clone()));
+        }
+        // Entries of included files are located in their included file.
+        let (filename, content) = match &self.file {
+            Some(file) => {
+                map.insert(
+                    "filename".to_string(),
+                    serde_json::Value::String(file.filename.clone()),
+                );
+                (file.filename.as_str(), file.content.as_str())
+            }
+            None => (filename, content),
+        };
+        if !self.tags.is_empty() {
+            let tags = self
+                .tags
+                .iter()
+                .map(|t| serde_json::Value::String(t.clone()))
+                .collect();
+            map.insert("tags".to_string(), tags);
+        }
         let calls = self.calls.iter().map(|c| c.to_json()).collect();
         map.insert("calls".to_string(), calls);
         let captures = self.captures.iter().map(|c| c.to_json()).collect();
@@ -76,6 +218,9 @@ impl EntryResult {
             .map(|a| a.to_json(filename, content))
             .collect();
         map.insert("asserts".to_string(), asserts);
+        if let Some(retry) = &self.retry {
+            map.insert("retry".to_string(), retry.to_json());
+        }
         map.insert(
             "time".to_string(),
             serde_json::Value::Number(serde_json::Number::from(self.time_in_ms as u64)),
@@ -328,6 +473,25 @@ impl CaptureResult {
     }
 }
 
+impl RetryAttempt {
+    fn to_json(&self) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "attempt".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(self.attempt)),
+        );
+        map.insert(
+            "reason".to_string(),
+            serde_json::Value::String(self.reason.to_string()),
+        );
+        map.insert(
+            "delay".to_string(),
+            serde_json::Value::Number(serde_json::Number::from(self.delay.as_millis() as u64)),
+        );
+        serde_json::Value::Object(map)
+    }
+}
+
 impl AssertResult {
     fn to_json(&self, filename: &str, content: &str) -> serde_json::Value {
         let mut map = serde_json::Map::new();
@@ -0,0 +1,38 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! JSON Schema validation.
+//!
+//! Specification: <https://json-schema.org/draft/2020-12/json-schema-validation>
+//!
+//! Hurl implements the applicator and validation vocabularies of the draft 2020-12, with the
+//! following limitations:
+//! - references (`$ref`) must target the schema itself: `#`, a JSON pointer like `#/$defs/user`,
+//!   an anchor like `#user`, or the same forms prefixed by the `$id` of the schema,
+//! - `format` is an annotation and is not validated (which is the default behaviour of the spec),
+//! - `unevaluatedItems`, `unevaluatedProperties` and dynamic references (`$dynamicRef`) are not
+//!   supported: a schema using them is rejected.
+//!
+//! Patterns (`pattern` and `patternProperties`) use the syntax of the Rust regex crate, which is
+//! a superset of the ECMA 262 syntax used by most schemas, without look-around and backreferences.
+//!
+//! Validating an instance returns all the validation errors, each one with the path of the
+//! failing instance (using a JSONPath like syntax `$.users[0].name`) and the failing keyword.
+pub use self::schema::Schema;
+
+mod schema;
+mod validate;
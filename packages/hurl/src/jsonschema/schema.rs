@@ -0,0 +1,390 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use percent_encoding::percent_decode_str;
+use regex::Regex;
+use serde_json::Value;
+
+/// The types of the JSON Schema `type` keyword.
+const TYPES: [&str; 7] = [
+    "array", "boolean", "integer", "null", "number", "object", "string",
+];
+
+/// A JSON Schema, checked and ready to validate instances.
+#[derive(Clone, Debug)]
+pub struct Schema {
+    pub(super) root: Value,
+    /// Compiled regexes of `pattern` and `patternProperties` keywords
+    pub(super) regexes: HashMap<String, Regex>,
+}
+
+// Regexes are compiled from the root value, so comparing the root values is enough.
+impl PartialEq for Schema {
+    fn eq(&self, other: &Self) -> bool {
+        self.root == other.root
+    }
+}
+
+impl Eq for Schema {}
+
+/// An invalid JSON Schema error, with the location of the invalid keyword in the schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaError {
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+impl Schema {
+    /// Creates a new schema from its JSON `root` value, checking that all its keywords are valid.
+    pub fn new(root: Value) -> Result<Schema, SchemaError> {
+        let mut schema = Schema {
+            root,
+            regexes: HashMap::new(),
+        };
+        let mut regexes = HashMap::new();
+        let mut refs = HashSet::new();
+        schema.check(&schema.root, "#", &mut regexes, &mut refs)?;
+        schema.regexes = regexes;
+        Ok(schema)
+    }
+
+    /// Creates a new schema from a JSON `document` holding subschemas (like an OpenAPI document),
+    /// checking the subschemas targeted by `references`.
+    ///
+    /// Instances are validated against one of these subschemas with [`Schema::validate_ref`].
+    pub fn from_document(document: Value, references: &[String]) -> Result<Schema, SchemaError> {
+        let mut schema = Schema {
+            root: document,
+            regexes: HashMap::new(),
+        };
+        let mut regexes = HashMap::new();
+        let mut refs = HashSet::new();
+        for reference in references {
+            schema.check_ref(reference, "#", &mut regexes, &mut refs)?;
+        }
+        schema.regexes = regexes;
+        Ok(schema)
+    }
+
+    /// Returns the subschema targeted by a `reference`, `None` if the reference can't be resolved.
+    pub(super) fn resolve(&self, reference: &str) -> Option<&Value> {
+        let id = self
+            .root
+            .get("$id")
+            .and_then(|id| id.as_str())
+            .unwrap_or("");
+        let fragment = match reference.strip_prefix(id) {
+            Some(fragment) if !id.is_empty() && fragment.is_empty() => "#",
+            Some(fragment) if !id.is_empty() => fragment,
+            _ => reference,
+        };
+        let fragment = fragment.strip_prefix('#')?;
+        if fragment.is_empty() {
+            return Some(&self.root);
+        }
+        match fragment.strip_prefix('/') {
+            Some(pointer) => resolve_pointer(&self.root, pointer),
+            None => find_anchor(&self.root, fragment),
+        }
+    }
+
+    /// Checks a `schema` located at `location`, adding the compiled regexes to `regexes`.
+    ///
+    /// `refs` are the references whose targets have already been checked.
+    fn check(
+        &self,
+        schema: &Value,
+        location: &str,
+        regexes: &mut HashMap<String, Regex>,
+        refs: &mut HashSet<String>,
+    ) -> Result<(), SchemaError> {
+        let keywords = match schema {
+            Value::Bool(_) => return Ok(()),
+            Value::Object(keywords) => keywords,
+            _ => return Err(error(location, "a schema must be an object or a boolean")),
+        };
+        for (keyword, value) in keywords {
+            let location = format!("{location}/{keyword}");
+            let location = location.as_str();
+            match keyword.as_str() {
+                "additionalProperties"
+                | "contains"
+                | "else"
+                | "if"
+                | "items"
+                | "not"
+                | "propertyNames"
+                | "then" => {
+                    if value.is_array() {
+                        return Err(error(location, "must be a schema (not an array)"));
+                    }
+                    self.check(value, location, regexes, refs)?;
+                }
+                "$defs" | "definitions" | "dependentSchemas" | "properties" => {
+                    let schemas = value
+                        .as_object()
+                        .ok_or_else(|| error(location, "must be an object"))?;
+                    for (name, schema) in schemas {
+                        self.check(schema, &format!("{location}/{name}"), regexes, refs)?;
+                    }
+                }
+                "patternProperties" => {
+                    let schemas = value
+                        .as_object()
+                        .ok_or_else(|| error(location, "must be an object"))?;
+                    for (pattern, schema) in schemas {
+                        add_regex(pattern, location, regexes)?;
+                        self.check(schema, &format!("{location}/{pattern}"), regexes, refs)?;
+                    }
+                }
+                "allOf" | "anyOf" | "oneOf" | "prefixItems" => {
+                    let schemas = match value.as_array() {
+                        Some(schemas) if !schemas.is_empty() => schemas,
+                        _ => return Err(error(location, "must be a non-empty array")),
+                    };
+                    for (index, schema) in schemas.iter().enumerate() {
+                        self.check(schema, &format!("{location}/{index}"), regexes, refs)?;
+                    }
+                }
+                "type" => {
+                    let types = match value {
+                        Value::String(_) => vec![value],
+                        Value::Array(types) => types.iter().collect(),
+                        _ => return Err(error(location, "must be a string or an array")),
+                    };
+                    for t in types {
+                        if !t.as_str().is_some_and(|t| TYPES.contains(&t)) {
+                            let message = format!("invalid type {t}");
+                            return Err(error(location, &message));
+                        }
+                    }
+                }
+                "required" => check_strings(value, location)?,
+                "dependentRequired" => {
+                    let properties = value
+                        .as_object()
+                        .ok_or_else(|| error(location, "must be an object"))?;
+                    for (name, required) in properties {
+                        check_strings(required, &format!("{location}/{name}"))?;
+                    }
+                }
+                "enum" if !value.is_array() => {
+                    return Err(error(location, "must be an array"));
+                }
+                "exclusiveMaximum" | "exclusiveMinimum" | "maximum" | "minimum"
+                    if !value.is_number() =>
+                {
+                    return Err(error(location, "must be a number"));
+                }
+                "multipleOf" if !value.as_f64().is_some_and(|v| v > 0.0) => {
+                    return Err(error(location, "must be a number strictly greater than 0"));
+                }
+                "maxContains" | "maxItems" | "maxLength" | "maxProperties" | "minContains"
+                | "minItems" | "minLength" | "minProperties"
+                    if value.as_u64().is_none() =>
+                {
+                    return Err(error(location, "must be a non-negative integer"));
+                }
+                "uniqueItems" if !value.is_boolean() => {
+                    return Err(error(location, "must be a boolean"));
+                }
+                "pattern" => {
+                    let pattern = value
+                        .as_str()
+                        .ok_or_else(|| error(location, "must be a string"))?;
+                    add_regex(pattern, location, regexes)?;
+                }
+                "$ref" => {
+                    let reference = value
+                        .as_str()
+                        .ok_or_else(|| error(location, "must be a string"))?;
+                    self.check_ref(reference, location, regexes, refs)?;
+                }
+                // Ignoring these keywords would validate instances that are not valid.
+                "$dynamicRef" | "$recursiveRef" | "unevaluatedItems" | "unevaluatedProperties" => {
+                    return Err(error(location, "keyword is not supported"));
+                }
+                // Other keywords are annotations.
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the target of a `reference` located at `location`, if it has not been checked yet.
+    fn check_ref(
+        &self,
+        reference: &str,
+        location: &str,
+        regexes: &mut HashMap<String, Regex>,
+        refs: &mut HashSet<String>,
+    ) -> Result<(), SchemaError> {
+        let target = match self.resolve(reference) {
+            Some(target) => target,
+            None => {
+                let message = format!(
+                    "reference {reference} can not be resolved (only references within the schema are supported)"
+                );
+                return Err(error(location, &message));
+            }
+        };
+        // Targets can be outside of the subschemas checked here (for instance, in an OpenAPI
+        // document, `#/components/schemas/user`), so they're checked once too.
+        if refs.insert(reference.to_string()) {
+            self.check(target, reference, regexes, refs)?;
+        }
+        Ok(())
+    }
+}
+
+fn error(location: &str, message: &str) -> SchemaError {
+    SchemaError {
+        location: location.to_string(),
+        message: message.to_string(),
+    }
+}
+
+fn check_strings(value: &Value, location: &str) -> Result<(), SchemaError> {
+    match value.as_array() {
+        Some(values) if values.iter().all(|v| v.is_string()) => Ok(()),
+        _ => Err(error(location, "must be an array of strings")),
+    }
+}
+
+fn add_regex(
+    pattern: &str,
+    location: &str,
+    regexes: &mut HashMap<String, Regex>,
+) -> Result<(), SchemaError> {
+    match Regex::new(pattern) {
+        Ok(regex) => {
+            regexes.insert(pattern.to_string(), regex);
+            Ok(())
+        }
+        Err(_) => {
+            let message = format!("invalid regex {pattern}");
+            Err(error(location, &message))
+        }
+    }
+}
+
+/// Returns the value targeted by a JSON `pointer` (without its leading `/`) in `root`.
+fn resolve_pointer<'a>(root: &'a Value, pointer: &str) -> Option<&'a Value> {
+    let mut value = root;
+    for token in pointer.split('/') {
+        let token = percent_decode_str(token).decode_utf8().ok()?;
+        let token = token.replace("~1", "/").replace("~0", "~");
+        value = match value {
+            Value::Object(map) => map.get(&token)?,
+            Value::Array(values) => values.get(token.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Returns the subschema of `schema` with an `$anchor` `name`.
+fn find_anchor<'a>(schema: &'a Value, name: &str) -> Option<&'a Value> {
+    match schema {
+        Value::Object(map) => {
+            if map.get("$anchor").and_then(|a| a.as_str()) == Some(name) {
+                return Some(schema);
+            }
+            map.values().find_map(|v| find_anchor(v, name))
+        }
+        Value::Array(values) => values.iter().find_map(|v| find_anchor(v, name)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_invalid_schema() {
+        let error = |value: Value| Schema::new(value).unwrap_err().to_string();
+        assert_eq!(
+            error(json!(1)),
+            "#: a schema must be an object or a boolean"
+        );
+        assert_eq!(
+            error(json!({"properties": {"name": {"type": "text"}}})),
+            "#/properties/name/type: invalid type \"text\""
+        );
+        assert_eq!(
+            error(json!({"items": {"minLength": -1}})),
+            "#/items/minLength: must be a non-negative integer"
+        );
+        assert_eq!(error(json!({"pattern": "("})), "#/pattern: invalid regex (");
+        assert_eq!(
+            error(json!({"$ref": "#/components/user", "components": {"user": {"pattern": "("}}})),
+            "#/components/user/pattern: invalid regex ("
+        );
+        assert_eq!(
+            error(json!({"items": {"unevaluatedProperties": false}})),
+            "#/items/unevaluatedProperties: keyword is not supported"
+        );
+        assert_eq!(
+            error(json!({"$ref": "other.json#/$defs/user"})),
+            "#/$ref: reference other.json#/$defs/user can not be resolved (only references within the schema are supported)"
+        );
+    }
+
+    #[test]
+    fn test_resolve() {
+        let schema = Schema::new(json!({
+            "$id": "https://example.org/user.json",
+            "$defs": {
+                "name": {"type": "string"},
+                "a/b": {"$anchor": "ab", "type": "integer"},
+            },
+            "prefixItems": [{"type": "null"}]
+        }))
+        .unwrap();
+        assert_eq!(schema.resolve("#"), Some(&schema.root));
+        assert_eq!(
+            schema.resolve("#/$defs/name"),
+            Some(&json!({"type": "string"}))
+        );
+        assert_eq!(
+            schema.resolve("https://example.org/user.json#/$defs/a~1b"),
+            Some(&json!({"$anchor": "ab", "type": "integer"}))
+        );
+        assert_eq!(
+            schema.resolve("#ab"),
+            Some(&json!({"$anchor": "ab", "type": "integer"}))
+        );
+        assert_eq!(
+            schema.resolve("#/prefixItems/0"),
+            Some(&json!({"type": "null"}))
+        );
+        assert_eq!(schema.resolve("#/$defs/age"), None);
+        assert_eq!(schema.resolve("user.json"), None);
+    }
+}
@@ -0,0 +1,747 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::fmt;
+
+use serde_json::{Map, Number, Value};
+
+use crate::jsonschema::Schema;
+
+/// Maximum number of nested references, to stop on recursive schemas that never consume the
+/// instance (like `{"$ref": "#"}`).
+const MAX_REF_DEPTH: usize = 64;
+
+/// A validation error, with the path of the failing instance and the failing keyword.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    pub instance_path: String,
+    pub keyword: String,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} ({})",
+            self.instance_path, self.message, self.keyword
+        )
+    }
+}
+
+/// An element of the path of an instance, from the root instance.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathElement {
+    Key(String),
+    Index(usize),
+}
+
+impl Schema {
+    /// Validates an `instance` against this schema, returning all the validation errors.
+    pub fn validate(&self, instance: &Value) -> Vec<ValidationError> {
+        self.validate_schema(&self.root, instance, &[], 0)
+    }
+
+    /// Validates an `instance` against the subschema targeted by a `reference`, like
+    /// `#/components/schemas/user`.
+    pub fn validate_ref(&self, reference: &str, instance: &Value) -> Vec<ValidationError> {
+        match self.resolve(reference) {
+            Some(schema) => self.validate_schema(schema, instance, &[], 0),
+            None => {
+                let message = format!("reference {reference} can not be resolved");
+                vec![error(&[], "$ref", &message)]
+            }
+        }
+    }
+
+    /// Validates an `instance` located at `path` against a `schema`, `depth` being the number of
+    /// references followed so far.
+    fn validate_schema(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        path: &[PathElement],
+        depth: usize,
+    ) -> Vec<ValidationError> {
+        let keywords = match schema {
+            Value::Object(keywords) => keywords,
+            Value::Bool(false) => return vec![error(path, "false", "no value is allowed")],
+            // Boolean `true` schema, other values have been rejected when checking the schema.
+            _ => return vec![],
+        };
+        let mut errors = vec![];
+        for (keyword, value) in keywords {
+            let mut keyword_errors = match instance {
+                Value::Array(items) => self.validate_array(keywords, keyword, items, path, depth),
+                Value::Number(number) => validate_number(keyword, value, number, path),
+                Value::Object(properties) => {
+                    self.validate_object(keywords, keyword, properties, path, depth)
+                }
+                Value::String(s) => self.validate_string(keyword, value, s, path),
+                _ => vec![],
+            };
+            errors.append(&mut keyword_errors);
+            let mut keyword_errors = self.validate_any(keywords, keyword, instance, path, depth);
+            errors.append(&mut keyword_errors);
+        }
+        errors
+    }
+
+    /// Returns true if an `instance` is valid against a `schema`.
+    fn is_valid(
+        &self,
+        schema: &Value,
+        instance: &Value,
+        path: &[PathElement],
+        depth: usize,
+    ) -> bool {
+        self.validate_schema(schema, instance, path, depth)
+            .is_empty()
+    }
+
+    /// Validates a `keyword` applying to any type of `instance`.
+    fn validate_any(
+        &self,
+        keywords: &Map<String, Value>,
+        keyword: &str,
+        instance: &Value,
+        path: &[PathElement],
+        depth: usize,
+    ) -> Vec<ValidationError> {
+        let value = &keywords[keyword];
+        match keyword {
+            "$ref" => {
+                if depth >= MAX_REF_DEPTH {
+                    return vec![error(path, keyword, "too many nested references")];
+                }
+                let reference = value.as_str().unwrap_or_default();
+                match self.resolve(reference) {
+                    Some(schema) => self.validate_schema(schema, instance, path, depth + 1),
+                    None => vec![],
+                }
+            }
+            "type" => {
+                let types = match value {
+                    Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+                    _ => vec![value.as_str().unwrap_or_default()],
+                };
+                if types.iter().any(|t| is_type(instance, t)) {
+                    return vec![];
+                }
+                let expected = types.join(" or ");
+                let message = format!("value is not of type {expected}");
+                vec![error(path, keyword, &message)]
+            }
+            "enum" => {
+                let values = value.as_array().map(|v| v.as_slice()).unwrap_or_default();
+                if values.iter().any(|v| json_equal(v, instance)) {
+                    return vec![];
+                }
+                vec![error(
+                    path,
+                    keyword,
+                    "value is not one of the allowed values",
+                )]
+            }
+            "const" => {
+                if json_equal(value, instance) {
+                    return vec![];
+                }
+                let message = format!("value is not equal to {value}");
+                vec![error(path, keyword, &message)]
+            }
+            "allOf" => schemas(value)
+                .iter()
+                .flat_map(|schema| self.validate_schema(schema, instance, path, depth))
+                .collect(),
+            "anyOf" => {
+                if schemas(value)
+                    .iter()
+                    .any(|schema| self.is_valid(schema, instance, path, depth))
+                {
+                    return vec![];
+                }
+                vec![error(path, keyword, "value does not match any schema")]
+            }
+            "oneOf" => {
+                let count = schemas(value)
+                    .iter()
+                    .filter(|schema| self.is_valid(schema, instance, path, depth))
+                    .count();
+                match count {
+                    1 => vec![],
+                    0 => vec![error(path, keyword, "value does not match any schema")],
+                    _ => {
+                        let message = format!("value matches {count} schemas instead of one");
+                        vec![error(path, keyword, &message)]
+                    }
+                }
+            }
+            "not" => {
+                if self.is_valid(value, instance, path, depth) {
+                    return vec![error(path, keyword, "value must not match the schema")];
+                }
+                vec![]
+            }
+            "if" => {
+                let branch = if self.is_valid(value, instance, path, depth) {
+                    keywords.get("then")
+                } else {
+                    keywords.get("else")
+                };
+                match branch {
+                    Some(schema) => self.validate_schema(schema, instance, path, depth),
+                    None => vec![],
+                }
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Validates a `keyword` applying to an array instance `items`.
+    fn validate_array(
+        &self,
+        keywords: &Map<String, Value>,
+        keyword: &str,
+        items: &[Value],
+        path: &[PathElement],
+        depth: usize,
+    ) -> Vec<ValidationError> {
+        let value = &keywords[keyword];
+        match keyword {
+            "prefixItems" => schemas(value)
+                .iter()
+                .zip(items.iter().enumerate())
+                .flat_map(|(schema, (index, item))| {
+                    let path = child_path(path, PathElement::Index(index));
+                    self.validate_schema(schema, item, &path, depth)
+                })
+                .collect(),
+            "items" => {
+                let start = keywords.get("prefixItems").map_or(0, |p| schemas(p).len());
+                items
+                    .iter()
+                    .enumerate()
+                    .skip(start)
+                    .flat_map(|(index, item)| {
+                        let path = child_path(path, PathElement::Index(index));
+                        self.validate_schema(value, item, &path, depth)
+                    })
+                    .collect()
+            }
+            "contains" => {
+                let count = items
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, item)| {
+                        let path = child_path(path, PathElement::Index(*index));
+                        self.is_valid(value, item, &path, depth)
+                    })
+                    .count() as u64;
+                let min = keywords.get("minContains").and_then(|v| v.as_u64());
+                let max = keywords.get("maxContains").and_then(|v| v.as_u64());
+                let mut errors = vec![];
+                match min {
+                    None if count == 0 => {
+                        let message = "array does not contain any matching item";
+                        errors.push(error(path, keyword, message));
+                    }
+                    Some(min) if count < min => {
+                        let message =
+                            format!("array contains {count} matching items, less than {min}");
+                        errors.push(error(path, "minContains", &message));
+                    }
+                    _ => {}
+                }
+                match max {
+                    Some(max) if count > max => {
+                        let message =
+                            format!("array contains {count} matching items, more than {max}");
+                        errors.push(error(path, "maxContains", &message));
+                    }
+                    _ => {}
+                }
+                errors
+            }
+            "minItems" => {
+                let min = value.as_u64().unwrap_or_default();
+                if (items.len() as u64) < min {
+                    let message = format!("array has {} items, less than {min}", items.len());
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            "maxItems" => {
+                let max = value.as_u64().unwrap_or_default();
+                if (items.len() as u64) > max {
+                    let message = format!("array has {} items, more than {max}", items.len());
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            "uniqueItems" => {
+                if value != &Value::Bool(true) {
+                    return vec![];
+                }
+                for (i, a) in items.iter().enumerate() {
+                    for (j, b) in items.iter().enumerate().skip(i + 1) {
+                        if json_equal(a, b) {
+                            let message = format!("items {i} and {j} are equal");
+                            return vec![error(path, keyword, &message)];
+                        }
+                    }
+                }
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Validates a `keyword` applying to an object instance `properties`.
+    fn validate_object(
+        &self,
+        keywords: &Map<String, Value>,
+        keyword: &str,
+        properties: &Map<String, Value>,
+        path: &[PathElement],
+        depth: usize,
+    ) -> Vec<ValidationError> {
+        let value = &keywords[keyword];
+        match keyword {
+            "properties" => {
+                let schemas = value.as_object().cloned().unwrap_or_default();
+                properties
+                    .iter()
+                    .filter_map(|(name, property)| {
+                        let schema = schemas.get(name)?;
+                        let path = child_path(path, PathElement::Key(name.clone()));
+                        Some(self.validate_schema(schema, property, &path, depth))
+                    })
+                    .flatten()
+                    .collect()
+            }
+            "patternProperties" => {
+                let schemas = value.as_object().cloned().unwrap_or_default();
+                let mut errors = vec![];
+                for (pattern, schema) in schemas.iter() {
+                    for (name, property) in properties {
+                        if !self.regexes[pattern].is_match(name) {
+                            continue;
+                        }
+                        let path = child_path(path, PathElement::Key(name.clone()));
+                        errors.append(&mut self.validate_schema(schema, property, &path, depth));
+                    }
+                }
+                errors
+            }
+            "additionalProperties" => {
+                let mut errors = vec![];
+                for (name, property) in properties {
+                    if self.is_evaluated_property(keywords, name) {
+                        continue;
+                    }
+                    if value == &Value::Bool(false) {
+                        let message = format!("property {name} is not allowed");
+                        errors.push(error(path, keyword, &message));
+                        continue;
+                    }
+                    let path = child_path(path, PathElement::Key(name.clone()));
+                    errors.append(&mut self.validate_schema(value, property, &path, depth));
+                }
+                errors
+            }
+            "propertyNames" => properties
+                .keys()
+                .filter(|name| !self.is_valid(value, &Value::String(name.to_string()), path, depth))
+                .map(|name| {
+                    let message = format!("property name {name} is not valid");
+                    error(path, keyword, &message)
+                })
+                .collect(),
+            "required" => strings(value)
+                .filter(|name| !properties.contains_key(*name))
+                .map(|name| {
+                    let message = format!("required property {name} is missing");
+                    error(path, keyword, &message)
+                })
+                .collect(),
+            "dependentRequired" => {
+                let dependencies = value.as_object().cloned().unwrap_or_default();
+                let mut errors = vec![];
+                for (name, required) in dependencies.iter() {
+                    if !properties.contains_key(name) {
+                        continue;
+                    }
+                    for dependency in strings(required) {
+                        if !properties.contains_key(dependency) {
+                            let message =
+                                format!("property {dependency} is required by property {name}");
+                            errors.push(error(path, keyword, &message));
+                        }
+                    }
+                }
+                errors
+            }
+            "dependentSchemas" => {
+                let dependencies = value.as_object().cloned().unwrap_or_default();
+                let instance = Value::Object(properties.clone());
+                dependencies
+                    .iter()
+                    .filter(|(name, _)| properties.contains_key(*name))
+                    .flat_map(|(_, schema)| self.validate_schema(schema, &instance, path, depth))
+                    .collect()
+            }
+            "minProperties" => {
+                let min = value.as_u64().unwrap_or_default();
+                if (properties.len() as u64) < min {
+                    let message = format!(
+                        "object has {} properties, less than {min}",
+                        properties.len()
+                    );
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            "maxProperties" => {
+                let max = value.as_u64().unwrap_or_default();
+                if (properties.len() as u64) > max {
+                    let message = format!(
+                        "object has {} properties, more than {max}",
+                        properties.len()
+                    );
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Returns true if the property `name` is evaluated by the `properties` or `patternProperties`
+    /// keywords of a schema.
+    fn is_evaluated_property(&self, keywords: &Map<String, Value>, name: &str) -> bool {
+        if let Some(Value::Object(properties)) = keywords.get("properties") {
+            if properties.contains_key(name) {
+                return true;
+            }
+        }
+        if let Some(Value::Object(patterns)) = keywords.get("patternProperties") {
+            if patterns.keys().any(|p| self.regexes[p].is_match(name)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Validates a `keyword` with a `value` applying to a string instance `s`.
+    fn validate_string(
+        &self,
+        keyword: &str,
+        value: &Value,
+        s: &str,
+        path: &[PathElement],
+    ) -> Vec<ValidationError> {
+        let length = s.chars().count() as u64;
+        match keyword {
+            "minLength" => {
+                let min = value.as_u64().unwrap_or_default();
+                if length < min {
+                    let message = format!("string has {length} characters, less than {min}");
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            "maxLength" => {
+                let max = value.as_u64().unwrap_or_default();
+                if length > max {
+                    let message = format!("string has {length} characters, more than {max}");
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            "pattern" => {
+                let pattern = value.as_str().unwrap_or_default();
+                if !self.regexes[pattern].is_match(s) {
+                    let message = format!("string does not match {pattern}");
+                    return vec![error(path, keyword, &message)];
+                }
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Validates a `keyword` with a `value` applying to a number instance `number`.
+fn validate_number(
+    keyword: &str,
+    value: &Value,
+    number: &Number,
+    path: &[PathElement],
+) -> Vec<ValidationError> {
+    let (actual, limit) = match (number.as_f64(), value.as_f64()) {
+        (Some(actual), Some(limit)) => (actual, limit),
+        _ => return vec![],
+    };
+    let message = match keyword {
+        "minimum" if actual < limit => format!("{number} is less than {value}"),
+        "exclusiveMinimum" if actual <= limit => {
+            format!("{number} is less than or equal to {value}")
+        }
+        "maximum" if actual > limit => format!("{number} is greater than {value}"),
+        "exclusiveMaximum" if actual >= limit => {
+            format!("{number} is greater than or equal to {value}")
+        }
+        "multipleOf" if !is_multiple_of(number, actual, value, limit) => {
+            format!("{number} is not a multiple of {value}")
+        }
+        _ => return vec![],
+    };
+    vec![error(path, keyword, &message)]
+}
+
+fn is_multiple_of(number: &Number, actual: f64, divisor: &Value, divisor_f64: f64) -> bool {
+    if let (Some(n), Some(d)) = (number.as_i64(), divisor.as_i64()) {
+        return n % d == 0;
+    }
+    let quotient = actual / divisor_f64;
+    (quotient - quotient.round()).abs() < 1e-9
+}
+
+/// Returns true if an `instance` is of the JSON Schema type `t`.
+fn is_type(instance: &Value, t: &str) -> bool {
+    match (t, instance) {
+        ("array", Value::Array(_))
+        | ("boolean", Value::Bool(_))
+        | ("null", Value::Null)
+        | ("number", Value::Number(_))
+        | ("object", Value::Object(_))
+        | ("string", Value::String(_)) => true,
+        ("integer", Value::Number(n)) => {
+            n.as_i64().is_some()
+                || n.as_u64().is_some()
+                || n.as_f64()
+                    .is_some_and(|f| f.is_finite() && f.fract() == 0.0)
+        }
+        _ => false,
+    }
+}
+
+/// Returns true if two JSON values are equal, numbers being compared by their value (`1` is equal
+/// to `1.0`).
+fn json_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a == b || a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| json_equal(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+fn schemas(value: &Value) -> &[Value] {
+    value.as_array().map(|v| v.as_slice()).unwrap_or_default()
+}
+
+fn strings(value: &Value) -> impl Iterator<Item = &str> {
+    schemas(value).iter().filter_map(|v| v.as_str())
+}
+
+fn child_path(path: &[PathElement], element: PathElement) -> Vec<PathElement> {
+    let mut path = path.to_vec();
+    path.push(element);
+    path
+}
+
+fn error(path: &[PathElement], keyword: &str, message: &str) -> ValidationError {
+    ValidationError {
+        instance_path: path_to_string(path),
+        keyword: keyword.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Returns the JSONPath expression of an instance `path`.
+fn path_to_string(path: &[PathElement]) -> String {
+    let mut s = "$".to_string();
+    for element in path {
+        match element {
+            PathElement::Index(index) => s.push_str(&format!("[{index}]")),
+            PathElement::Key(key) => {
+                if !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                    s.push_str(&format!(".{key}"));
+                } else {
+                    s.push_str(&format!("['{}']", key.replace('\'', "\\'")));
+                }
+            }
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn errors(schema: Value, instance: Value) -> Vec<String> {
+        Schema::new(schema)
+            .unwrap()
+            .validate(&instance)
+            .iter()
+            .map(|e| e.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_object() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer", "minimum": 1},
+                "name": {"type": "string", "minLength": 1},
+                "email": {"type": "string", "pattern": "^[^@]+@[^@]+$"},
+                "tags": {"type": "array", "items": {"type": "string"}, "uniqueItems": true},
+            },
+            "required": ["id", "name"],
+            "additionalProperties": false
+        });
+        assert!(errors(schema.clone(), json!({"id": 1, "name": "Bob"})).is_empty());
+        assert!(errors(schema.clone(), json!({"id": 1.0, "name": "Bob"})).is_empty());
+        assert_eq!(
+            errors(
+                schema.clone(),
+                json!({"id": 0, "email": "bob", "tags": ["a", 1, "a"], "age": 30})
+            ),
+            vec![
+                "$: property age is not allowed (additionalProperties)",
+                "$.email: string does not match ^[^@]+@[^@]+$ (pattern)",
+                "$.id: 0 is less than 1 (minimum)",
+                "$.tags[1]: value is not of type string (type)",
+                "$.tags: items 0 and 2 are equal (uniqueItems)",
+                "$: required property name is missing (required)",
+            ]
+        );
+        assert_eq!(
+            errors(schema, json!([])),
+            vec!["$: value is not of type object (type)"]
+        );
+    }
+
+    #[test]
+    fn test_array() {
+        let schema = json!({
+            "prefixItems": [{"const": "header"}],
+            "items": {"type": ["integer", "null"], "multipleOf": 2},
+            "contains": {"type": "null"},
+            "maxContains": 1,
+            "maxItems": 4,
+        });
+        assert!(errors(schema.clone(), json!(["header", 2, null, 4])).is_empty());
+        assert_eq!(
+            errors(schema, json!(["x", 3, 1.5, null, null])),
+            vec![
+                "$: array contains 2 matching items, more than 1 (maxContains)",
+                "$[1]: 3 is not a multiple of 2 (multipleOf)",
+                "$[2]: 1.5 is not a multiple of 2 (multipleOf)",
+                "$[2]: value is not of type integer or null (type)",
+                "$: array has 5 items, more than 4 (maxItems)",
+                "$[0]: value is not equal to \"header\" (const)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_applicators() {
+        let schema = json!({
+            "$defs": {
+                "positive": {"type": "number", "exclusiveMinimum": 0},
+            },
+            "properties": {
+                "a": {"anyOf": [{"type": "string"}, {"$ref": "#/$defs/positive"}]},
+                "b": {"oneOf": [{"type": "number"}, {"type": "integer"}]},
+                "c": {"not": {"enum": ["x", "y"]}},
+                "d": {
+                    "if": {"type": "string"},
+                    "then": {"maxLength": 2},
+                    "else": {"$ref": "#/$defs/positive"}
+                },
+            },
+            "dependentRequired": {"a": ["b"]},
+        });
+        assert!(errors(
+            schema.clone(),
+            json!({"a": 1, "b": 1.5, "c": "z", "d": "ab"})
+        )
+        .is_empty());
+        assert_eq!(
+            errors(schema, json!({"a": -1, "c": "x", "d": 0, "my key": 1})),
+            vec![
+                "$: property b is required by property a (dependentRequired)",
+                "$.a: value does not match any schema (anyOf)",
+                "$.c: value must not match the schema (not)",
+                "$.d: 0 is less than or equal to 0 (exclusiveMinimum)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive() {
+        let schema = json!({
+            "$anchor": "node",
+            "type": "object",
+            "properties": {"children": {"items": {"$ref": "#node"}}},
+            "required": ["name"],
+            "propertyNames": {"pattern": "^[a-z]+$"}
+        });
+        assert_eq!(
+            errors(
+                schema,
+                json!({"name": "root", "children": [{"name": "a"}, {"children": [], "my key": 1}]})
+            ),
+            vec![
+                "$.children[1]: property name my key is not valid (propertyNames)",
+                "$.children[1]: required property name is missing (required)",
+            ]
+        );
+        assert_eq!(
+            errors(json!({"$ref": "#"}), json!(1)),
+            vec!["$: too many nested references ($ref)"]
+        );
+        assert_eq!(
+            errors(json!(false), json!(1)),
+            vec!["$: no value is allowed (false)"]
+        );
+    }
+
+    #[test]
+    fn test_path_to_string() {
+        let path = vec![
+            PathElement::Key("users".to_string()),
+            PathElement::Index(0),
+            PathElement::Key("first name".to_string()),
+            PathElement::Key("it's".to_string()),
+        ];
+        assert_eq!(path_to_string(&path), "$.users[0]['first name']['it\\'s']");
+    }
+}
@@ -43,6 +43,8 @@ mod html;
 pub mod http;
 mod json;
 mod jsonpath;
+mod jsonschema;
+pub mod openapi;
 pub mod output;
 pub mod report;
 pub mod runner;
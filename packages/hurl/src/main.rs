@@ -17,18 +17,23 @@
  */
 mod cli;
 
+use std::collections::HashMap;
 use std::io::prelude::*;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 use std::{env, process};
 
 use colored::control;
-use hurl::report::{html, junit, tap};
-use hurl::runner::HurlResult;
-use hurl::util::logger::{BaseLogger, Logger, LoggerOptionsBuilder, Verbosity};
-use hurl::{output, runner};
+use hurl::openapi::OpenApi;
+use hurl::report::load::LoadReport;
+use hurl::report::{curl, html, junit, tap};
+use hurl::runner::{HurlResult, Output, Value};
+use hurl::util::logger::{BaseLogger, Logger};
+use hurl::util::term::{Stderr, WriteMode};
+use hurl::{http, output, runner};
+use hurl_core::ast::Retry;
 
-use crate::cli::options::OptionsError;
+use crate::cli::options::{OptionsError, Profile};
 
 const EXIT_OK: i32 = 0;
 const EXIT_ERROR_COMMANDLINE: i32 = 1;
@@ -44,9 +49,58 @@ struct HurlRun {
     content: String,
     /// Filename of the content
     filename: String,
+    /// Index of the data row (starting at 1) this file has been run with, see [`Job`]
+    row: Option<usize>,
     hurl_result: HurlResult,
 }
 
+impl HurlRun {
+    /// Returns the name of this run in the logs and in the reports.
+    fn name(&self) -> String {
+        run_name(&self.filename, self.row)
+    }
+}
+
+/// A Hurl file to run, with its variables.
+///
+/// With `--data`, each input file is run once per data row, the values of the row being added to
+/// the variables.
+#[derive(Clone, Debug)]
+struct Job {
+    /// Filename of the Hurl file
+    filename: String,
+    /// Index of the data row (starting at 1), `None` without `--data`
+    row: Option<usize>,
+    variables: HashMap<String, Value>,
+}
+
+impl Job {
+    /// Returns the name of this job in the logs and in the reports.
+    fn name(&self) -> String {
+        run_name(&self.filename, self.row)
+    }
+}
+
+/// Returns the name of the run of `filename` with the data `row` (for instance
+/// `create_user.hurl[row 3]`).
+fn run_name(filename: &str, row: Option<usize>) -> String {
+    match row {
+        Some(row) => format!("{filename}[row {row}]"),
+        None => filename.to_string(),
+    }
+}
+
+/// The outcome of a Hurl file run by a worker: the logs of the run are kept so they can be
+/// displayed once all the previous files have been displayed.
+struct ParallelRun<'a> {
+    /// Index of the job in the jobs list
+    current: usize,
+    job: &'a Job,
+    content: String,
+    hurl_result: Result<HurlResult, String>,
+    logs: String,
+}
+
 /// Executes Hurl entry point.
 fn main() {
     init_colored();
@@ -72,118 +126,772 @@ fn main() {
     // We create a basic logger that can just display info, warning or error generic messages.
     // We'll use a more advanced logger for rich error report when running Hurl files.
     let verbose = opts.verbose || opts.very_verbose || opts.interactive;
-    let base_logger = BaseLogger::new(opts.color, verbose);
+    let secrets = opts
+        .secrets
+        .values()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>();
+    let base_logger = BaseLogger::new(opts.color, verbose, &secrets);
     let current_dir = env::current_dir();
     let current_dir = unwrap_or_exit(current_dir, EXIT_ERROR_UNDEFINED, &base_logger);
     let current_dir = current_dir.as_path();
+
+    if let Some(profile) = &opts.profile {
+        log_profile(profile, &opts, &base_logger);
+    }
+
+    if let Some(filename) = &opts.merge_junit {
+        base_logger.debug(format!("Merging JUnit reports to {filename}").as_str());
+        let result = junit::merge_reports(filename, &opts.input_files);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, &base_logger);
+        process::exit(EXIT_OK);
+    }
+
+    if let Some(shard) = &opts.shard {
+        // With more shards than input files, some shards are empty.
+        if opts.input_files.is_empty() {
+            base_logger.info(format!("No input file in shard {shard}").as_str());
+            process::exit(EXIT_OK);
+        }
+        base_logger.debug(format!("Running shard {shard}: {:?}", opts.input_files).as_str());
+    }
+
+    if opts.load {
+        let code = run_load(&opts, current_dir, &base_logger);
+        process::exit(code);
+    }
+
+    if opts.dry_run {
+        let code = run_dry_run(&opts, current_dir, &base_logger);
+        process::exit(code);
+    }
+
+    if opts.serve {
+        let code = run_serve(&opts, current_dir, &base_logger);
+        process::exit(code);
+    }
+
+    // In watch mode, reports are saved before the first run, to be written again from their
+    // initial content after each re-run.
+    let reports = if opts.watch {
+        cli::watch::Reports::save(&appended_reports(&opts))
+    } else {
+        cli::watch::Reports::save(&[])
+    };
     let start = Instant::now();
+    let runs = run_suite(&opts.input_files, &opts, current_dir, &base_logger);
+    write_reports(&runs, &opts, &base_logger);
+
+    if let Some(openapi) = &opts.openapi {
+        let coverage = get_openapi_coverage(&runs, openapi);
+        base_logger.info(coverage.as_str());
+    }
+
+    if opts.test || opts.watch {
+        let duration = start.elapsed().as_millis();
+        let summary = get_summary(&runs, duration);
+        base_logger.info(summary.as_str());
+    }
+
+    if opts.watch {
+        watch(runs, &reports, &opts, current_dir, &base_logger);
+    }
+
+    process::exit(exit_code(&runs));
+}
+
+/// Logs the values of the `--env` `profile`, and whether they're used or overridden by a source
+/// of higher precedence. The values of the variables are not logged.
+fn log_profile(profile: &Profile, opts: &cli::options::Options, base_logger: &BaseLogger) {
+    let name = &profile.name;
+    let file = profile.file.display();
+    base_logger.debug(format!("Environment {name} loaded from {file}").as_str());
+    base_logger.debug(format!("Options precedence: command line > environment {name}").as_str());
+    base_logger.debug(
+        format!(
+            "Variables precedence: --variable > --variables-file > HURL_* > environment {name}"
+        )
+        .as_str(),
+    );
+    let source = |used: bool| {
+        if used {
+            format!("environment {name}")
+        } else {
+            "overridden".to_string()
+        }
+    };
+    let mut values = vec![];
+    if let Some(cacert_file) = &profile.cacert_file {
+        let used = opts.cacert_file.as_ref() == Some(cacert_file);
+        values.push(("cacert", cacert_file.clone(), used));
+    }
+    if !profile.connects_to.is_empty() {
+        let used = opts.connects_to == profile.connects_to;
+        values.push(("connect-to", profile.connects_to.join(", "), used));
+    }
+    if let Some(insecure) = profile.insecure {
+        values.push(("insecure", insecure.to_string(), opts.insecure == insecure));
+    }
+    if let Some(proxy) = &profile.proxy {
+        let used = opts.proxy.as_ref() == Some(proxy);
+        values.push(("proxy", proxy.clone(), used));
+    }
+    if let Some(retry) = profile.retry {
+        // Retries are displayed with the `--retry` notation.
+        let value = match retry {
+            Retry::None => "0".to_string(),
+            Retry::Finite(n) => n.to_string(),
+            Retry::Infinite => "-1".to_string(),
+        };
+        values.push(("retry", value, opts.retry == retry));
+    }
+    for (option, value, used) in values {
+        let source = source(used);
+        base_logger.debug(format!("    {option}: {value} ({source})").as_str());
+    }
+
+    // Variables can hold secrets, only their names are logged.
+    let mut variables = profile.variables.iter().collect::<Vec<_>>();
+    variables.sort_by_key(|(name, _)| *name);
+    for (variable, value) in variables {
+        let source = source(opts.variables.get(variable) == Some(value));
+        base_logger.debug(format!("    variable {variable} ({source})").as_str());
+    }
+}
+
+/// Runs the Hurl `files`, between the optional setup and teardown files.
+///
+/// The values captured by the setup file are added to the variables of `files` and of the
+/// teardown file. The teardown file is run even if some files have failed. The returned runs
+/// include the setup and teardown runs, so they're reported as any other file.
+fn run_suite(
+    files: &[String],
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> Vec<HurlRun> {
     let mut runs = vec![];
+    let mut opts = opts.clone();
 
-    for (current, filename) in opts.input_files.iter().enumerate() {
+    let mut setup_success = true;
+    if let Some(filename) = &opts.setup {
+        base_logger.debug(format!("Running setup file {filename}").as_str());
+        if let Some(run) = run_hook(filename, &opts, current_dir, base_logger) {
+            for capture in run.hurl_result.entries.iter().flat_map(|e| &e.captures) {
+                // Redacted captures of the setup file remain secrets in the other files.
+                if run.hurl_result.secrets.contains(&capture.value.to_string()) {
+                    opts.secrets
+                        .insert(capture.name.clone(), capture.value.clone());
+                }
+                opts.variables
+                    .insert(capture.name.clone(), capture.value.clone());
+            }
+            setup_success = run.hurl_result.success;
+            runs.push(run);
+        } else {
+            setup_success = false;
+        }
+    }
+
+    if setup_success {
+        runs.extend(run_files(files, &opts, current_dir, base_logger));
+    } else {
+        base_logger.error("Setup file has failed, input files are not run");
+    }
+
+    if let Some(filename) = &opts.teardown {
+        base_logger.debug(format!("Running teardown file {filename}").as_str());
+        if let Some(run) = run_hook(filename, &opts, current_dir, base_logger) {
+            runs.push(run);
+        }
+    }
+
+    // Secrets of the runs, such as redacted captures, are also redacted from the next logs.
+    for secret in runs.iter().flat_map(|r| &r.hurl_result.secrets) {
+        base_logger.add_secret(secret);
+    }
+    runs
+}
+
+/// Runs a setup or teardown Hurl file `filename`.
+///
+/// Contrary to input files, the response body of the file is not written to the output. In watch
+/// mode, `None` is returned if the file can't be read or parsed.
+fn run_hook(
+    filename: &str,
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> Option<HurlRun> {
+    let stderr = Stderr::default();
+    let content = if !Path::new(filename).exists() {
+        let message = format!("hurl: cannot access '{filename}': No such file or directory");
+        Err(cli::CliError { message })
+    } else {
+        cli::read_to_string(filename)
+    };
+    let content = match content {
+        Ok(c) => c,
+        Err(e) if opts.watch => {
+            base_logger.error(&e.to_string());
+            return None;
+        }
+        Err(e) => exit_with_error(&e.to_string(), EXIT_ERROR_PARSING, base_logger),
+    };
+
+    let variables = &opts.variables;
+    let hurl_result = match execute(&content, filename, current_dir, opts, variables, &stderr) {
+        Ok(h) => h,
+        Err(_) if opts.watch => return None,
+        Err(_) => process::exit(EXIT_ERROR_PARSING),
+    };
+    let logger_options = opts.to_logger_options(filename, &stderr);
+    let logger = Logger::from(&logger_options);
+    logger.test_completed(&hurl_result);
+
+    Some(HurlRun {
+        content,
+        filename: filename.to_string(),
+        row: None,
+        hurl_result,
+    })
+}
+
+/// Runs the Hurl `files`, sequentially or in parallel.
+fn run_files(
+    files: &[String],
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> Vec<HurlRun> {
+    let jobs = get_jobs(files, opts);
+    if opts.jobs > 1 {
+        run_parallel(&jobs, opts, current_dir, base_logger)
+    } else {
+        run_sequential(&jobs, opts, current_dir, base_logger)
+    }
+}
+
+/// Returns the jobs to run for the Hurl `files`: one job per file, or with `--data`, one job per
+/// file and per data row.
+fn get_jobs(files: &[String], opts: &cli::options::Options) -> Vec<Job> {
+    let rows = match &opts.data {
+        Some(rows) => rows,
+        None => {
+            return files
+                .iter()
+                .map(|filename| Job {
+                    filename: filename.clone(),
+                    row: None,
+                    variables: opts.variables.clone(),
+                })
+                .collect();
+        }
+    };
+    let mut jobs = vec![];
+    for filename in files {
+        for (index, row) in rows.iter().enumerate() {
+            let mut variables = opts.variables.clone();
+            variables.extend(row.clone());
+            jobs.push(Job {
+                filename: filename.clone(),
+                row: Some(index + 1),
+                variables,
+            });
+        }
+    }
+    jobs
+}
+
+/// Runs the Hurl `jobs` one after another.
+fn run_sequential(
+    jobs: &[Job],
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> Vec<HurlRun> {
+    let mut runs = vec![];
+    let stderr = Stderr::default();
+
+    for (current, job) in jobs.iter().enumerate() {
+        let filename = &job.filename;
         // We check the input file existence and check that we can read its contents.
         // Once the preconditions succeed, we can parse the Hurl file, and run it.
         if filename != "-" && !Path::new(filename).exists() {
             let message = format!("hurl: cannot access '{filename}': No such file or directory");
-            exit_with_error(&message, EXIT_ERROR_PARSING, &base_logger);
+            if opts.watch {
+                base_logger.error(&message);
+                continue;
+            }
+            exit_with_error(&message, EXIT_ERROR_PARSING, base_logger);
         }
         let content = cli::read_to_string(filename.as_str());
-        let content = unwrap_or_exit(content, EXIT_ERROR_PARSING, &base_logger);
-
-        let verbosity = Verbosity::from(opts.verbose, opts.very_verbose);
-        let logger_options = LoggerOptionsBuilder::new()
-            .color(opts.color)
-            .error_format(opts.error_format.into())
-            .filename(filename)
-            .progress_bar(opts.progress_bar)
-            .test(opts.test)
-            .verbosity(verbosity)
-            .build();
+        let content = match content {
+            Ok(c) => c,
+            Err(e) if opts.watch => {
+                base_logger.error(&e.to_string());
+                continue;
+            }
+            Err(e) => exit_with_error(&e.to_string(), EXIT_ERROR_PARSING, base_logger),
+        };
+
+        let logger_options = opts.to_logger_options(&job.name(), &stderr);
         let logger = Logger::from(&logger_options);
-        let total = opts.input_files.len();
+        let total = jobs.len();
         logger.test_running(current + 1, total);
 
         // Run our Hurl file now
-        let hurl_result = execute(&content, filename, current_dir, &opts);
+        let variables = &job.variables;
+        let hurl_result = execute(&content, filename, current_dir, opts, variables, &stderr);
         let hurl_result = match hurl_result {
             Ok(h) => h,
+            // In watch mode, the parsing error has been logged and we wait for the file to be fixed.
+            Err(_) if opts.watch => continue,
             Err(_) => process::exit(EXIT_ERROR_PARSING),
         };
 
         logger.test_completed(&hurl_result);
-        let success = hurl_result.success;
-
-        // We can output the result, either the raw body or a structured JSON representation.
-        let output_body = success
-            && !opts.interactive
-            && matches!(opts.output_type, cli::OutputType::ResponseBody);
-        if output_body {
-            let include_headers = opts.include;
-            let result = output::write_body(
-                &hurl_result,
-                filename,
-                include_headers,
-                opts.color,
-                &opts.output,
-                &logger,
-            );
-            unwrap_or_exit(result, EXIT_ERROR_RUNTIME, &base_logger);
-        }
-        if matches!(opts.output_type, cli::OutputType::Json) {
-            let result = output::write_json(&hurl_result, &content, filename, &opts.output);
-            unwrap_or_exit(result, EXIT_ERROR_RUNTIME, &base_logger);
-        }
 
         let run = HurlRun {
             content,
-            filename: filename.to_string(),
+            filename: filename.clone(),
+            row: job.row,
             hurl_result,
         };
+        write_output(&run, opts, &logger, base_logger);
         runs.push(run);
     }
+    runs
+}
+
+/// Runs the Hurl `jobs` on a pool of `opts.jobs` workers.
+///
+/// Each job is run with its own HTTP client and variables, and its logs are buffered. Once a
+/// job has been run, its logs are displayed, in the jobs order, so the console output is the
+/// same as a sequential run.
+fn run_parallel(
+    jobs: &[Job],
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> Vec<HurlRun> {
+    let mut runs = vec![];
+    let total = jobs.len();
+    let stderr = Stderr::default();
 
-    if let Some(filename) = opts.junit_file {
+    // As in a sequential run, a missing or invalid file stops the run before the next files are
+    // run: the files are read and parsed before starting the workers. In watch mode, the jobs of
+    // these files are not run.
+    let mut contents = HashMap::new();
+    for job in jobs {
+        let filename = &job.filename;
+        if contents.contains_key(filename) {
+            continue;
+        }
+        let content = read_and_parse(filename, opts, current_dir, &stderr, base_logger);
+        contents.insert(filename.clone(), content);
+    }
+    let jobs = jobs
+        .iter()
+        .enumerate()
+        .filter_map(|(current, job)| {
+            let content = contents.get(&job.filename)?.as_ref()?;
+            Some((current, job, content))
+        })
+        .collect::<Vec<_>>();
+
+    cli::parallel::run(
+        jobs,
+        opts.jobs,
+        |(current, job, content)| {
+            let worker_stderr = Stderr::new(WriteMode::Buffered);
+            let variables = &job.variables;
+            let hurl_result = execute(
+                content,
+                &job.filename,
+                current_dir,
+                opts,
+                variables,
+                &worker_stderr,
+            );
+            ParallelRun {
+                current,
+                job,
+                content: content.clone(),
+                hurl_result,
+                logs: worker_stderr.take_buffer(),
+            }
+        },
+        |job| {
+            let logger_options = opts.to_logger_options(&job.job.name(), &stderr);
+            let logger = Logger::from(&logger_options);
+            logger.test_running(job.current + 1, total);
+            stderr.eprint(&job.logs);
+
+            let hurl_result = match job.hurl_result {
+                Ok(h) => h,
+                Err(_) if opts.watch => return,
+                Err(_) => process::exit(EXIT_ERROR_PARSING),
+            };
+
+            logger.test_completed(&hurl_result);
+
+            let run = HurlRun {
+                content: job.content,
+                filename: job.job.filename.clone(),
+                row: job.job.row,
+                hurl_result,
+            };
+            write_output(&run, opts, &logger, base_logger);
+            runs.push(run);
+        },
+    );
+    runs
+}
+
+/// Reads and parses the Hurl file `filename` of a parallel run, and returns its content.
+///
+/// If the file can't be read or parsed, the error is logged and the process exits, or in watch
+/// mode, `None` is returned.
+fn read_and_parse(
+    filename: &str,
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    stderr: &Stderr,
+    base_logger: &BaseLogger,
+) -> Option<String> {
+    let content = if filename != "-" && !Path::new(filename).exists() {
+        let message = format!("hurl: cannot access '{filename}': No such file or directory");
+        Err(cli::CliError { message })
+    } else {
+        cli::read_to_string(filename)
+    };
+    let content = match content {
+        Ok(c) => c,
+        Err(e) if opts.watch => {
+            base_logger.error(&e.to_string());
+            return None;
+        }
+        Err(e) => exit_with_error(&e.to_string(), EXIT_ERROR_PARSING, base_logger),
+    };
+    let runner_options = opts.to_runner_options(filename, current_dir);
+    let logger_options = opts.to_logger_options(filename, stderr);
+    match runner::parse(&content, &runner_options, &logger_options) {
+        Ok(()) => Some(content),
+        Err(_) if opts.watch => None,
+        Err(_) => process::exit(EXIT_ERROR_PARSING),
+    }
+}
+
+/// Runs each input file as a load test, and writes the load reports to the output.
+///
+/// Iterations with errors don't stop the load test, but are reflected in the returned exit code,
+/// as for a test run.
+fn run_load(opts: &cli::options::Options, current_dir: &Path, base_logger: &BaseLogger) -> i32 {
+    let mut reports = String::new();
+    let mut count_errors_runner = 0;
+    let mut count_errors_assert = 0;
+
+    for filename in &opts.input_files {
+        if filename != "-" && !Path::new(filename).exists() {
+            let message = format!("hurl: cannot access '{filename}': No such file or directory");
+            exit_with_error(&message, EXIT_ERROR_PARSING, base_logger);
+        }
+        let content = cli::read_to_string(filename.as_str());
+        let content = unwrap_or_exit(content, EXIT_ERROR_PARSING, base_logger);
+
+        let users = opts.users;
+        let duration = opts.duration.as_secs_f64();
+        base_logger.info(
+            format!("Running load test on {filename}: {users} users during {duration:.1} s")
+                .as_str(),
+        );
+        let runner_options = opts.to_runner_options(filename, current_dir);
+        let mut report = LoadReport::new(users);
+        let start = Instant::now();
+        let result = cli::load::run(
+            users,
+            opts.duration,
+            || {
+                // Logs of the iterations are not displayed, except for parsing errors.
+                let stderr = Stderr::new(WriteMode::Buffered);
+                let logger_options = opts.to_logger_options(filename, &stderr);
+                match runner::run(&content, &runner_options, &opts.variables, &logger_options) {
+                    Ok(hurl_result) => Ok(hurl_result),
+                    Err(_) => Err(stderr.take_buffer()),
+                }
+            },
+            |hurl_result| {
+                let errors = hurl_result.errors();
+                if errors.iter().any(|e| !e.assert) {
+                    count_errors_runner += 1;
+                } else if !errors.is_empty() {
+                    count_errors_assert += 1;
+                }
+                report.add(&hurl_result);
+            },
+        );
+        if let Err(logs) = result {
+            Stderr::default().eprint(&logs);
+            process::exit(EXIT_ERROR_PARSING);
+        }
+        report.duration = start.elapsed();
+
+        if matches!(opts.output_type, cli::OutputType::Json) {
+            let json = serde_json::to_string(&report.to_json(filename)).unwrap();
+            reports.push_str(&format!("{json}\n"));
+        } else {
+            reports.push_str(&report.to_text());
+        }
+    }
+
+    let output = match &opts.output {
+        Some(filename) => Output::File(filename.to_string()),
+        None => Output::StdOut,
+    };
+    let result = output
+        .write(reports.as_bytes())
+        .map_err(output::Error::from);
+    unwrap_or_exit(result, EXIT_ERROR_RUNTIME, base_logger);
+
+    if count_errors_runner > 0 {
+        EXIT_ERROR_RUNTIME
+    } else if count_errors_assert > 0 {
+        EXIT_ERROR_ASSERT
+    } else {
+        EXIT_OK
+    }
+}
+
+/// Renders the requests of each input file without sending them, and writes them to the output.
+///
+/// With `--data`, the requests of each data row are rendered.
+fn run_dry_run(opts: &cli::options::Options, current_dir: &Path, base_logger: &BaseLogger) -> i32 {
+    let mut requests = String::new();
+    let mut success = true;
+    let stderr = Stderr::default();
+
+    for job in get_jobs(&opts.input_files, opts) {
+        let filename = &job.filename;
+        if filename != "-" && !Path::new(filename).exists() {
+            let message = format!("hurl: cannot access '{filename}': No such file or directory");
+            exit_with_error(&message, EXIT_ERROR_PARSING, base_logger);
+        }
+        let content = cli::read_to_string(filename.as_str());
+        let content = unwrap_or_exit(content, EXIT_ERROR_PARSING, base_logger);
+
+        let runner_options = opts.to_runner_options(filename, current_dir);
+        let logger_options = opts.to_logger_options(filename, &stderr);
+        let result = runner::dry_run(&content, &runner_options, &job.variables, &logger_options);
+        let dry_run_result = match result {
+            Ok(r) => r,
+            Err(_) => process::exit(EXIT_ERROR_PARSING),
+        };
+        success &= dry_run_result.success;
+        let text = output::format_dry_run(&dry_run_result, filename, opts.dry_run_format);
+        requests.push_str(&text);
+    }
+
+    let output = match &opts.output {
+        Some(filename) => Output::File(filename.to_string()),
+        None => Output::StdOut,
+    };
+    let result = output
+        .write(requests.as_bytes())
+        .map_err(output::Error::from);
+    unwrap_or_exit(result, EXIT_ERROR_RUNTIME, base_logger);
+
+    if success {
+        EXIT_OK
+    } else {
+        EXIT_ERROR_RUNTIME
+    }
+}
+
+/// Serves the input file as a local mock server, until the process is stopped.
+fn run_serve(opts: &cli::options::Options, current_dir: &Path, base_logger: &BaseLogger) -> i32 {
+    let filename = &opts.input_files[0];
+    if filename != "-" && !Path::new(filename).exists() {
+        let message = format!("hurl: cannot access '{filename}': No such file or directory");
+        exit_with_error(&message, EXIT_ERROR_PARSING, base_logger);
+    }
+    let content = cli::read_to_string(filename.as_str());
+    let content = unwrap_or_exit(content, EXIT_ERROR_PARSING, base_logger);
+
+    let stderr = Stderr::default();
+    let runner_options = opts.to_runner_options(filename, current_dir);
+    let logger_options = opts.to_logger_options(filename, &stderr);
+    let variables = &opts.variables;
+    let result = runner::serve(
+        &content,
+        &runner_options,
+        variables,
+        &logger_options,
+        opts.port,
+    );
+    match result {
+        Ok(()) => EXIT_OK,
+        Err(_) => EXIT_ERROR_RUNTIME,
+    }
+}
+
+/// Outputs the result of a Hurl file `run`.
+///
+/// The result can be either the raw body of the last response or a structured JSON representation.
+fn write_output(
+    run: &HurlRun,
+    opts: &cli::options::Options,
+    logger: &Logger,
+    base_logger: &BaseLogger,
+) {
+    let output_body = run.hurl_result.success
+        && !opts.interactive
+        && matches!(opts.output_type, cli::OutputType::ResponseBody);
+    if output_body {
+        let include_headers = opts.include;
+        let result = output::write_body(
+            &run.hurl_result,
+            &run.filename,
+            include_headers,
+            opts.color,
+            &opts.output,
+            logger,
+        );
+        unwrap_or_exit(result, EXIT_ERROR_RUNTIME, base_logger);
+    }
+    if matches!(opts.output_type, cli::OutputType::Json) {
+        let result =
+            output::write_json(&run.hurl_result, &run.content, &run.filename, &opts.output);
+        unwrap_or_exit(result, EXIT_ERROR_RUNTIME, base_logger);
+    }
+}
+
+/// Writes the JUnit, TAP, HTML reports, the cookies file, the curl script and the cassette of
+/// these `runs`.
+fn write_reports(runs: &[HurlRun], opts: &cli::options::Options, base_logger: &BaseLogger) {
+    if let Some(filename) = &opts.junit_file {
         base_logger.debug(format!("Writing JUnit report to {filename}").as_str());
-        let result = create_junit_report(&runs, &filename);
-        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, &base_logger);
+        let result = create_junit_report(runs, filename);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
     }
 
-    if let Some(filename) = opts.tap_file {
+    if let Some(filename) = &opts.tap_file {
         base_logger.debug(format!("Writing TAP report to {filename}").as_str());
-        let result = create_tap_report(&runs, &filename);
-        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, &base_logger);
+        let result = create_tap_report(runs, filename);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
     }
 
-    if let Some(dir) = opts.html_dir {
+    if let Some(dir) = &opts.html_dir {
         base_logger.debug(format!("Writing HTML report to {}", dir.display()).as_str());
-        let result = create_html_report(&runs, &dir);
-        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, &base_logger);
+        let result = create_html_report(runs, dir);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
     }
 
-    if let Some(filename) = opts.cookie_output_file {
+    if let Some(filename) = &opts.cookie_output_file {
         base_logger.debug(format!("Writing cookies to {filename}").as_str());
-        let result = create_cookies_file(&runs, &filename);
-        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, &base_logger);
+        let result = create_cookies_file(runs, filename);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
+    }
+
+    if let Some(filename) = &opts.curl_file {
+        base_logger.debug(format!("Writing curl script to {filename}").as_str());
+        let result = create_curl_script(runs, filename);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
+    }
+
+    if let Some(filename) = &opts.record_file {
+        base_logger.debug(format!("Recording HTTP calls to {filename}").as_str());
+        let result = create_cassette(runs, filename);
+        unwrap_or_exit(result, EXIT_ERROR_UNDEFINED, base_logger);
     }
+}
 
-    if opts.test {
+/// Returns the paths of the reports that are appended to on each write.
+fn appended_reports(opts: &cli::options::Options) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Some(filename) = &opts.junit_file {
+        paths.push(PathBuf::from(filename));
+    }
+    if let Some(filename) = &opts.tap_file {
+        paths.push(PathBuf::from(filename));
+    }
+    if let Some(dir) = &opts.html_dir {
+        paths.push(dir.join("index.html"));
+    }
+    paths
+}
+
+/// Watches the input files, and the files they reference, and re-runs the affected input files
+/// on every change. `runs` are the results of the initial run.
+///
+/// After each re-run, reports are written again from their initial content `reports`, and a
+/// fresh summary of all the input files is printed.
+fn watch(
+    runs: Vec<HurlRun>,
+    reports: &cli::watch::Reports,
+    opts: &cli::options::Options,
+    current_dir: &Path,
+    base_logger: &BaseLogger,
+) -> ! {
+    // Keeps the last run of each input file.
+    let mut last_runs = runs
+        .into_iter()
+        .map(|r| (r.name(), r))
+        .collect::<HashMap<_, _>>();
+    let mut watcher = cli::watch::Watcher::new();
+    for filename in &opts.input_files {
+        let context_dir = opts.context_dir(filename, current_dir);
+        watcher.watch(filename, &context_dir);
+    }
+
+    loop {
+        base_logger.info("Waiting for changes...");
+        let files = watcher.wait_for_changes();
+        let start = Instant::now();
+        for job in get_jobs(&files, opts) {
+            last_runs.remove(&job.name());
+        }
+        // Setup and teardown files are run again with the changed files.
+        for run in run_suite(&files, opts, current_dir, base_logger) {
+            last_runs.insert(run.name(), run);
+        }
+        // Referenced files may have been added or removed by the changes.
+        for filename in &files {
+            let context_dir = opts.context_dir(filename, current_dir);
+            watcher.watch(filename, &context_dir);
+        }
+
+        let names = get_jobs(&opts.input_files, opts)
+            .into_iter()
+            .map(|job| job.name());
+        let runs = opts
+            .setup
+            .iter()
+            .cloned()
+            .chain(names)
+            .chain(opts.teardown.iter().cloned())
+            .filter_map(|name| last_runs.get(&name))
+            .cloned()
+            .collect::<Vec<_>>();
+        unwrap_or_exit(reports.restore(), EXIT_ERROR_UNDEFINED, base_logger);
+        write_reports(&runs, opts, base_logger);
         let duration = start.elapsed().as_millis();
         let summary = get_summary(&runs, duration);
         base_logger.info(summary.as_str());
     }
-
-    process::exit(exit_code(&runs));
 }
 
-/// Runs a Hurl `content` and returns a result.
+/// Runs a Hurl `content` with `variables` and returns a result.
 fn execute(
     content: &str,
     filename: &str,
     current_dir: &Path,
     cli_options: &cli::options::Options,
+    variables: &HashMap<String, Value>,
+    stderr: &Stderr,
 ) -> Result<HurlResult, String> {
-    let variables = &cli_options.variables;
     let runner_options = cli_options.to_runner_options(filename, current_dir);
-    let logger_options = cli_options.to_logger_options(filename);
+    let logger_options = cli_options.to_logger_options(filename, stderr);
     runner::run(content, &runner_options, variables, &logger_options)
 }
 
@@ -221,7 +929,7 @@ fn exit_with_error(message: &str, code: i32, logger: &BaseLogger) -> ! {
 fn create_junit_report(runs: &[HurlRun], filename: &str) -> Result<(), cli::CliError> {
     let testcases: Vec<junit::Testcase> = runs
         .iter()
-        .map(|r| junit::Testcase::from(&r.hurl_result, &r.content, &r.filename))
+        .map(|r| junit::Testcase::from(&r.hurl_result, &r.content, &r.name()))
         .collect();
     junit::write_report(filename, &testcases)?;
     Ok(())
@@ -231,12 +939,43 @@ fn create_junit_report(runs: &[HurlRun], filename: &str) -> Result<(), cli::CliE
 fn create_tap_report(runs: &[HurlRun], filename: &str) -> Result<(), cli::CliError> {
     let testcases: Vec<tap::Testcase> = runs
         .iter()
-        .map(|r| tap::Testcase::from(&r.hurl_result, &r.filename))
+        .map(|r| tap::Testcase::from(&r.hurl_result, &r.name()))
         .collect();
     tap::write_report(filename, &testcases)?;
     Ok(())
 }
 
+/// Creates a curl script of the requests executed by these `runs`.
+fn create_curl_script(runs: &[HurlRun], filename: &str) -> Result<(), cli::CliError> {
+    let results = runs
+        .iter()
+        .map(|r| (&r.hurl_result, r.filename.as_str()))
+        .collect::<Vec<_>>();
+    curl::write_script(filename, &results)?;
+    Ok(())
+}
+
+/// Creates a cassette of the HTTP calls of these `runs`, to be replayed with `--replay`.
+fn create_cassette(runs: &[HurlRun], filename: &str) -> Result<(), cli::CliError> {
+    let calls = runs
+        .iter()
+        .flat_map(|r| &r.hurl_result.entries)
+        .flat_map(|e| e.calls.clone())
+        .collect::<Vec<_>>();
+    let secrets = runs
+        .iter()
+        .flat_map(|r| r.hurl_result.secrets.clone())
+        .collect::<Vec<_>>();
+    let cassette = http::Cassette::new(calls);
+    let content = serde_json::to_string_pretty(&cassette.to_json(&secrets)).unwrap();
+    if let Err(why) = std::fs::write(filename, content) {
+        return Err(cli::CliError {
+            message: format!("Issue writing to {filename}: {why:?}"),
+        });
+    }
+    Ok(())
+}
+
 /// Create an HTML report for this run.
 fn create_html_report(runs: &[HurlRun], dir_path: &Path) -> Result<(), cli::CliError> {
     // We ensure that the containing folder exists.
@@ -244,7 +983,7 @@ fn create_html_report(runs: &[HurlRun], dir_path: &Path) -> Result<(), cli::CliE
 
     let mut testcases = vec![];
     for run in runs.iter() {
-        let testcase = html::Testcase::from(&run.hurl_result, &run.filename);
+        let testcase = html::Testcase::from(&run.hurl_result, &run.name());
         testcase.write_html(&run.content, &run.hurl_result.entries, dir_path)?;
         testcases.push(testcase);
     }
@@ -326,6 +1065,41 @@ fn get_summary(runs: &[HurlRun], duration: u128) -> String {
     )
 }
 
+/// Returns the coverage of the operations of an `openapi` document by the HTTP calls of `runs`,
+/// listing the operations that have not been exercised.
+fn get_openapi_coverage(runs: &[HurlRun], openapi: &OpenApi) -> String {
+    let exercised = runs
+        .iter()
+        .flat_map(|r| &r.hurl_result.entries)
+        .flat_map(|e| &e.calls)
+        .filter_map(|c| openapi.find_operation(&c.request.method, &c.request.url))
+        .collect::<Vec<_>>();
+    let not_exercised = openapi
+        .operations()
+        .iter()
+        .filter(|o| !exercised.contains(o))
+        .collect::<Vec<_>>();
+    let total = openapi.operations().len();
+    let covered = total - not_exercised.len();
+    let covered_percent = if total == 0 {
+        100.0
+    } else {
+        100.0 * covered as f32 / total as f32
+    };
+    let mut coverage =
+        format!("OpenAPI coverage: {covered}/{total} operations ({covered_percent:.1}%)");
+    if !not_exercised.is_empty() {
+        coverage.push_str("\nOperations not exercised:");
+        for operation in not_exercised {
+            coverage.push_str(&format!("\n    {operation}"));
+            if let Some(id) = &operation.id {
+                coverage.push_str(&format!(" ({id})"));
+            }
+        }
+    }
+    coverage
+}
+
 #[cfg(test)]
 pub mod tests {
     use hurl::runner::EntryResult;
@@ -338,23 +1112,31 @@ pub mod tests {
         fn new_run(success: bool, entries_count: usize) -> HurlRun {
             let dummy_entry = EntryResult {
                 entry_index: 0,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
                 calls: vec![],
                 captures: vec![],
                 asserts: vec![],
                 errors: vec![],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: false,
             };
             HurlRun {
                 content: String::new(),
                 filename: String::new(),
+                row: None,
                 hurl_result: HurlResult {
                     entries: vec![dummy_entry; entries_count],
                     time_in_ms: 0,
                     success,
                     cookies: vec![],
                     timestamp: 1,
+                    secrets: vec![],
                 },
             }
         }
@@ -0,0 +1,39 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Contract testing of HTTP calls against an OpenAPI 3 document.
+//!
+//! Specification: <https://spec.openapis.org/oas/v3.1.0>
+//!
+//! A call is matched to an operation of the document by its method and its URL path: the host of
+//! the URL is ignored, and the path of the `servers` URLs is removed from the URL path before
+//! matching the path templates (concrete paths like `/users/me` are matched before templated paths
+//! like `/users/{id}`).
+//!
+//! The response of the call is then checked against the operation:
+//! - the status code must be declared (exactly, by a range like `2XX`, or by `default`),
+//! - the required headers must be present, and header values must be valid against their schema,
+//! - the content type must be declared when the response has a body,
+//! - JSON bodies must be valid against the schema of their media type.
+//!
+//! Schemas are validated with the JSON Schema validator of `matchesSchema` predicates. OpenAPI 3.0
+//! schemas are converted to JSON Schema first (`nullable` and boolean `exclusiveMinimum` and
+//! `exclusiveMaximum`). References (`$ref`) must target the document itself.
+pub use self::spec::{OpenApi, Operation};
+
+mod spec;
+mod validate;
@@ -0,0 +1,528 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::jsonschema::Schema;
+use crate::openapi::validate::schema_pointers;
+
+/// The HTTP methods of the operations of a path item.
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// An OpenAPI 3 document, used to check HTTP calls.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenApi {
+    /// The document, with schemas converted to JSON Schema
+    pub(super) doc: Value,
+    /// Paths of the `servers` URLs, removed from the URL paths before matching operations
+    base_paths: Vec<String>,
+    operations: Vec<Operation>,
+    /// The document as a JSON Schema, whose response schemas have been checked
+    pub(super) schema: Schema,
+}
+
+/// An operation of an OpenAPI document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Operation {
+    /// The HTTP method, in uppercase
+    pub method: String,
+    /// The path template, like `/users/{id}`
+    pub path: String,
+    /// The optional `operationId`
+    pub id: Option<String>,
+    /// JSON pointer to the operation object in the document
+    pub(super) pointer: String,
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.method, self.path)
+    }
+}
+
+impl OpenApi {
+    /// Parses an OpenAPI 3 document from its YAML or JSON `content`.
+    ///
+    /// The schemas of the operation responses are checked here, so that an invalid schema is
+    /// reported when the document is loaded.
+    pub fn parse(content: &str) -> Result<OpenApi, String> {
+        let mut doc = serde_norway::from_str::<Value>(content).map_err(|e| e.to_string())?;
+        let version = match doc.get("openapi").and_then(|v| v.as_str()) {
+            Some(version) => version.to_string(),
+            None => return Err("missing openapi version (only OpenAPI 3 is supported)".to_string()),
+        };
+        if !version.starts_with("3.") {
+            return Err(format!(
+                "unsupported openapi version {version} (only OpenAPI 3 is supported)"
+            ));
+        }
+        if version.starts_with("3.0") {
+            convert_schemas(&mut doc);
+        }
+        let base_paths = base_paths(&doc);
+        let operations = operations(&doc)?;
+        let references = schema_pointers(&doc, &operations)
+            .iter()
+            .map(|pointer| format!("#{pointer}"))
+            .collect::<Vec<_>>();
+        let schema = Schema::from_document(doc.clone(), &references)
+            .map_err(|e| format!("invalid schema {e}"))?;
+        Ok(OpenApi {
+            doc,
+            base_paths,
+            operations,
+            schema,
+        })
+    }
+
+    /// Returns the operations of this document, sorted by path.
+    pub fn operations(&self) -> &[Operation] {
+        &self.operations
+    }
+
+    /// Returns the operation matching an HTTP `method` and `url`, `None` if there's no matching
+    /// operation.
+    pub fn find_operation(&self, method: &str, url: &str) -> Option<&Operation> {
+        let path = match url::Url::parse(url) {
+            Ok(url) => url.path().to_string(),
+            Err(_) => return None,
+        };
+        let method = method.to_uppercase();
+        self.base_paths
+            .iter()
+            .filter_map(|base| strip_base_path(&path, base))
+            .flat_map(|path| {
+                self.operations
+                    .iter()
+                    .filter(|o| o.method == method)
+                    .filter_map(move |o| {
+                        let params = match_path(&o.path, path)?;
+                        Some((params, o))
+                    })
+            })
+            // Concrete paths are matched before templated paths.
+            .min_by_key(|(params, _)| *params)
+            .map(|(_, o)| o)
+    }
+
+    /// Returns the value targeted by a JSON `pointer` (without the leading `#`) and its pointer,
+    /// following the reference objects (`$ref`).
+    ///
+    /// `None` is returned if the pointer can't be resolved.
+    pub(super) fn locate(&self, pointer: &str) -> Option<(String, &Value)> {
+        locate(&self.doc, pointer)
+    }
+}
+
+/// Returns the value targeted by a JSON `pointer` in an OpenAPI document `doc` and its pointer,
+/// following the reference objects (see [`OpenApi::locate`]).
+pub(super) fn locate<'a>(doc: &'a Value, pointer: &str) -> Option<(String, &'a Value)> {
+    let mut pointer = pointer.to_string();
+    // Limits the number of references followed, to stop on circular references.
+    for _ in 0..32 {
+        let value = resolve_pointer(doc, &pointer)?;
+        match value.get("$ref").and_then(|r| r.as_str()) {
+            Some(reference) => pointer = reference.strip_prefix('#')?.to_string(),
+            None => return Some((pointer, value)),
+        }
+    }
+    None
+}
+
+/// Returns the operations of an OpenAPI document `doc`.
+fn operations(doc: &Value) -> Result<Vec<Operation>, String> {
+    let paths = match doc.get("paths") {
+        None => return Ok(vec![]),
+        Some(Value::Object(paths)) => paths,
+        Some(_) => return Err("paths must be an object".to_string()),
+    };
+    let mut operations = vec![];
+    for (path, item) in paths {
+        if !path.starts_with('/') {
+            return Err(format!("path {path} must start with /"));
+        }
+        for method in METHODS {
+            let operation = match item.get(method) {
+                Some(operation) => operation,
+                None => continue,
+            };
+            let id = operation
+                .get("operationId")
+                .and_then(|id| id.as_str())
+                .map(str::to_string);
+            let pointer = format!("/paths/{}/{method}", escape_pointer(path));
+            operations.push(Operation {
+                method: method.to_uppercase(),
+                path: path.clone(),
+                id,
+                pointer,
+            });
+        }
+    }
+    Ok(operations)
+}
+
+/// Returns the paths of the `servers` URLs of an OpenAPI document `doc`, without trailing `/`.
+///
+/// Server variables are replaced by their default value.
+fn base_paths(doc: &Value) -> Vec<String> {
+    let servers = match doc.get("servers").and_then(|s| s.as_array()) {
+        Some(servers) if !servers.is_empty() => servers,
+        _ => return vec![String::new()],
+    };
+    let mut paths = vec![];
+    for server in servers {
+        let mut url = match server.get("url").and_then(|u| u.as_str()) {
+            Some(url) => url.to_string(),
+            None => continue,
+        };
+        if let Some(Value::Object(variables)) = server.get("variables") {
+            for (name, variable) in variables {
+                if let Some(default) = variable.get("default").and_then(|d| d.as_str()) {
+                    url = url.replace(&format!("{{{name}}}"), default);
+                }
+            }
+        }
+        // Absolute URLs (`https://example.org/v1`) and relative URLs (`/v1`) are supported.
+        let path = match url.find("://") {
+            Some(index) => {
+                let rest = &url[index + 3..];
+                rest.find('/').map(|i| &rest[i..]).unwrap_or("")
+            }
+            None => url.as_str(),
+        };
+        let path = path.trim_end_matches('/').to_string();
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Returns a URL `path` without a `base` path, `None` if `path` doesn't start with `base`.
+fn strip_base_path<'a>(path: &'a str, base: &str) -> Option<&'a str> {
+    match path.strip_prefix(base)? {
+        "" => Some("/"),
+        path if path.starts_with('/') => Some(path),
+        _ => None,
+    }
+}
+
+/// Returns the number of parameters of a path `template` if it matches a URL `path`, `None` if
+/// the path doesn't match.
+fn match_path(template: &str, path: &str) -> Option<usize> {
+    let template_segments = template.split('/').collect::<Vec<_>>();
+    let path_segments = path.split('/').collect::<Vec<_>>();
+    if template_segments.len() != path_segments.len() {
+        return None;
+    }
+    let mut params = 0;
+    for (template, segment) in template_segments.iter().zip(path_segments) {
+        let tokens = segment_tokens(template);
+        if !match_tokens(&tokens, segment) {
+            return None;
+        }
+        params += tokens.iter().filter(|t| t.is_none()).count();
+    }
+    Some(params)
+}
+
+/// Returns the tokens of a path template segment: `Some(literal)`, or `None` for a parameter.
+///
+/// For instance, `{id}.json` is `[None, Some(".json")]`.
+fn segment_tokens(segment: &str) -> Vec<Option<&str>> {
+    let mut tokens = vec![];
+    let mut rest = segment;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        if start > 0 {
+            tokens.push(Some(&rest[..start]));
+        }
+        tokens.push(None);
+        rest = &rest[end + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Some(rest));
+    }
+    tokens
+}
+
+/// Returns true if the template `tokens` of a segment match a URL path `segment`.
+///
+/// A parameter matches at least one character.
+fn match_tokens(tokens: &[Option<&str>], segment: &str) -> bool {
+    match tokens.split_first() {
+        None => segment.is_empty(),
+        Some((Some(literal), rest)) => segment
+            .strip_prefix(literal)
+            .is_some_and(|segment| match_tokens(rest, segment)),
+        Some((None, rest)) => segment
+            .char_indices()
+            .skip(1)
+            .map(|(index, _)| index)
+            .chain([segment.len()])
+            .filter(|index| *index > 0)
+            .any(|index| match_tokens(rest, &segment[index..])),
+    }
+}
+
+/// Escapes a JSON pointer token.
+pub(super) fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+/// Returns the value targeted by a JSON `pointer` in `root`.
+fn resolve_pointer<'a>(root: &'a Value, pointer: &str) -> Option<&'a Value> {
+    if pointer.is_empty() {
+        return Some(root);
+    }
+    root.pointer(pointer)
+}
+
+/// Converts the OpenAPI 3.0 schemas of a document `doc` to JSON Schema.
+///
+/// - `nullable: true` adds `null` to the `type` of the schema,
+/// - boolean `exclusiveMinimum` and `exclusiveMaximum` are converted to numbers.
+fn convert_schemas(doc: &mut Value) {
+    match doc {
+        Value::Object(map) => {
+            if map.get("nullable") == Some(&Value::Bool(true)) {
+                if let Some(Value::String(t)) = map.get("type") {
+                    let types = vec![Value::String(t.clone()), Value::String("null".to_string())];
+                    map.insert("type".to_string(), Value::Array(types));
+                }
+                if let Some(Value::Array(values)) = map.get_mut("enum") {
+                    if !values.contains(&Value::Null) {
+                        values.push(Value::Null);
+                    }
+                }
+            }
+            for (exclusive, bound) in [
+                ("exclusiveMinimum", "minimum"),
+                ("exclusiveMaximum", "maximum"),
+            ] {
+                if let Some(Value::Bool(exclusive_bound)) = map.get(exclusive) {
+                    match (exclusive_bound, map.get(bound).cloned()) {
+                        (true, Some(value)) => {
+                            map.remove(bound);
+                            map.insert(exclusive.to_string(), value);
+                        }
+                        _ => {
+                            map.remove(exclusive);
+                        }
+                    }
+                }
+            }
+            map.values_mut().for_each(convert_schemas);
+        }
+        Value::Array(values) => values.iter_mut().for_each(convert_schemas),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Users
+  version: 1.0.0
+servers:
+  - url: https://{env}.example.org/api/{version}
+    variables:
+      env:
+        default: prod
+      version:
+        default: v1
+paths:
+  /users:
+    get:
+      operationId: getUsers
+      responses:
+        200:
+          description: Users
+    post:
+      responses:
+        201:
+          description: Created user
+  /users/{id}:
+    get:
+      operationId: getUser
+      responses:
+        '200':
+          $ref: '#/components/responses/User'
+  /users/me:
+    get:
+      responses:
+        '200':
+          description: Current user
+  /files/{name}.{ext}:
+    get:
+      responses:
+        '200':
+          description: File
+components:
+  responses:
+    User:
+      description: User
+"#;
+
+    #[test]
+    fn test_parse() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        assert_eq!(spec.base_paths, vec!["/api/v1".to_string()]);
+        assert_eq!(
+            spec.operations()
+                .iter()
+                .map(|o| o.to_string())
+                .collect::<Vec<_>>(),
+            vec![
+                "GET /files/{name}.{ext}",
+                "GET /users",
+                "POST /users",
+                "GET /users/me",
+                "GET /users/{id}",
+            ]
+        );
+        assert_eq!(
+            spec.locate("/paths/~1users/get/responses/200"),
+            Some((
+                "/paths/~1users/get/responses/200".to_string(),
+                &json!({"description": "Users"})
+            ))
+        );
+        assert_eq!(
+            spec.locate("/paths/~1users~1{id}/get/responses/200"),
+            Some((
+                "/components/responses/User".to_string(),
+                &json!({"description": "User"})
+            ))
+        );
+
+        assert_eq!(
+            OpenApi::parse("swagger: '2.0'").unwrap_err(),
+            "missing openapi version (only OpenAPI 3 is supported)"
+        );
+        assert_eq!(
+            OpenApi::parse("openapi: 2.0.0").unwrap_err(),
+            "unsupported openapi version 2.0.0 (only OpenAPI 3 is supported)"
+        );
+
+        let spec = r#"
+openapi: 3.1.0
+paths:
+  /users:
+    get:
+      responses:
+        '200':
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/Users'
+components:
+  schemas:
+    Users:
+      type: list
+"#;
+        assert_eq!(
+            OpenApi::parse(spec).unwrap_err(),
+            "invalid schema #/components/schemas/Users/type: invalid type \"list\""
+        );
+    }
+
+    #[test]
+    fn test_find_operation() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        let find = |method: &str, url: &str| {
+            spec.find_operation(method, url)
+                .map(|o| o.to_string())
+                .unwrap_or_default()
+        };
+        assert_eq!(
+            find("GET", "http://localhost:8000/api/v1/users"),
+            "GET /users"
+        );
+        assert_eq!(
+            find("GET", "http://localhost:8000/api/v1/users?page=2"),
+            "GET /users"
+        );
+        assert_eq!(find("post", "http://localhost/api/v1/users"), "POST /users");
+        assert_eq!(
+            find("GET", "http://localhost/api/v1/users/42"),
+            "GET /users/{id}"
+        );
+        assert_eq!(
+            find("GET", "http://localhost/api/v1/users/me"),
+            "GET /users/me"
+        );
+        assert_eq!(
+            find("GET", "http://localhost/api/v1/files/a.b.txt"),
+            "GET /files/{name}.{ext}"
+        );
+        assert_eq!(find("DELETE", "http://localhost/api/v1/users/42"), "");
+        assert_eq!(find("GET", "http://localhost/api/v1/users/42/friends"), "");
+        assert_eq!(find("GET", "http://localhost/users"), "");
+        assert_eq!(find("GET", "http://localhost/api/v1/files/a"), "");
+    }
+
+    #[test]
+    fn test_match_path() {
+        assert_eq!(match_path("/users", "/users"), Some(0));
+        assert_eq!(match_path("/users/{id}", "/users/1"), Some(1));
+        assert_eq!(match_path("/users/{id}", "/users/"), None);
+        assert_eq!(match_path("/users/{id}.json", "/users/1.json"), Some(1));
+        assert_eq!(match_path("/users/{id}.json", "/users/.json"), None);
+        assert_eq!(match_path("/a/{b}/c/{d}", "/a/1/c/2"), Some(2));
+    }
+
+    #[test]
+    fn test_convert_schemas() {
+        let mut doc = json!({
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "nullable": true},
+                "kind": {"type": "string", "enum": ["a", "b"], "nullable": true},
+                "age": {"type": "integer", "minimum": 0, "exclusiveMinimum": true},
+                "score": {"type": "number", "maximum": 10, "exclusiveMaximum": false},
+            }
+        });
+        convert_schemas(&mut doc);
+        assert_eq!(
+            doc,
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": ["string", "null"], "nullable": true},
+                    "kind": {"type": ["string", "null"], "enum": ["a", "b", null], "nullable": true},
+                    "age": {"type": "integer", "exclusiveMinimum": 0},
+                    "score": {"type": "number", "maximum": 10},
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,446 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use serde_json::{Map, Value};
+
+use crate::http::Call;
+use crate::openapi::spec::{escape_pointer, locate};
+use crate::openapi::{OpenApi, Operation};
+
+impl OpenApi {
+    /// Checks an HTTP `call` against the matching operation of this document, returning all the
+    /// violations.
+    pub fn validate(&self, call: &Call) -> Vec<String> {
+        let method = &call.request.method;
+        let operation = match self.find_operation(method, &call.request.url) {
+            Some(operation) => operation,
+            None => {
+                let path = match url::Url::parse(&call.request.url) {
+                    Ok(url) => url.path().to_string(),
+                    Err(_) => call.request.url.clone(),
+                };
+                return vec![format!("{method} {path} does not match any operation")];
+            }
+        };
+        let status = call.response.status;
+        let (pointer, response) = match self.find_response(operation, status) {
+            Some(response) => response,
+            None => {
+                return vec![format!(
+                    "status code {status} is not declared by operation {operation}"
+                )]
+            }
+        };
+
+        let mut violations = vec![];
+        violations.extend(self.validate_headers(call, &pointer, response));
+        violations.extend(self.validate_content(call, operation, &pointer, response));
+        violations
+    }
+
+    /// Returns the response object of an `operation` declaring a `status` code, and its pointer.
+    ///
+    /// Status codes are looked up exactly (`200`), then by range (`2XX`), then `default`.
+    fn find_response(&self, operation: &Operation, status: u32) -> Option<(String, &Value)> {
+        let pointer = format!("{}/responses", operation.pointer);
+        let (pointer, responses) = self.locate(&pointer)?;
+        let responses = responses.as_object()?;
+        let range = format!("{}XX", status / 100);
+        let key = responses
+            .keys()
+            .find(|key| key.as_str() == status.to_string())
+            .or_else(|| {
+                responses
+                    .keys()
+                    .find(|key| key.eq_ignore_ascii_case(&range))
+            })
+            .or_else(|| responses.keys().find(|key| key.as_str() == "default"))?;
+        self.locate(&format!("{pointer}/{key}"))
+    }
+
+    /// Checks the headers of a `call` against the headers of a `response` object.
+    fn validate_headers(&self, call: &Call, pointer: &str, response: &Value) -> Vec<String> {
+        let headers = match response.get("headers") {
+            Some(Value::Object(headers)) => headers,
+            _ => return vec![],
+        };
+        let mut violations = vec![];
+        for name in headers.keys() {
+            // Content-Type is described by the content of the response and is ignored here.
+            if name.eq_ignore_ascii_case("Content-Type") {
+                continue;
+            }
+            let header_pointer = format!("{pointer}/headers/{}", escape_pointer(name));
+            let (header_pointer, header) = match self.locate(&header_pointer) {
+                Some(header) => header,
+                None => continue,
+            };
+            let values = call.response.get_header_values(name);
+            if values.is_empty() {
+                if header.get("required") == Some(&Value::Bool(true)) {
+                    violations.push(format!("required header {name} is missing"));
+                }
+                continue;
+            }
+            if header.get("schema").is_none() {
+                continue;
+            }
+            let schema_pointer = format!("{header_pointer}/schema");
+            let types = self
+                .locate(&schema_pointer)
+                .map(|(_, schema)| schema_types(schema))
+                .unwrap_or_default();
+            for value in values {
+                let instance = header_instance(&value, &types);
+                let errors = self
+                    .schema
+                    .validate_ref(&format!("#{schema_pointer}"), &instance)
+                    .iter()
+                    .map(|e| e.message.clone())
+                    .collect::<Vec<_>>();
+                if !errors.is_empty() {
+                    let errors = errors.join(", ");
+                    violations.push(format!("header {name} <{value}> is not valid ({errors})"));
+                }
+            }
+        }
+        violations
+    }
+
+    /// Checks the content type and the body of a `call` against the content of a `response`
+    /// object.
+    fn validate_content(
+        &self,
+        call: &Call,
+        operation: &Operation,
+        pointer: &str,
+        response: &Value,
+    ) -> Vec<String> {
+        let content = match response.get("content") {
+            Some(Value::Object(content)) if !content.is_empty() => content,
+            _ => return vec![],
+        };
+        if call.response.body.is_empty() {
+            return vec![];
+        }
+        let declared = content.keys().cloned().collect::<Vec<_>>().join(", ");
+        let content_type = match call.response.content_type() {
+            Some(content_type) => media_type(&content_type),
+            None => {
+                return vec![format!(
+                    "missing Content-Type header (declared: {declared})"
+                )]
+            }
+        };
+        let key = match find_media_type(content, &content_type) {
+            Some(key) => key,
+            None => {
+                return vec![format!(
+                    "content type {content_type} is not declared by operation {operation} (declared: {declared})"
+                )]
+            }
+        };
+        let media_pointer = format!("{pointer}/content/{}", escape_pointer(key));
+        let has_schema = self
+            .locate(&media_pointer)
+            .is_some_and(|(_, media)| media.get("schema").is_some());
+        if !has_schema || !is_json(&content_type) {
+            return vec![];
+        }
+
+        let instance = call
+            .response
+            .text()
+            .map_err(|_| "body can not be decoded".to_string())
+            .and_then(|text| serde_json::from_str::<Value>(&text).map_err(|e| e.to_string()));
+        let instance = match instance {
+            Ok(instance) => instance,
+            Err(message) => return vec![format!("invalid JSON body ({message})")],
+        };
+        let errors = self
+            .schema
+            .validate_ref(&format!("#{media_pointer}/schema"), &instance);
+        if errors.is_empty() {
+            return vec![];
+        }
+        let errors = errors
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        vec![format!(
+            "body is not valid against the schema of operation {operation} ({}, {key}):\n{errors}",
+            call.response.status
+        )]
+    }
+}
+
+/// Returns the pointers of the schemas used to check the responses of `operations` (header and
+/// media type schemas), in an OpenAPI document `doc`.
+pub(super) fn schema_pointers(doc: &Value, operations: &[Operation]) -> Vec<String> {
+    let mut pointers = vec![];
+    for operation in operations {
+        let responses_pointer = format!("{}/responses", operation.pointer);
+        let (responses_pointer, responses) = match locate(doc, &responses_pointer) {
+            Some((pointer, Value::Object(responses))) => (pointer, responses),
+            _ => continue,
+        };
+        for key in responses.keys() {
+            let (pointer, response) = match locate(doc, &format!("{responses_pointer}/{key}")) {
+                Some(response) => response,
+                None => continue,
+            };
+            if let Some(Value::Object(headers)) = response.get("headers") {
+                for name in headers.keys() {
+                    let header_pointer = format!("{pointer}/headers/{}", escape_pointer(name));
+                    if let Some((header_pointer, header)) = locate(doc, &header_pointer) {
+                        if header.get("schema").is_some() {
+                            pointers.push(format!("{header_pointer}/schema"));
+                        }
+                    }
+                }
+            }
+            if let Some(Value::Object(content)) = response.get("content") {
+                for (media_type, media) in content {
+                    if media.get("schema").is_some() {
+                        let media_type = escape_pointer(media_type);
+                        pointers.push(format!("{pointer}/content/{media_type}/schema"));
+                    }
+                }
+            }
+        }
+    }
+    pointers
+}
+
+/// Returns the media type of a `content_type` header value, without its parameters and in
+/// lowercase.
+fn media_type(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_lowercase()
+}
+
+/// Returns the key of the `content` map matching a media type `expected`.
+///
+/// Media types are looked up exactly (`application/json`), then by range (`application/*`), then
+/// `*/*`.
+fn find_media_type<'a>(content: &'a Map<String, Value>, expected: &str) -> Option<&'a String> {
+    let range = match expected.split_once('/') {
+        Some((kind, _)) => format!("{kind}/*"),
+        None => String::new(),
+    };
+    [expected, &range, "*/*"]
+        .iter()
+        .find_map(|candidate| content.keys().find(|key| media_type(key) == *candidate))
+}
+
+/// Returns true if a `media_type` is JSON (`application/json` or `+json` suffix).
+fn is_json(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// Returns the types declared by the `type` keyword of a `schema`.
+fn schema_types(schema: &Value) -> Vec<String> {
+    match schema.get("type") {
+        Some(Value::String(t)) => vec![t.clone()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(|t| t.as_str().map(str::to_string))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the JSON instance of a header `value`, converted to the first matching `types` of its
+/// schema (header values are strings and simple types are serialized as is).
+fn header_instance(value: &str, types: &[String]) -> Value {
+    for t in types {
+        let instance = match t.as_str() {
+            "integer" => value.parse::<i64>().ok().map(Value::from),
+            "number" => value
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number),
+            "boolean" => value.parse::<bool>().ok().map(Value::Bool),
+            "string" => Some(Value::String(value.to_string())),
+            _ => None,
+        };
+        if let Some(instance) = instance {
+            return instance;
+        }
+    }
+    Value::String(value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Header, HttpVersion, Request, Response, Timings};
+
+    const SPEC: &str = r#"
+openapi: 3.0.3
+info:
+  title: Users
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+      responses:
+        '200':
+          description: User
+          headers:
+            X-Rate-Limit:
+              required: true
+              schema:
+                type: integer
+                maximum: 100
+          content:
+            application/json:
+              schema:
+                $ref: '#/components/schemas/User'
+        4XX:
+          description: Error
+          content:
+            text/*:
+              schema:
+                type: string
+components:
+  schemas:
+    User:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+        email:
+          type: string
+          nullable: true
+"#;
+
+    fn call(status: u32, headers: Vec<(&str, &str)>, body: &str) -> Call {
+        Call {
+            request: Request {
+                url: "http://localhost:8000/users/1".to_string(),
+                method: "GET".to_string(),
+                headers: vec![],
+                body: vec![],
+            },
+            response: Response {
+                version: HttpVersion::Http11,
+                status,
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| Header::new(name, value))
+                    .collect(),
+                body: body.as_bytes().to_vec(),
+                duration: Default::default(),
+                url: "http://localhost:8000/users/1".to_string(),
+                certificate: None,
+            },
+            timings: Timings::default(),
+        }
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        let headers = vec![
+            ("Content-Type", "application/json; charset=utf-8"),
+            ("X-Rate-Limit", "10"),
+        ];
+        let body = r#"{"id": 1, "name": "Bob", "email": null}"#;
+        assert!(spec.validate(&call(200, headers, body)).is_empty());
+
+        let headers = vec![("Content-Type", "text/plain")];
+        assert!(spec.validate(&call(404, headers, "Not found")).is_empty());
+    }
+
+    #[test]
+    fn test_validate_operation() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        let mut call = call(200, vec![], "");
+        call.request.method = "DELETE".to_string();
+        assert_eq!(
+            spec.validate(&call),
+            vec!["DELETE /users/1 does not match any operation"]
+        );
+        assert_eq!(
+            spec.validate(&super::tests::call(500, vec![], "")),
+            vec!["status code 500 is not declared by operation GET /users/{id}"]
+        );
+    }
+
+    #[test]
+    fn test_validate_headers() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        assert_eq!(
+            spec.validate(&call(200, vec![], "")),
+            vec!["required header X-Rate-Limit is missing"]
+        );
+        assert_eq!(
+            spec.validate(&call(200, vec![("X-Rate-Limit", "200")], "")),
+            vec!["header X-Rate-Limit <200> is not valid (200 is greater than 100)"]
+        );
+    }
+
+    #[test]
+    fn test_validate_content() {
+        let spec = OpenApi::parse(SPEC).unwrap();
+        let body = r#"{"id": "1"}"#;
+        assert_eq!(
+            spec.validate(&call(200, vec![("X-Rate-Limit", "1")], body)),
+            vec!["missing Content-Type header (declared: application/json)"]
+        );
+        let headers = vec![("X-Rate-Limit", "1"), ("Content-Type", "text/html")];
+        assert_eq!(
+            spec.validate(&call(200, headers, body)),
+            vec!["content type text/html is not declared by operation GET /users/{id} (declared: application/json)"]
+        );
+        let headers = vec![("X-Rate-Limit", "1"), ("Content-Type", "application/json")];
+        assert_eq!(
+            spec.validate(&call(200, headers.clone(), "{")),
+            vec!["invalid JSON body (EOF while parsing an object at line 1 column 1)"]
+        );
+        assert_eq!(
+            spec.validate(&call(200, headers, body)),
+            vec!["body is not valid against the schema of operation GET /users/{id} (200, application/json):\n\
+            $.id: value is not of type integer (type)\n\
+            $: required property name is missing (required)"]
+        );
+    }
+
+    #[test]
+    fn test_header_instance() {
+        let types = vec!["integer".to_string(), "string".to_string()];
+        assert_eq!(header_instance("12", &types), Value::from(12));
+        assert_eq!(header_instance("a", &types), Value::String("a".to_string()));
+        let types = vec!["boolean".to_string()];
+        assert_eq!(header_instance("true", &types), Value::Bool(true));
+        assert_eq!(
+            header_instance("yes", &types),
+            Value::String("yes".to_string())
+        );
+    }
+}
@@ -0,0 +1,157 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use crate::http::{Body, RequestSpec};
+use crate::runner::{DryRunEntry, DryRunResult};
+use crate::util::redact::redact;
+
+/// The format of the requests written by a dry run.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DryRunFormat {
+    /// Curl command lines, as a shell script
+    Curl,
+    /// Raw HTTP requests
+    Http,
+    /// JSON representation of the requests
+    Json,
+}
+
+/// Returns the requests of the `dry_run_result` of the file `filename_in`, serialized with
+/// `format`.
+///
+/// With the raw HTTP and curl formats, each request is preceded by a comment locating its entry
+/// and followed by an empty line. Secret values are redacted.
+pub fn format_dry_run(
+    dry_run_result: &DryRunResult,
+    filename_in: &str,
+    format: DryRunFormat,
+) -> String {
+    match format {
+        DryRunFormat::Curl => {
+            let commands = dry_run_result
+                .entries
+                .iter()
+                .map(|e| format!("{}\n{}\n\n", entry_comment(e), e.curl))
+                .collect::<String>();
+            redact(&commands, &dry_run_result.secrets)
+        }
+        DryRunFormat::Http => {
+            let requests = dry_run_result
+                .entries
+                .iter()
+                .map(|e| format!("{}\n{}\n", entry_comment(e), request_text(&e.request)))
+                .collect::<String>();
+            redact(&requests, &dry_run_result.secrets)
+        }
+        DryRunFormat::Json => {
+            let json = dry_run_result.to_json(filename_in);
+            let serialized = serde_json::to_string(&json).unwrap();
+            format!("{serialized}\n")
+        }
+    }
+}
+
+/// Returns a comment line locating the entry of a rendered request.
+fn entry_comment(entry: &DryRunEntry) -> String {
+    let index = entry.entry_index;
+    let line = entry.source_info.start.line;
+    let location = match &entry.file {
+        Some(file) => format!("{}:{line}", file.filename),
+        None => format!("line {line}"),
+    };
+    match entry.iteration {
+        Some(iteration) => format!("# Entry {index}, iteration {iteration} ({location})"),
+        None => format!("# Entry {index} ({location})"),
+    }
+}
+
+/// Returns the text of a `request`, with the sections of the Hurl format for its parameters.
+fn request_text(request: &RequestSpec) -> String {
+    let mut lines = vec![format!("{} {}", request.method, request.url)];
+    lines.extend(request.headers.iter().map(|h| h.to_string()));
+    if let Some(content_type) = &request.content_type {
+        if request.get_header_values("Content-Type").is_empty() {
+            lines.push(format!("Content-Type: {content_type}"));
+        }
+    }
+    if !request.querystring.is_empty() {
+        lines.push("[QueryStringParams]".to_string());
+        lines.extend(request.querystring.iter().map(|p| p.to_string()));
+    }
+    if !request.form.is_empty() {
+        lines.push("[FormParams]".to_string());
+        lines.extend(request.form.iter().map(|p| p.to_string()));
+    }
+    if !request.multipart.is_empty() {
+        lines.push("[MultipartFormData]".to_string());
+        lines.extend(request.multipart.iter().map(|p| p.to_string()));
+    }
+    if !request.cookies.is_empty() {
+        lines.push("[Cookies]".to_string());
+        lines.extend(
+            request
+                .cookies
+                .iter()
+                .map(|c| format!("{}: {}", c.name, c.value)),
+        );
+    }
+    let empty = matches!(&request.body, Body::Binary(bs) if bs.is_empty());
+    if !empty {
+        lines.push(request.body.to_string());
+    }
+    let mut text = lines.join("\n");
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Header, Method, Param};
+
+    #[test]
+    fn test_request_text() {
+        let request = RequestSpec {
+            method: Method("POST".to_string()),
+            url: "http://localhost:8000/users".to_string(),
+            headers: vec![Header::new("X-Id", "{{id}}")],
+            querystring: vec![Param {
+                name: "page".to_string(),
+                value: "1".to_string(),
+            }],
+            body: Body::Text(r#"{"name":"Bob"}"#.to_string()),
+            content_type: Some("application/json".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            request_text(&request),
+            r#"POST http://localhost:8000/users
+X-Id: {{id}}
+Content-Type: application/json
+[QueryStringParams]
+page: 1
+{"name":"Bob"}
+"#
+        );
+
+        let request = RequestSpec {
+            url: "http://localhost:8000/hello".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(request_text(&request), "GET http://localhost:8000/hello\n");
+    }
+}
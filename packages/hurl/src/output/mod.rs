@@ -21,10 +21,14 @@
 //! - JSON: the whole run is serialized to JSON (like the [HAR](https://en.wikipedia.org/wiki/HAR_(file_format)) format)
 //! - raw: the last response of a run is serialized to a file. The body can be automatically uncompress
 //! or written as it.
+//!
+//! The requests rendered by a dry run are also serialized, as raw HTTP, curl commands or JSON.
+mod dry_run;
 mod error;
 mod json;
 mod raw;
 
+pub use self::dry_run::{format_dry_run, DryRunFormat};
 pub use self::error::Error;
 pub use self::json::write_json;
 pub use self::raw::write_body;
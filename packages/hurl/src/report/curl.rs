@@ -0,0 +1,182 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! curl script report: the curl command lines of the requests executed by Hurl runs, as a
+//! shell script that can be run without Hurl.
+
+use std::fs::File;
+use std::io::Write;
+
+use crate::report::Error;
+use crate::runner::{EntryResult, HurlResult};
+use crate::util::redact::redact;
+
+/// Writes the curl script of these Hurl `results` to `filename`. Each result is given with the
+/// filename of its Hurl file.
+pub fn write_script(filename: &str, results: &[(&HurlResult, &str)]) -> Result<(), Error> {
+    let mut file = match File::create(filename) {
+        Ok(file) => file,
+        Err(e) => {
+            return Err(Error {
+                message: format!("Failed to produce curl script {filename}: {e:?}"),
+            });
+        }
+    };
+    let script = script(results);
+    if let Err(e) = file.write_all(script.as_bytes()) {
+        return Err(Error {
+            message: format!("Failed to write curl script {filename}: {e:?}"),
+        });
+    }
+    Ok(())
+}
+
+/// Returns the curl script of these Hurl `results`.
+///
+/// Entries are listed in their order of execution, every attempt of a retried entry having its
+/// own command. Entries whose request could not be built are listed without command.
+fn script(results: &[(&HurlResult, &str)]) -> String {
+    let mut text = "#!/bin/sh\n# This file was generated by Hurl\n".to_string();
+    for (hurl_result, filename) in results {
+        let mut prev: Option<&EntryResult> = None;
+        for entry in &hurl_result.entries {
+            text.push('\n');
+            text.push_str(&entry_comment(entry, prev, filename));
+            for call in entry.calls.iter().skip(1) {
+                let url = redact(&call.request.url, &hurl_result.secrets);
+                text.push_str(&format!("# Redirected to {url}\n"));
+            }
+            if !entry.curl_cmd.is_empty() {
+                text.push_str(&redact(&entry.curl_cmd, &hurl_result.secrets));
+                text.push('\n');
+            }
+            prev = Some(entry);
+        }
+    }
+    text
+}
+
+/// Returns the comment line describing an `entry`, `prev` being the entry executed just before.
+fn entry_comment(entry: &EntryResult, prev: Option<&EntryResult>, filename: &str) -> String {
+    let filename = match &entry.file {
+        Some(file) => file.filename.as_str(),
+        None => filename,
+    };
+    let line = entry.source_info.start.line;
+    let mut comment = format!("# {filename}:{line}, entry {}", entry.entry_index);
+    if let Some(iteration) = entry.iteration {
+        comment.push_str(&format!(", iteration {iteration}"));
+    }
+    if let Some(retry) = prev.and_then(|p| p.retry.as_ref()) {
+        comment.push_str(&format!(", retry {}", retry.attempt));
+    }
+    comment.push('\n');
+    comment
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use hurl_core::ast::{Pos, SourceInfo};
+
+    use super::*;
+    use crate::http::{Call, Request, Response, Timings};
+    use crate::runner::{RetryAttempt, RetryReason};
+
+    fn new_entry(
+        entry_index: usize,
+        line: usize,
+        urls: &[&str],
+        retry: Option<RetryAttempt>,
+    ) -> EntryResult {
+        let calls = urls
+            .iter()
+            .map(|url| Call {
+                request: Request {
+                    url: url.to_string(),
+                    method: "GET".to_string(),
+                    headers: vec![],
+                    body: vec![],
+                },
+                response: Response::default(),
+                timings: Timings::default(),
+            })
+            .collect::<Vec<_>>();
+        EntryResult {
+            entry_index,
+            iteration: None,
+            name: None,
+            tags: vec![],
+            file: None,
+            source_info: SourceInfo::new(Pos::new(line, 1), Pos::new(line, 1)),
+            curl_cmd: format!("curl --location '{}'", urls[0]),
+            calls,
+            captures: vec![],
+            asserts: vec![],
+            errors: vec![],
+            retry,
+            time_in_ms: 0,
+            compressed: false,
+        }
+    }
+
+    #[test]
+    fn test_script() {
+        let retry = RetryAttempt {
+            attempt: 1,
+            reason: RetryReason::Assert,
+            delay: Duration::from_millis(1000),
+        };
+        let hurl_result = HurlResult {
+            entries: vec![
+                new_entry(
+                    1,
+                    1,
+                    &[
+                        "http://localhost:8000/redirect",
+                        "http://localhost:8000/?token=abc",
+                    ],
+                    None,
+                ),
+                new_entry(2, 4, &["http://localhost:8000/token?abc"], Some(retry)),
+                new_entry(2, 4, &["http://localhost:8000/token?abc"], None),
+            ],
+            time_in_ms: 0,
+            success: true,
+            cookies: vec![],
+            timestamp: 0,
+            secrets: vec!["abc".to_string()],
+        };
+        assert_eq!(
+            script(&[(&hurl_result, "tests/test.hurl")]),
+            r#"#!/bin/sh
+# This file was generated by Hurl
+
+# tests/test.hurl:1, entry 1
+# Redirected to http://localhost:8000/?token=***
+curl --location 'http://localhost:8000/redirect'
+
+# tests/test.hurl:4, entry 2
+curl --location 'http://localhost:8000/token?***'
+
+# tests/test.hurl:4, entry 2, retry 1
+curl --location 'http://localhost:8000/token?***'
+"#
+        );
+    }
+}
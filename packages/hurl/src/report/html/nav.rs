@@ -56,17 +56,24 @@ impl Testcase {
     fn get_errors_html(&self, content: &str) -> String {
         self.errors
             .iter()
-            .map(|e| {
+            .map(|(e, file)| {
                 let line = e.source_info.start.line;
                 let column = e.source_info.start.column;
-                let filename = &self.filename;
-                let message = logger::error_string(filename, content, e, false);
-                // We override the first part of the error string to add an anchor to
-                // the error context.
-                let old = format!("{filename}:{line}:{column}");
-                let href = self.source_filename();
-                let new = format!("<a href=\"{href}#l{line}\">{filename}:{line}:{column}</a>");
-                let message = message.replace(&old, &new);
+                let message = match file {
+                    // Errors of included files have no source view to link to.
+                    Some(file) => logger::error_string(&file.filename, &file.content, e, false),
+                    None => {
+                        let filename = &self.filename;
+                        let message = logger::error_string(filename, content, e, false);
+                        // We override the first part of the error string to add an anchor to
+                        // the error context.
+                        let old = format!("{filename}:{line}:{column}");
+                        let href = self.source_filename();
+                        let new =
+                            format!("<a href=\"{href}#l{line}\">{filename}:{line}:{column}</a>");
+                        message.replace(&old, &new)
+                    }
+                };
                 format!(
                     "<div class=\"error\">\
                      <div class=\"error-desc\"><pre><code>{message}</code></pre></div>\
@@ -15,8 +15,6 @@
  * limitations under the License.
  *
  */
-use hurl_core::ast::HurlFile;
-
 use crate::http::Call;
 use crate::report::html::nav::Tab;
 use crate::report::html::Testcase;
@@ -24,22 +22,19 @@ use crate::runner::EntryResult;
 
 impl Testcase {
     /// Creates an HTML view of a run (HTTP status code, response header etc...)
-    pub fn get_run_html(
-        &self,
-        hurl_file: &HurlFile,
-        content: &str,
-        entries: &[EntryResult],
-    ) -> String {
+    pub fn get_run_html(&self, content: &str, entries: &[EntryResult]) -> String {
         let nav = self.get_nav_html(content, Tab::Run);
         let nav_css = include_str!("resources/nav.css");
         let run_css = include_str!("resources/run.css");
 
         let mut run = String::new();
         for (entry_index, e) in entries.iter().enumerate() {
-            let entry_src_index = e.entry_index - 1;
-            let entry_src = hurl_file.entries.get(entry_src_index).unwrap();
-            let line = entry_src.source_info().start.line;
-            let source = self.source_filename();
+            let line = e.source_info.start.line;
+            // Entries of included files have no source view, we only display their filename.
+            let (filename, source) = match &e.file {
+                Some(file) => (file.filename.as_str(), None),
+                None => (self.filename.as_str(), Some(self.source_filename())),
+            };
 
             run.push_str("<details open>");
             let info = get_entry_html(e, entry_index + 1);
@@ -50,8 +45,8 @@ impl Testcase {
                     c,
                     entry_index + 1,
                     call_index + 1,
-                    &self.filename,
-                    &source,
+                    filename,
+                    source.as_deref(),
                     line,
                 );
                 run.push_str(&info);
@@ -71,10 +66,24 @@ impl Testcase {
     }
 }
 
-/// Returns an HTML view of an `entry` information as HTML (title, `entry_index` and captures).
+/// Returns an HTML view of an `entry` information as HTML (title, `entry_index`, name, tags and
+/// captures).
 fn get_entry_html(entry: &EntryResult, entry_index: usize) -> String {
     let mut text = String::new();
-    text.push_str(&format!("<summary>Entry {entry_index}</summary>"));
+    let title = match entry.iteration {
+        Some(iteration) => format!("Entry {entry_index} (iteration {iteration})"),
+        None => format!("Entry {entry_index}"),
+    };
+    match &entry.name {
+        Some(name) => text.push_str(&format!("<summary>{title}: {name}</summary>")),
+        None => text.push_str(&format!("<summary>{title}</summary>")),
+    }
+
+    if !entry.tags.is_empty() {
+        let tags = entry.tags.join(", ");
+        let table = new_table("Tags", &[("Tags", tags)]);
+        text.push_str(&table);
+    }
 
     if !entry.captures.is_empty() {
         let mut values = entry
@@ -96,7 +105,7 @@ fn get_call_html(
     entry_index: usize,
     call_index: usize,
     filename: &str,
-    source: &str,
+    source: Option<&str>,
     line: usize,
 ) -> String {
     let mut text = String::new();
@@ -108,7 +117,10 @@ fn get_call_html(
     let version = call.response.version.to_string();
     let url = &call.request.url;
     let url = format!("<a href=\"{url}\">{url}</a>");
-    let source = format!("<a href=\"{source}#l{line}\">{filename}:{line}</a>");
+    let source = match source {
+        Some(source) => format!("<a href=\"{source}#l{line}\">{filename}:{line}</a>"),
+        None => format!("{filename}:{line}"),
+    };
     let values = vec![
         ("Request URL", url.as_str()),
         ("Request Method", call.request.method.as_str()),
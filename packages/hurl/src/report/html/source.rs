@@ -28,7 +28,14 @@ impl Testcase {
         let nav = self.get_nav_html(content, Tab::Source);
         let nav_css = include_str!("resources/nav.css");
         let source_div = hurl_core::format::format_html(hurl_file, false);
-        let source_div = underline_errors(&source_div, &self.errors);
+        // Only errors of the main file can be underlined.
+        let errors = self
+            .errors
+            .iter()
+            .filter(|(_, file)| file.is_none())
+            .map(|(error, _)| error.clone())
+            .collect::<Vec<_>>();
+        let source_div = underline_errors(&source_div, &errors);
         let lines_div = get_numbered_lines(content);
         let source_css = include_str!("resources/source.css");
         let hurl_css = hurl_core::format::hurl_css();
@@ -18,11 +18,14 @@
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::sync::Arc;
 
 use hurl_core::parser;
 use uuid::Uuid;
 
-use crate::runner::{EntryResult, Error, HurlResult};
+use crate::html::html_escape;
+use crate::runner::{EntryResult, Error, HurlResult, IncludedFile};
+use crate::util::redact::redact;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Testcase {
@@ -30,15 +33,22 @@ pub struct Testcase {
     pub filename: String,
     pub success: bool,
     pub time_in_ms: u128,
-    pub errors: Vec<Error>,
+    /// Errors of the run, with the file they belong to (`None` for the main file).
+    pub errors: Vec<(Error, Option<Arc<IncludedFile>>)>,
     pub timestamp: i64,
+    /// Secret values of the run, redacted from the source, timeline and run views.
+    pub secrets: Vec<String>,
 }
 
 impl Testcase {
     /// Creates an HTML testcase.
     pub fn from(hurl_result: &HurlResult, filename: &str) -> Testcase {
         let id = Uuid::new_v4();
-        let errors = hurl_result.errors().into_iter().cloned().collect();
+        let errors = hurl_result
+            .entry_errors()
+            .into_iter()
+            .map(|(entry, error)| (error.clone(), entry.file.clone()))
+            .collect();
         Testcase {
             id: id.to_string(),
             filename: filename.to_string(),
@@ -46,6 +56,7 @@ impl Testcase {
             success: hurl_result.success,
             errors,
             timestamp: hurl_result.timestamp,
+            secrets: hurl_result.secrets.clone(),
         }
     }
 
@@ -61,32 +72,41 @@ impl Testcase {
         entries: &[EntryResult],
         dir_path: &Path,
     ) -> Result<(), crate::report::Error> {
-        // We parse the content as we'll reuse the AST to construct the HTML source file, and
-        // the waterfall.
+        // We parse the content as we'll reuse the AST to construct the HTML source file.
         // TODO: for the moment, we can only have parseable file.
         let hurl_file = parser::parse_hurl_file(content).unwrap();
 
         // We create the timeline view.
         let output_file = dir_path.join("store").join(self.timeline_filename());
         let mut file = File::create(output_file)?;
-        let html = self.get_timeline_html(&hurl_file, content, entries);
+        let html = self.get_timeline_html(content, entries);
+        let html = self.redact(&html);
         file.write_all(html.as_bytes())?;
 
         // Then create the run view.
         let output_file = dir_path.join("store").join(self.run_filename());
         let mut file = File::create(output_file)?;
-        let html = self.get_run_html(&hurl_file, content, entries);
+        let html = self.get_run_html(content, entries);
+        let html = self.redact(&html);
         file.write_all(html.as_bytes())?;
 
         // And create the source view.
         let output_file = dir_path.join("store").join(self.source_filename());
         let mut file = File::create(output_file)?;
         let html = self.get_source_html(&hurl_file, content);
+        let html = self.redact(&html);
         file.write_all(html.as_bytes())?;
 
         Ok(())
     }
 
+    /// Returns `html` with the secrets of this testcase redacted, escaped or not.
+    fn redact(&self, html: &str) -> String {
+        let mut secrets = self.secrets.clone();
+        secrets.extend(self.secrets.iter().map(|s| html_escape(s)));
+        redact(html, &secrets)
+    }
+
     pub fn source_filename(&self) -> String {
         format!("{}-source.html", self.id)
     }
@@ -99,3 +119,52 @@ impl Testcase {
         format!("{}-run.html", self.id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use hurl_core::ast::{Pos, SourceInfo};
+
+    use super::*;
+    use crate::runner::RunnerError;
+
+    #[test]
+    fn test_write_html_redacts_secrets() {
+        let content = "GET http://localhost:8000/hello\nHTTP 200\n";
+        let error = Error::new(
+            SourceInfo::new(Pos::new(2, 1), Pos::new(2, 9)),
+            RunnerError::AssertFailure {
+                actual: "string <S3cr3tVal>".to_string(),
+                expected: "string <nope>".to_string(),
+                type_mismatch: false,
+            },
+            true,
+        );
+        let testcase = Testcase {
+            id: Uuid::new_v4().to_string(),
+            filename: "secret.hurl".to_string(),
+            success: false,
+            time_in_ms: 0,
+            errors: vec![(error, None)],
+            timestamp: 0,
+            secrets: vec!["S3cr3tVal".to_string()],
+        };
+        let dir = std::env::temp_dir().join(format!("html_report_{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("store")).unwrap();
+
+        testcase.write_html(content, &[], &dir).unwrap();
+
+        for filename in [
+            testcase.source_filename(),
+            testcase.timeline_filename(),
+            testcase.run_filename(),
+        ] {
+            let html = fs::read_to_string(dir.join("store").join(filename)).unwrap();
+            assert!(!html.contains("S3cr3tVal"));
+        }
+        let html = fs::read_to_string(dir.join("store").join(testcase.source_filename())).unwrap();
+        assert!(html.contains("actual:   string <***>"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
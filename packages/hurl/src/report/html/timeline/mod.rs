@@ -15,13 +15,11 @@
  * limitations under the License.
  *
  */
-use hurl_core::ast::HurlFile;
-
 use crate::http::Call;
 use crate::report::html::nav::Tab;
 use crate::report::html::timeline::unit::Pixel;
 use crate::report::html::Testcase;
-use crate::runner::EntryResult;
+use crate::runner::{EntryResult, RetryAttempt};
 
 mod calls;
 mod nice;
@@ -50,24 +48,20 @@ pub struct CallContext {
     pub call_index: usize,     // Index of the runtime Call in the whole run
     pub source_filename: String,
     pub run_filename: String,
+    pub retry: Option<RetryAttempt>, // Retry of the parent entry, if it has been retried
 }
 
 impl Testcase {
     /// Returns the HTML timeline of these `entries`.
-    /// The AST `hurl_file` is used to construct URL with line numbers to the corresponding
-    /// entry in the colored HTML source file.
-    pub fn get_timeline_html(
-        &self,
-        hurl_file: &HurlFile,
-        content: &str,
-        entries: &[EntryResult],
-    ) -> String {
+    /// The source info of `entries` is used to construct URL with line numbers to the
+    /// corresponding entry in the colored HTML source file.
+    pub fn get_timeline_html(&self, content: &str, entries: &[EntryResult]) -> String {
         let calls = entries
             .iter()
             .flat_map(|e| &e.calls)
             .collect::<Vec<&Call>>();
 
-        let call_ctxs = self.get_call_contexts(hurl_file, entries);
+        let call_ctxs = self.get_call_contexts(entries);
         let timeline_css = include_str!("../resources/timeline.css");
         let nav = self.get_nav_html(content, Tab::Timeline);
         let nav_css = include_str!("../resources/nav.css");
@@ -85,24 +79,17 @@ impl Testcase {
     }
 
     /// Constructs a list of call contexts to record source line code, runtime entry and call indices.
-    fn get_call_contexts(&self, hurl_file: &HurlFile, entries: &[EntryResult]) -> Vec<CallContext> {
+    fn get_call_contexts(&self, entries: &[EntryResult]) -> Vec<CallContext> {
         let mut calls_ctx = vec![];
 
         for (entry_index, e) in entries.iter().enumerate() {
-            let next_e = entries.get(entry_index + 1);
-            let retry = match next_e {
-                None => false, // last entry of the whole run can't be retried
-                Some(next_e) => e.entry_index == next_e.entry_index,
-            };
-            let kind = match (e.errors.is_empty(), retry) {
+            let kind = match (e.errors.is_empty(), e.retry.is_some()) {
                 (true, _) => CallContextKind::Success,
                 (false, true) => CallContextKind::Retry,
                 (false, false) => CallContextKind::Failure,
             };
             for (call_entry_index, _) in e.calls.iter().enumerate() {
-                let entry_src_index = e.entry_index - 1;
-                let entry_src = hurl_file.entries.get(entry_src_index).unwrap();
-                let line = entry_src.source_info().start.line;
+                let line = e.source_info.start.line;
                 let ctx = CallContext {
                     kind,
                     line,
@@ -111,6 +98,7 @@ impl Testcase {
                     call_index: calls_ctx.len() + 1,
                     source_filename: self.source_filename(),
                     run_filename: self.run_filename(),
+                    retry: e.retry.clone(),
                 };
                 calls_ctx.push(ctx);
             }
@@ -439,9 +439,24 @@ fn new_call_tooltip(
     let value = value.to_human_string();
     let elt = new_value("Transferred:", &value, x, y);
     group.add_child(elt);
+    let links_y = y + 56.px();
+
+    // Retry of the entry, displayed without moving the links below
+    if let Some(retry) = &call_ctx.retry {
+        y += delta_y;
+        let delay = Microsecond(retry.delay.as_micros() as f64);
+        let value = format!(
+            "#{} {} ({})",
+            retry.attempt,
+            retry.reason,
+            delay.to_human_string()
+        );
+        let elt = new_value("Retry:", &value, x, y);
+        group.add_child(elt);
+    }
 
     // Run URL
-    y += 56.px();
+    y = links_y;
     let href = format!(
         "{}#e{}:c{}",
         call_ctx.run_filename, call_ctx.entry_index, call_ctx.call_entry_index
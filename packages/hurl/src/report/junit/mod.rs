@@ -60,7 +60,7 @@ use std::fs::File;
 
 pub use testcase::Testcase;
 
-use crate::report::junit::xml::{Element, XmlDocument};
+use crate::report::junit::xml::{Element, XmlDocument, XmlNode};
 use crate::report::Error;
 
 /// Creates a JUnit from a list of `testcases`.
@@ -68,23 +68,97 @@ pub fn write_report(filename: &str, testcases: &[Testcase]) -> Result<(), Error>
     // If there is an existing JUnit report, we parses it to insert a new testsuite.
     let path = std::path::Path::new(&filename);
     let mut root = if path.exists() {
-        let file = match File::open(path) {
-            Ok(s) => s,
-            Err(why) => {
-                return Err(Error {
-                    message: format!("Issue reading {} to string to {:?}", path.display(), why),
-                });
-            }
-        };
-        let doc = XmlDocument::parse(file).unwrap();
-        doc.root.unwrap()
+        read_report(filename)?
     } else {
         Element::new("testsuites")
     };
 
     let testsuite = create_testsuite(testcases);
     root = root.add_child(testsuite);
+    write_document(filename, root)
+}
 
+/// Merges the JUnit reports `inputs` into a new JUnit report `filename`.
+///
+/// Every testsuite of the input reports is copied to the merged report, in the `inputs` order.
+pub fn merge_reports(filename: &str, inputs: &[String]) -> Result<(), Error> {
+    let mut root = Element::new("testsuites");
+    for input in inputs {
+        for testsuite in testsuites(read_report(input)?) {
+            root = root.add_child(testsuite);
+        }
+    }
+    write_document(filename, root)
+}
+
+/// Returns the time in milliseconds of each testcase of the JUnit report `filename`, by testcase id.
+///
+/// If a testcase is present in many testsuites, the last one is returned.
+pub fn read_times(filename: &str) -> Result<Vec<(String, u64)>, Error> {
+    let mut times = vec![];
+    for testsuite in testsuites(read_report(filename)?) {
+        for node in testsuite.children {
+            let testcase = match node {
+                XmlNode::Element(e) if e.name == "testcase" => e,
+                _ => continue,
+            };
+            let id = testcase.attrs.iter().find(|a| a.name == "id");
+            let time = testcase.attrs.iter().find(|a| a.name == "time");
+            if let (Some(id), Some(time)) = (id, time) {
+                if let Ok(time) = time.value.parse::<f64>() {
+                    times.push((id.value.clone(), (time * 1000.0).round() as u64));
+                }
+            }
+        }
+    }
+    Ok(times)
+}
+
+/// Returns the testsuites of a JUnit report `root` element.
+///
+/// The root element is either a `<testsuites>` list or a single `<testsuite>`.
+fn testsuites(root: Element) -> Vec<Element> {
+    if root.name == "testsuite" {
+        return vec![root];
+    }
+    root.children
+        .into_iter()
+        .filter_map(|node| match node {
+            XmlNode::Element(e) if e.name == "testsuite" => Some(e),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Reads the JUnit report `filename` and returns its root element.
+fn read_report(filename: &str) -> Result<Element, Error> {
+    let path = std::path::Path::new(&filename);
+    let file = match File::open(path) {
+        Ok(s) => s,
+        Err(why) => {
+            return Err(Error {
+                message: format!("Issue reading {} to string to {:?}", path.display(), why),
+            });
+        }
+    };
+    let doc = match XmlDocument::parse(file) {
+        Ok(doc) => doc,
+        Err(e) => {
+            return Err(Error {
+                message: format!("Issue parsing JUnit report {}: {e:?}", path.display()),
+            });
+        }
+    };
+    match doc.root {
+        Some(root) => Ok(root),
+        None => Err(Error {
+            message: format!("Empty JUnit report {}", path.display()),
+        }),
+    }
+}
+
+/// Writes the XML document of `root` element to the file `filename`.
+fn write_document(filename: &str, root: Element) -> Result<(), Error> {
     let doc = XmlDocument::new(root);
     let file = match File::create(filename) {
         Ok(f) => f,
@@ -131,7 +205,7 @@ mod tests {
     use hurl_core::ast::{Pos, SourceInfo};
 
     use crate::report::junit::xml::XmlDocument;
-    use crate::report::junit::{create_testsuite, Testcase};
+    use crate::report::junit::{create_testsuite, testsuites, Testcase};
     use crate::runner::{EntryResult, Error, HurlResult, RunnerError};
 
     #[test]
@@ -146,6 +220,7 @@ mod tests {
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
         let tc = Testcase::from(&res, content, filename);
         testcases.push(tc);
@@ -153,6 +228,10 @@ mod tests {
         let res = HurlResult {
             entries: vec![EntryResult {
                 entry_index: 1,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 35)),
                 calls: vec![],
                 captures: vec![],
@@ -164,6 +243,8 @@ mod tests {
                     },
                     true,
                 )],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: false,
             }],
@@ -171,6 +252,7 @@ mod tests {
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
         let tc = Testcase::from(&res, content, filename);
         testcases.push(tc);
@@ -178,6 +260,10 @@ mod tests {
         let res = HurlResult {
             entries: vec![EntryResult {
                 entry_index: 1,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 35)),
                 calls: vec![],
                 captures: vec![],
@@ -187,6 +273,8 @@ mod tests {
                     RunnerError::HttpConnection("(6) Could not resolve host: unknown".to_string()),
                     false,
                 )],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: false,
             }],
@@ -194,6 +282,7 @@ mod tests {
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
         let tc = Testcase::from(&res, content, filename);
         testcases.push(tc);
@@ -221,4 +310,30 @@ mod tests {
             </testsuite>"
         );
     }
+
+    #[test]
+    fn get_testsuites() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuites>
+    <testsuite tests="1" errors="0" failures="0">
+        <testcase id="a.hurl" name="a.hurl" time="0.120" />
+    </testsuite>
+    <testsuite tests="1" errors="0" failures="0">
+        <testcase id="b.hurl" name="b.hurl" time="0.010" />
+    </testsuite>
+</testsuites>
+"#;
+        let root = XmlDocument::parse_str(xml).unwrap().root.unwrap();
+        let suites = testsuites(root);
+        assert_eq!(suites.len(), 2);
+        assert!(suites.iter().all(|s| s.name == "testsuite"));
+
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<testsuite tests="1" errors="0" failures="0">
+    <testcase id="a.hurl" name="a.hurl" time="0.120" />
+</testsuite>
+"#;
+        let root = XmlDocument::parse_str(xml).unwrap().root.unwrap();
+        assert_eq!(testsuites(root).len(), 1);
+    }
 }
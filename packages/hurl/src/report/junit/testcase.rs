@@ -18,12 +18,15 @@
 use crate::report::junit::xml::Element;
 use crate::runner::HurlResult;
 use crate::util::logger;
+use crate::util::redact::redact;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Testcase {
     id: String,
     name: String,
     time_in_ms: u128,
+    /// Names and tags of the run entries, as (name, value) pairs
+    properties: Vec<(String, String)>,
     failures: Vec<String>,
     errors: Vec<String>,
 }
@@ -37,8 +40,33 @@ impl Testcase {
         let mut failures = vec![];
         let mut errors = vec![];
 
-        for error in hurl_result.errors() {
+        // An entry can be retried: we keep names and tags of each entry only once.
+        let mut properties = vec![];
+        let mut last_index = None;
+        for entry in hurl_result.entries.iter() {
+            if last_index == Some(entry.entry_index) {
+                continue;
+            }
+            last_index = Some(entry.entry_index);
+            if let Some(name) = &entry.name {
+                properties.push(("entry".to_string(), name.clone()));
+            }
+            for tag in entry.tags.iter() {
+                let property = ("tag".to_string(), tag.clone());
+                if !properties.contains(&property) {
+                    properties.push(property);
+                }
+            }
+        }
+
+        for (entry, error) in hurl_result.entry_errors() {
+            // Errors of included entries are located in their included file.
+            let (filename, content) = match &entry.file {
+                Some(file) => (file.filename.as_str(), file.content.as_str()),
+                None => (filename, content),
+            };
             let message = logger::error_string(filename, content, error, false);
+            let message = redact(&message, &hurl_result.secrets);
             if error.assert {
                 failures.push(message);
             } else {
@@ -49,6 +77,7 @@ impl Testcase {
             id,
             name,
             time_in_ms,
+            properties,
             failures,
             errors,
         }
@@ -63,6 +92,17 @@ impl Testcase {
             .attr("name", &self.name)
             .attr("time", &time_in_seconds);
 
+        if !self.properties.is_empty() {
+            let mut properties = Element::new("properties");
+            for (name, value) in self.properties.iter() {
+                let property = Element::new("property")
+                    .attr("name", name)
+                    .attr("value", value);
+                properties = properties.add_child(property);
+            }
+            element = element.add_child(properties);
+        }
+
         for failure in self.failures.iter() {
             element = element.add_child(Element::new("failure").text(failure))
         }
@@ -98,6 +138,7 @@ mod test {
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
 
         let content = "";
@@ -119,6 +160,10 @@ HTTP/1.0 200
         let hurl_result = HurlResult {
             entries: vec![EntryResult {
                 entry_index: 1,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 35)),
                 calls: vec![],
                 captures: vec![],
@@ -130,6 +175,8 @@ HTTP/1.0 200
                     },
                     true,
                 )],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: false,
             }],
@@ -137,6 +184,7 @@ HTTP/1.0 200
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
 
         let element = Testcase::from(&hurl_result, content, filename).to_xml();
@@ -159,6 +207,10 @@ HTTP/1.0 200
         let hurl_result = HurlResult {
             entries: vec![EntryResult {
                 entry_index: 1,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 18)),
                 calls: vec![],
                 captures: vec![],
@@ -168,6 +220,8 @@ HTTP/1.0 200
                     RunnerError::HttpConnection("(6) Could not resolve host: unknown".to_string()),
                     false,
                 )],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: false,
             }],
@@ -175,6 +229,7 @@ HTTP/1.0 200
             success: true,
             cookies: vec![],
             timestamp: 1,
+            secrets: vec![],
         };
         let element = Testcase::from(&hurl_result, content, filename).to_xml();
         let doc = XmlDocument::new(element);
@@ -188,4 +243,96 @@ HTTP/1.0 200
    |</error></testcase>"#
         );
     }
+
+    #[test]
+    fn test_create_testcase_with_secrets() {
+        let content = "GET http://unknown?token=12345";
+        let filename = "test.hurl";
+        let hurl_result = HurlResult {
+            entries: vec![EntryResult {
+                entry_index: 1,
+                iteration: None,
+                name: None,
+                tags: vec![],
+                file: None,
+                source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 31)),
+                calls: vec![],
+                captures: vec![],
+                asserts: vec![],
+                errors: vec![Error::new(
+                    SourceInfo::new(Pos::new(1, 5), Pos::new(1, 31)),
+                    RunnerError::HttpConnection(
+                        "(6) Could not resolve host: unknown?token=12345".to_string(),
+                    ),
+                    false,
+                )],
+                curl_cmd: String::new(),
+                retry: None,
+                time_in_ms: 0,
+                compressed: false,
+            }],
+            time_in_ms: 230,
+            success: true,
+            cookies: vec![],
+            timestamp: 1,
+            secrets: vec!["12345".to_string()],
+        };
+        let element = Testcase::from(&hurl_result, content, filename).to_xml();
+        let doc = XmlDocument::new(element);
+        assert_eq!(
+            doc.to_string().unwrap(),
+            r#"<?xml version="1.0" encoding="utf-8"?><testcase id="test.hurl" name="test.hurl" time="0.230"><error>HTTP connection
+  --> test.hurl:1:5
+   |
+ 1 | GET http://unknown?token=***
+   |     ^^^^^^^^^^^^^^^^^^^^^^^^^^ (6) Could not resolve host: unknown?token=***
+   |</error></testcase>"#
+        );
+    }
+
+    #[test]
+    fn test_create_testcase_with_names_and_tags() {
+        let entry = |entry_index, name: Option<&str>, tags: &[&str]| EntryResult {
+            entry_index,
+            iteration: None,
+            name: name.map(|n| n.to_string()),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            file: None,
+            source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
+            calls: vec![],
+            captures: vec![],
+            asserts: vec![],
+            errors: vec![],
+            curl_cmd: String::new(),
+            retry: None,
+            time_in_ms: 0,
+            compressed: false,
+        };
+        let hurl_result = HurlResult {
+            entries: vec![
+                entry(1, Some("login"), &["smoke"]),
+                entry(2, None, &["smoke", "slow"]),
+                // Retry of the second entry
+                entry(2, None, &["smoke", "slow"]),
+            ],
+            time_in_ms: 230,
+            success: true,
+            cookies: vec![],
+            timestamp: 1,
+            secrets: vec![],
+        };
+        let element = Testcase::from(&hurl_result, "", "test.hurl").to_xml();
+        let doc = XmlDocument::new(element);
+        assert_eq!(
+            doc.to_string().unwrap(),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?>\
+            <testcase id=\"test.hurl\" name=\"test.hurl\" time=\"0.230\">\
+                <properties>\
+                    <property name=\"entry\" value=\"login\" />\
+                    <property name=\"tag\" value=\"smoke\" />\
+                    <property name=\"tag\" value=\"slow\" />\
+                </properties>\
+            </testcase>"
+        );
+    }
 }
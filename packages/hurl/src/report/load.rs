@@ -0,0 +1,311 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Load test report: throughput, error rate and latency percentiles of each entry of a Hurl file
+//! run repeatedly by concurrent users.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use serde_json::Number;
+
+use crate::runner::HurlResult;
+
+/// The aggregated results of the iterations of a load test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LoadReport {
+    /// Number of concurrent users
+    pub users: usize,
+    /// Elapsed time of the load test
+    pub duration: Duration,
+    /// Number of runs of the Hurl file
+    pub iterations: usize,
+    /// Number of runs of the Hurl file with at least one error
+    pub failed_iterations: usize,
+    /// Statistics of each entry, by entry index
+    pub entries: BTreeMap<usize, EntryStats>,
+}
+
+/// Statistics of an entry of a load test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryStats {
+    /// Entry name, or method and URL of its first request if the entry has no name
+    pub label: Option<String>,
+    /// Number of runs of this entry
+    pub runs: usize,
+    /// Number of runs of this entry with at least one error (failed asserts included)
+    pub errors: usize,
+    /// Network time of each run of this entry that has executed its HTTP requests
+    pub latencies: Vec<Duration>,
+}
+
+impl LoadReport {
+    /// Creates a new empty report for a load test of `users` concurrent users.
+    pub fn new(users: usize) -> Self {
+        LoadReport {
+            users,
+            duration: Duration::default(),
+            iterations: 0,
+            failed_iterations: 0,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the result of one iteration of the load test to this report.
+    pub fn add(&mut self, hurl_result: &HurlResult) {
+        self.iterations += 1;
+        if !hurl_result.success {
+            self.failed_iterations += 1;
+        }
+        for entry in &hurl_result.entries {
+            let stats = self
+                .entries
+                .entry(entry.entry_index)
+                .or_insert_with(|| EntryStats {
+                    label: None,
+                    runs: 0,
+                    errors: 0,
+                    latencies: vec![],
+                });
+            stats.runs += 1;
+            if !entry.errors.is_empty() {
+                stats.errors += 1;
+            }
+            if !entry.calls.is_empty() {
+                let latency = entry.calls.iter().map(|c| c.timings.total).sum();
+                stats.latencies.push(latency);
+            }
+            if stats.label.is_none() {
+                stats.label = match (&entry.name, entry.calls.first()) {
+                    (Some(name), _) => Some(name.clone()),
+                    (None, Some(call)) => {
+                        Some(format!("{} {}", call.request.method, call.request.url))
+                    }
+                    (None, None) => None,
+                };
+            }
+        }
+    }
+
+    /// Returns the total number of entries run.
+    pub fn requests(&self) -> usize {
+        self.entries.values().map(|e| e.runs).sum()
+    }
+
+    /// Returns the total number of entries run with errors.
+    pub fn errors(&self) -> usize {
+        self.entries.values().map(|e| e.errors).sum()
+    }
+
+    /// Returns the text representation of this report.
+    pub fn to_text(&self) -> String {
+        let duration = self.duration.as_secs_f64();
+        let requests = self.requests();
+        let errors = self.errors();
+        let mut text = format!(
+            "--------------------------------------------------------------------------------\n\
+             Users:           {}\n\
+             Duration:        {duration:.1} s\n\
+             Iterations:      {} ({} failed)\n\
+             Requests:        {requests} ({:.1} req/s)\n\
+             Errors:          {errors} ({:.1}%)\n",
+            self.users,
+            self.iterations,
+            self.failed_iterations,
+            rate(requests, duration),
+            100.0 * ratio(errors, requests),
+        );
+        for (index, stats) in &self.entries {
+            let label = match &stats.label {
+                Some(label) => format!("Entry {index}: {label}"),
+                None => format!("Entry {index}"),
+            };
+            text.push_str(&format!(
+                "\n{label}\n\
+                 \x20 Requests:      {} ({:.1} req/s)\n\
+                 \x20 Errors:        {} ({:.1}%)\n\
+                 \x20 Latency:       p50 {:.1} ms, p90 {:.1} ms, p99 {:.1} ms\n",
+                stats.runs,
+                rate(stats.runs, duration),
+                stats.errors,
+                100.0 * ratio(stats.errors, stats.runs),
+                stats.percentile(50).as_secs_f64() * 1000.0,
+                stats.percentile(90).as_secs_f64() * 1000.0,
+                stats.percentile(99).as_secs_f64() * 1000.0,
+            ));
+        }
+        text
+    }
+
+    /// Serializes this report to a JSON representation, for the Hurl file `filename`.
+    ///
+    /// Durations are in milliseconds, latencies in microseconds (as the JSON timings of a call).
+    pub fn to_json(&self, filename: &str) -> serde_json::Value {
+        let duration = self.duration.as_secs_f64();
+        let requests = self.requests();
+        let errors = self.errors();
+        let mut map = serde_json::Map::new();
+        map.insert(
+            "filename".to_string(),
+            serde_json::Value::String(filename.to_string()),
+        );
+        map.insert("users".to_string(), number(self.users as u64));
+        map.insert("time".to_string(), number(self.duration.as_millis() as u64));
+        map.insert("iterations".to_string(), number(self.iterations as u64));
+        map.insert(
+            "failed_iterations".to_string(),
+            number(self.failed_iterations as u64),
+        );
+        map.insert("requests".to_string(), number(requests as u64));
+        map.insert("errors".to_string(), number(errors as u64));
+        map.insert("error_rate".to_string(), float(ratio(errors, requests)));
+        map.insert("throughput".to_string(), float(rate(requests, duration)));
+        let entries = self
+            .entries
+            .iter()
+            .map(|(index, stats)| stats.to_json(*index, duration))
+            .collect();
+        map.insert("entries".to_string(), serde_json::Value::Array(entries));
+        serde_json::Value::Object(map)
+    }
+}
+
+impl EntryStats {
+    /// Returns the `p`th percentile of the latencies of this entry (nearest-rank method).
+    pub fn percentile(&self, p: usize) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::default();
+        }
+        let mut latencies = self.latencies.clone();
+        latencies.sort();
+        let rank = (p * latencies.len()).div_ceil(100).max(1);
+        latencies[rank - 1]
+    }
+
+    fn to_json(&self, index: usize, duration: f64) -> serde_json::Value {
+        let mut map = serde_json::Map::new();
+        map.insert("index".to_string(), number(index as u64));
+        if let Some(label) = &self.label {
+            map.insert(
+                "label".to_string(),
+                serde_json::Value::String(label.clone()),
+            );
+        }
+        map.insert("requests".to_string(), number(self.runs as u64));
+        map.insert("errors".to_string(), number(self.errors as u64));
+        map.insert(
+            "error_rate".to_string(),
+            float(ratio(self.errors, self.runs)),
+        );
+        map.insert("throughput".to_string(), float(rate(self.runs, duration)));
+        let mut latency = serde_json::Map::new();
+        for p in [50, 90, 99] {
+            let value = self.percentile(p).as_micros() as u64;
+            latency.insert(format!("p{p}"), number(value));
+        }
+        map.insert("latency".to_string(), serde_json::Value::Object(latency));
+        serde_json::Value::Object(map)
+    }
+}
+
+/// Returns `count / total`, or 0 if `total` is 0.
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+/// Returns the number of `count` per second during `duration` seconds.
+fn rate(count: usize, duration: f64) -> f64 {
+    if duration == 0.0 {
+        0.0
+    } else {
+        count as f64 / duration
+    }
+}
+
+fn number(value: u64) -> serde_json::Value {
+    serde_json::Value::Number(Number::from(value))
+}
+
+fn float(value: f64) -> serde_json::Value {
+    match Number::from_f64(value) {
+        Some(n) => serde_json::Value::Number(n),
+        None => serde_json::Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(latencies_in_ms: &[u64]) -> EntryStats {
+        EntryStats {
+            label: None,
+            runs: latencies_in_ms.len(),
+            errors: 0,
+            latencies: latencies_in_ms
+                .iter()
+                .map(|ms| Duration::from_millis(*ms))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn compute_percentiles() {
+        let latencies = (1..=100).rev().collect::<Vec<_>>();
+        let entry = stats(&latencies);
+        assert_eq!(entry.percentile(50), Duration::from_millis(50));
+        assert_eq!(entry.percentile(90), Duration::from_millis(90));
+        assert_eq!(entry.percentile(99), Duration::from_millis(99));
+
+        let entry = stats(&[30, 10, 20]);
+        assert_eq!(entry.percentile(50), Duration::from_millis(20));
+        assert_eq!(entry.percentile(99), Duration::from_millis(30));
+
+        let entry = stats(&[]);
+        assert_eq!(entry.percentile(50), Duration::default());
+    }
+
+    #[test]
+    fn report_to_text() {
+        let mut report = LoadReport::new(2);
+        report.duration = Duration::from_secs(2);
+        report.iterations = 4;
+        report.failed_iterations = 1;
+        let mut login = stats(&[10, 20, 30, 40]);
+        login.label = Some("login".to_string());
+        login.errors = 1;
+        report.entries.insert(1, login);
+        assert_eq!(
+            report.to_text(),
+            "--------------------------------------------------------------------------------\n\
+             Users:           2\n\
+             Duration:        2.0 s\n\
+             Iterations:      4 (1 failed)\n\
+             Requests:        4 (2.0 req/s)\n\
+             Errors:          1 (25.0%)\n\
+             \n\
+             Entry 1: login\n\
+             \x20 Requests:      4 (2.0 req/s)\n\
+             \x20 Errors:        1 (25.0%)\n\
+             \x20 Latency:       p50 20.0 ms, p90 40.0 ms, p99 40.0 ms\n"
+        );
+    }
+}
@@ -16,13 +16,15 @@
  *
  */
 
-//! Various reports for Hurl runs (JUnit, HTML etc...) A report aggregates multiple runs into
+//! Various reports for Hurl runs (JUnit, HTML, curl script etc...) A report aggregates multiple runs into
 //! a single unit.
 
 use std::fmt;
 
+pub mod curl;
 pub mod html;
 pub mod junit;
+pub mod load;
 pub mod tap;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
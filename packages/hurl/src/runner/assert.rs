@@ -23,7 +23,7 @@ use crate::http;
 use crate::runner::error::{Error, RunnerError};
 use crate::runner::filter::eval_filters;
 use crate::runner::predicate::eval_predicate;
-use crate::runner::query::eval_query;
+use crate::runner::query::{eval_query, eval_query_xpath_nodes};
 use crate::runner::result::AssertResult;
 use crate::runner::Value;
 use crate::util::path::ContextDir;
@@ -98,6 +98,24 @@ impl AssertResult {
                     }
                 },
             },
+            AssertResult::JsonBody {
+                mismatches,
+                source_info,
+            } => match mismatches {
+                Err(e) => Some(e.clone()),
+                Ok(errors) if errors.is_empty() => None,
+                Ok(errors) => {
+                    // Column error is set to 0 to display the mismatches under the body line.
+                    let source_info = SourceInfo::new(
+                        Pos::new(source_info.start.line, 0),
+                        Pos::new(source_info.start.line, 0),
+                    );
+                    let inner = RunnerError::AssertJsonMatch {
+                        errors: errors.clone(),
+                    };
+                    Some(Error::new(source_info, inner, true))
+                }
+            },
             AssertResult::Explicit { actual: Err(e), .. } => Some(e.clone()),
             AssertResult::Explicit {
                 predicate_result: Some(Err(e)),
@@ -112,6 +130,7 @@ impl AssertResult {
             AssertResult::Status { source_info, .. } => source_info.start.line,
             AssertResult::Header { source_info, .. } => source_info.start.line,
             AssertResult::Body { source_info, .. } => source_info.start.line,
+            AssertResult::JsonBody { source_info, .. } => source_info.start.line,
             AssertResult::Explicit { source_info, .. } => source_info.start.line,
         }
     }
@@ -122,8 +141,16 @@ pub fn eval_assert(
     variables: &HashMap<String, Value>,
     http_response: &http::Response,
     context_dir: &ContextDir,
+    update_snapshots: bool,
+    json_match: bool,
 ) -> AssertResult {
-    let query_result = eval_query(&assert.query, variables, http_response);
+    let query_result = match (&assert.query.value, &assert.predicate.predicate_func.value) {
+        // XML schemas validate the nodes selected by an xpath query, not the nodeset size.
+        (QueryValue::Xpath { expr, .. }, PredicateFuncValue::MatchXmlSchema { .. }) => {
+            eval_query_xpath_nodes(http_response, expr, variables, assert.query.source_info)
+        }
+        _ => eval_query(&assert.query, variables, http_response),
+    };
 
     let actual = if assert.filters.is_empty() {
         query_result
@@ -151,6 +178,19 @@ pub fn eval_assert(
         query_result
     };
 
+    // JSON predicates parse the response body, whereas other strings (like the ones selected by a
+    // JSONPath query) are JSON strings.
+    let actual = match actual {
+        Ok(Some(body))
+            if assert.filters.is_empty()
+                && assert.query.value == QueryValue::Body
+                && is_json_predicate(&assert.predicate.predicate_func.value) =>
+        {
+            Ok(Some(json_body(body)))
+        }
+        actual => actual,
+    };
+
     let source_info = assert.predicate.predicate_func.source_info;
     let predicate_result = match &actual {
         Err(_) => None,
@@ -159,6 +199,8 @@ pub fn eval_assert(
             variables,
             actual,
             context_dir,
+            update_snapshots,
+            json_match,
         )),
     };
 
@@ -169,6 +211,31 @@ pub fn eval_assert(
     }
 }
 
+/// Returns true if a predicate compares the actual value to a JSON value or a JSON schema.
+fn is_json_predicate(predicate_func: &PredicateFuncValue) -> bool {
+    matches!(
+        predicate_func,
+        PredicateFuncValue::Equal {
+            value: PredicateValue::Json(_),
+            ..
+        } | PredicateFuncValue::NotEqual {
+            value: PredicateValue::Json(_),
+            ..
+        } | PredicateFuncValue::MatchSchema { .. }
+    )
+}
+
+/// Returns the JSON value of a text `body`, or the body itself if it's not a JSON text.
+fn json_body(body: Value) -> Value {
+    match &body {
+        Value::String(text) => match serde_json::from_str::<serde_json::Value>(text) {
+            Ok(json) => Value::from_json(&json),
+            Err(_) => body,
+        },
+        _ => body,
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use hurl_core::ast::SourceInfo;
@@ -232,7 +299,9 @@ pub mod tests {
                 &assert_count_user(),
                 &variables,
                 &xml_three_users_http_response(),
-                &context_dir
+                &context_dir,
+                false,
+                false,
             ),
             AssertResult::Explicit {
                 actual: Ok(Some(Value::Number(Number::Integer(3)))),
@@ -241,4 +310,16 @@ pub mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_json_body() {
+        let body = |text: &str| json_body(Value::String(text.to_string()));
+        assert_eq!(body("42"), Value::Number(Number::Integer(42)));
+        assert_eq!(body("\"Hello\""), Value::String("Hello".to_string()));
+        assert_eq!(
+            body("{\"id\": 1}"),
+            Value::Object(vec![("id".to_string(), Value::Number(Number::Integer(1)))])
+        );
+        assert_eq!(body("Hello"), Value::String("Hello".to_string()));
+    }
 }
@@ -96,6 +96,8 @@ pub mod tests {
             // xpath count(//user)
             query: query::tests::xpath_count_user_query(),
             filters: vec![],
+            space3: whitespace.clone(),
+            redact: false,
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -127,6 +129,8 @@ pub mod tests {
             // xpath count(//user)
             query: query::tests::jsonpath_duration(),
             filters: vec![],
+            space3: whitespace.clone(),
+            redact: false,
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -158,6 +162,8 @@ pub mod tests {
             space2: whitespace.clone(),
 
             query: query::tests::xpath_invalid_query(),
+            space3: whitespace.clone(),
+            redact: false,
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -209,6 +215,8 @@ pub mod tests {
                 },
             },
             filters: vec![],
+            space3: whitespace.clone(),
+            redact: false,
             line_terminator0: LineTerminator {
                 space0: whitespace.clone(),
                 comment: None,
@@ -16,6 +16,7 @@
  *
  */
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use hurl_core::ast::*;
@@ -23,10 +24,12 @@ use hurl_core::ast::*;
 use crate::http;
 use crate::http::ClientOptions;
 use crate::runner::error::Error;
+use crate::runner::include::IncludedFile;
 use crate::runner::request::{cookie_storage_clear, cookie_storage_set, eval_request};
 use crate::runner::response::{eval_asserts, eval_captures, eval_version_status_asserts};
-use crate::runner::result::{AssertResult, EntryResult};
+use crate::runner::result::{AssertResult, CaptureResult, DryRunEntry, EntryResult};
 use crate::runner::runner_options::RunnerOptions;
+use crate::runner::template::eval_template;
 use crate::runner::value::Value;
 use crate::util::logger::{Logger, Verbosity};
 
@@ -36,9 +39,11 @@ use crate::util::logger::{Logger, Verbosity};
 /// been executed. If `http_client` has been configured to follow redirection, the `calls` list contains
 /// every step of the redirection for the first to the last.
 /// `variables` are used to render values at runtime, and can be updated by captures.
+/// `file` is the file `entry` has been included from, `None` for the main file.
 pub fn run(
     entry: &Entry,
     entry_index: usize,
+    file: Option<&Arc<IncludedFile>>,
     http_client: &mut http::Client,
     variables: &mut HashMap<String, Value>,
     runner_options: &RunnerOptions,
@@ -50,11 +55,17 @@ pub fn run(
         Err(error) => {
             return EntryResult {
                 entry_index,
+                iteration: None,
+                name: entry.name(),
+                tags: entry.tags(),
+                file: file.cloned(),
                 source_info: entry.source_info(),
                 calls: vec![],
                 captures: vec![],
                 asserts: vec![],
                 errors: vec![error],
+                curl_cmd: String::new(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: runner_options.compressed,
             };
@@ -104,11 +115,17 @@ pub fn run(
             let error = Error::new(error_source_info, http_error.into(), false);
             return EntryResult {
                 entry_index,
+                iteration: None,
+                name: entry.name(),
+                tags: entry.tags(),
+                file: file.cloned(),
                 source_info: entry.source_info(),
                 calls: vec![],
                 captures: vec![],
                 asserts: vec![],
                 errors: vec![error],
+                curl_cmd: curl_command.clone(),
+                retry: None,
                 time_in_ms: 0,
                 compressed: client_options.compressed,
             };
@@ -139,11 +156,17 @@ pub fn run(
                 logger.debug("");
                 return EntryResult {
                     entry_index,
+                    iteration: None,
+                    name: entry.name(),
+                    tags: entry.tags(),
+                    file: file.cloned(),
                     source_info: entry.source_info(),
                     calls,
                     captures: vec![],
                     asserts,
                     errors,
+                    curl_cmd: curl_command.clone(),
+                    retry: None,
                     time_in_ms,
                     compressed: client_options.compressed,
                 };
@@ -159,11 +182,17 @@ pub fn run(
             Err(e) => {
                 return EntryResult {
                     entry_index,
+                    iteration: None,
+                    name: entry.name(),
+                    tags: entry.tags(),
+                    file: file.cloned(),
                     source_info: entry.source_info(),
                     calls,
                     captures: vec![],
                     asserts: all_asserts,
                     errors: vec![e],
+                    curl_cmd: curl_command.clone(),
+                    retry: None,
                     time_in_ms,
                     compressed: client_options.compressed,
                 };
@@ -171,6 +200,10 @@ pub fn run(
         },
     };
 
+    // Values of redacted captures are hidden from the logs, starting with the captures log below.
+    for secret in redacted_values(entry, &captures) {
+        logger.add_secret(&secret);
+    }
     if !captures.is_empty() {
         logger.debug_important("Captures:");
         for c in captures.iter() {
@@ -182,7 +215,14 @@ pub fn run(
     // Compute asserts
     if !runner_options.ignore_asserts {
         if let Some(response_spec) = &entry.response {
-            let mut asserts = eval_asserts(response_spec, variables, http_response, context_dir);
+            let mut asserts = eval_asserts(
+                response_spec,
+                variables,
+                http_response,
+                context_dir,
+                runner_options.update_snapshots,
+                runner_options.json_match,
+            );
             all_asserts.append(&mut asserts);
         }
     };
@@ -191,16 +231,82 @@ pub fn run(
 
     EntryResult {
         entry_index,
+        iteration: None,
+        name: entry.name(),
+        tags: entry.tags(),
+        file: file.cloned(),
         source_info: entry.source_info(),
         calls,
         captures,
         asserts: all_asserts,
         errors,
+        curl_cmd: curl_command,
+        retry: None,
         time_in_ms,
         compressed: client_options.compressed,
     }
 }
 
+/// Renders the request of an `entry` with `http_client`, without sending it, and returns one
+/// [`DryRunEntry`].
+///
+/// As the values captured by `entry` can't be known without a response, the captured variables
+/// are set to placeholders (`{{name}}`) in `variables`, for the next entries.
+pub fn dry_run(
+    entry: &Entry,
+    entry_index: usize,
+    file: Option<&Arc<IncludedFile>>,
+    http_client: &mut http::Client,
+    variables: &mut HashMap<String, Value>,
+    runner_options: &RunnerOptions,
+    logger: &Logger,
+) -> Result<DryRunEntry, Error> {
+    let context_dir = &runner_options.context_dir;
+    let http_request = eval_request(&entry.request, variables, context_dir)?;
+    let client_options = ClientOptions::from(runner_options, logger.verbosity);
+    log_request_spec(&http_request, logger);
+
+    let output = &runner_options.output;
+    let curl = http_client.curl_command_line(
+        &http_request,
+        context_dir,
+        output.as_deref(),
+        &client_options,
+    );
+
+    if let Some(response_spec) = &entry.response {
+        for capture in response_spec.captures() {
+            let name = eval_template(&capture.name, variables)?;
+            let placeholder = Value::String(format!("{{{{{name}}}}}"));
+            variables.insert(name, placeholder);
+        }
+    }
+
+    Ok(DryRunEntry {
+        entry_index,
+        iteration: None,
+        file: file.cloned(),
+        source_info: entry.source_info(),
+        request: http_request,
+        curl,
+    })
+}
+
+/// Returns the values of the `captures` of this `entry` that are marked with `redact`.
+pub fn redacted_values(entry: &Entry, captures: &[CaptureResult]) -> Vec<String> {
+    let specs = match &entry.response {
+        Some(response_spec) => response_spec.captures(),
+        None => return vec![],
+    };
+    // Captures are evaluated in the order of their spec.
+    specs
+        .iter()
+        .zip(captures)
+        .filter(|(spec, _)| spec.redact)
+        .map(|(_, capture)| capture.value.to_string())
+        .collect()
+}
+
 /// Converts a list of [`AssertResult`] to a list of [`Error`].
 fn asserts_to_errors(asserts: &[AssertResult]) -> Vec<Error> {
     asserts
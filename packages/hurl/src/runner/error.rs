@@ -56,6 +56,21 @@ pub enum RunnerError {
     AssertHeaderValueError {
         actual: String,
     },
+    AssertJsonMatch {
+        errors: Vec<String>,
+    },
+    AssertSchema {
+        file: String,
+        errors: Vec<String>,
+    },
+    AssertSnapshot {
+        file: String,
+        diff: String,
+    },
+    AssertXmlSchema {
+        file: String,
+        errors: Vec<String>,
+    },
     AssertStatus {
         actual: String,
     },
@@ -64,6 +79,9 @@ pub enum RunnerError {
     },
     CouldNotParseResponse,
     CouldNotUncompressResponse(String),
+    EnvVariableNotDefined {
+        name: String,
+    },
     FileReadAccess {
         file: String,
     },
@@ -76,7 +94,14 @@ pub enum RunnerError {
     FilterInvalidEncoding(String),
     FilterInvalidInput(String),
     FilterMissingInput,
+    FilterMissingValue {
+        name: String,
+    },
+    FunctionInvalidArgument(String),
     HttpConnection(String),
+    IncludeCycle {
+        file: String,
+    },
     InvalidJson {
         value: String,
     },
@@ -86,10 +111,19 @@ pub enum RunnerError {
     InvalidDecoding {
         charset: String,
     },
+    InvalidJsonSchema {
+        file: String,
+        message: String,
+    },
     InvalidRegex,
+    InvalidXmlSchema {
+        file: String,
+        message: String,
+    },
     InvalidUrl(String),
     InvalidUrlPrefix(String),
     NoQueryResult,
+    OpenApiViolation(String),
     QueryHeaderNotFound,
     QueryInvalidJsonpathExpression {
         value: String,
@@ -128,24 +162,37 @@ impl hurl_core::error::Error for Error {
             RunnerError::AssertBodyValueError { .. } => "Assert body value".to_string(),
             RunnerError::AssertFailure { .. } => "Assert failure".to_string(),
             RunnerError::AssertHeaderValueError { .. } => "Assert header value".to_string(),
+            RunnerError::AssertJsonMatch { .. } => "Assert JSON match".to_string(),
+            RunnerError::AssertSchema { .. } => "Assert JSON schema".to_string(),
+            RunnerError::AssertSnapshot { .. } => "Assert snapshot".to_string(),
+            RunnerError::AssertXmlSchema { .. } => "Assert XML schema".to_string(),
             RunnerError::AssertStatus { .. } => "Assert status code".to_string(),
             RunnerError::AssertVersion { .. } => "Assert HTTP version".to_string(),
             RunnerError::CouldNotParseResponse => "HTTP connection".to_string(),
             RunnerError::CouldNotUncompressResponse(..) => "Decompression error".to_string(),
+            RunnerError::EnvVariableNotDefined { .. } => {
+                "Undefined environment variable".to_string()
+            }
             RunnerError::FileReadAccess { .. } => "File read access".to_string(),
             RunnerError::FileWriteAccess { .. } => "File write access".to_string(),
             RunnerError::FilterDecode { .. } => "Filter Error".to_string(),
             RunnerError::FilterInvalidEncoding { .. } => "Filter Error".to_string(),
             RunnerError::FilterInvalidInput { .. } => "Filter Error".to_string(),
             RunnerError::FilterMissingInput => "Filter Error".to_string(),
+            RunnerError::FilterMissingValue { .. } => "Filter Error".to_string(),
+            RunnerError::FunctionInvalidArgument(..) => "Invalid function argument".to_string(),
             RunnerError::HttpConnection { .. } => "HTTP connection".to_string(),
+            RunnerError::IncludeCycle { .. } => "Include cycle".to_string(),
             RunnerError::InvalidCharset { .. } => "Invalid charset".to_string(),
             RunnerError::InvalidDecoding { .. } => "Invalid decoding".to_string(),
             RunnerError::InvalidJson { .. } => "Invalid JSON".to_string(),
+            RunnerError::InvalidJsonSchema { .. } => "Invalid JSON schema".to_string(),
             RunnerError::InvalidRegex => "Invalid regex".to_string(),
+            RunnerError::InvalidXmlSchema { .. } => "Invalid XML schema".to_string(),
             RunnerError::InvalidUrl(..) => "Invalid URL".to_string(),
             RunnerError::InvalidUrlPrefix(..) => "Invalid URL".to_string(),
             RunnerError::NoQueryResult => "No query result".to_string(),
+            RunnerError::OpenApiViolation(..) => "OpenAPI violation".to_string(),
             RunnerError::QueryHeaderNotFound => "Header not found".to_string(),
             RunnerError::QueryInvalidJson => "Invalid JSON".to_string(),
             RunnerError::QueryInvalidJsonpathExpression { .. } => "Invalid JSONPath".to_string(),
@@ -182,12 +229,30 @@ impl hurl_core::error::Error for Error {
             RunnerError::AssertHeaderValueError { actual } => {
                 format!("actual value is <{actual}>")
             }
+            RunnerError::AssertJsonMatch { errors } => {
+                let errors = errors.join("\n");
+                format!("actual value does not match expected JSON:\n{errors}")
+            }
+            RunnerError::AssertSchema { file, errors } => {
+                let errors = errors.join("\n");
+                format!("actual value is not valid against schema {file}:\n{errors}")
+            }
+            RunnerError::AssertSnapshot { file, diff } => {
+                format!("actual value differs from snapshot {file} (- snapshot, + actual):\n{diff}")
+            }
+            RunnerError::AssertXmlSchema { file, errors } => {
+                let errors = errors.join("\n");
+                format!("actual value is not valid against schema {file}:\n{errors}")
+            }
             RunnerError::AssertStatus { actual, .. } => format!("actual value is <{actual}>"),
             RunnerError::AssertVersion { actual, .. } => format!("actual value is <{actual}>"),
             RunnerError::CouldNotParseResponse => "could not parse response".to_string(),
             RunnerError::CouldNotUncompressResponse(algorithm) => {
                 format!("could not uncompress response with {algorithm}")
             }
+            RunnerError::EnvVariableNotDefined { name } => {
+                format!("environment variable {name} is not defined")
+            }
             RunnerError::FileReadAccess { file } => format!("file {file} can not be read"),
             RunnerError::FileWriteAccess { file, error } => {
                 format!("{file} can not be written ({error})")
@@ -202,7 +267,14 @@ impl hurl_core::error::Error for Error {
                 format!("invalid filter input: {message}")
             }
             RunnerError::FilterMissingInput => "missing value to apply filter".to_string(),
+            RunnerError::FilterMissingValue { name } => {
+                format!("filter <{name}> of the expression has no value")
+            }
+            RunnerError::FunctionInvalidArgument(message) => message.to_string(),
             RunnerError::HttpConnection(message) => message.to_string(),
+            RunnerError::IncludeCycle { file } => {
+                format!("file {file} is already being included")
+            }
             RunnerError::InvalidCharset { charset } => {
                 format!("the charset '{charset}' is not valid")
             }
@@ -212,12 +284,19 @@ impl hurl_core::error::Error for Error {
             RunnerError::InvalidJson { value } => {
                 format!("actual value is <{value}>")
             }
+            RunnerError::InvalidJsonSchema { file, message } => {
+                format!("JSON schema {file} is not valid ({message})")
+            }
             RunnerError::InvalidRegex => "regex expression is not valid".to_string(),
+            RunnerError::InvalidXmlSchema { file, message } => {
+                format!("XML schema {file} is not valid ({message})")
+            }
             RunnerError::InvalidUrl(url) => format!("invalid URL <{url}>"),
             RunnerError::InvalidUrlPrefix(url) => {
                 format!("URL <{url}> must start with http:// or https://")
             }
             RunnerError::NoQueryResult => "The query didn't return any result".to_string(),
+            RunnerError::OpenApiViolation(message) => message.clone(),
             RunnerError::QueryHeaderNotFound => {
                 "this header has not been found in the response".to_string()
             }
@@ -276,6 +355,9 @@ impl From<HttpError> for RunnerError {
             } => RunnerError::HttpConnection(format!(
                 "Option {option} requires libcurl version {minimum_version} or higher"
             )),
+            HttpError::NoRecordedCall { method, url } => {
+                RunnerError::HttpConnection(format!("no recorded call for {method} {url}"))
+            }
             HttpError::StatuslineIsMissing => {
                 RunnerError::HttpConnection("status line is missing".to_string())
             }
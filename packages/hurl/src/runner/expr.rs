@@ -17,20 +17,43 @@
  */
 use std::collections::HashMap;
 
-use hurl_core::ast::Expr;
+use hurl_core::ast::{Expr, ExprKind};
 
 use crate::runner::error::{Error, RunnerError};
+use crate::runner::filter::eval_filters;
+use crate::runner::function;
+use crate::runner::options::eval_variable_value;
 use crate::runner::value::Value;
 
 /// Evaluates the expression `expr` with `variables` map and `http_response`, returns a
 /// [`Value`] on success or an [`Error`] .
 pub fn eval_expr(expr: &Expr, variables: &HashMap<String, Value>) -> Result<Value, Error> {
-    if let Some(value) = variables.get(expr.variable.name.as_str()) {
-        Ok(value.clone())
-    } else {
-        let inner = RunnerError::TemplateVariableNotDefined {
-            name: expr.variable.name.clone(),
-        };
-        Err(Error::new(expr.variable.source_info, inner, false))
+    let value = match &expr.kind {
+        ExprKind::Variable(variable) => match (variables.get(&variable.name), &expr.default) {
+            (Some(value), _) => value.clone(),
+            (None, Some(default)) => eval_variable_value(&default.value, variables)?,
+            (None, None) => {
+                let inner = RunnerError::TemplateVariableNotDefined {
+                    name: variable.name.clone(),
+                };
+                return Err(Error::new(variable.source_info, inner, false));
+            }
+        },
+        ExprKind::Function(function) => function::eval(function)?,
+    };
+    if expr.filters.is_empty() {
+        return Ok(value);
+    }
+    let filters = expr.filters.iter().map(|f| f.filter.clone()).collect();
+    match eval_filters(&filters, &value, variables, false)? {
+        Some(value) => Ok(value),
+        None => {
+            // A filter without input fails, so the missing value comes from the last filter.
+            let filter = &filters[filters.len() - 1];
+            let inner = RunnerError::FilterMissingValue {
+                name: filter.value.name().to_string(),
+            };
+            Err(Error::new(filter.source_info, inner, false))
+        }
     }
 }
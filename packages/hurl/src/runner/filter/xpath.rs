@@ -55,6 +55,28 @@ pub fn eval_xpath_string(
     } else {
         xpath::eval_xml(xml, &expr)
     };
+    xpath_result(result, &expr, expr_template, source_info)
+}
+
+/// Evaluates an xpath expression against a XML text `xml`, returning the selected nodes as a list
+/// of XML texts instead of a nodeset.
+pub fn eval_xpath_nodes_string(
+    xml: &str,
+    expr_template: &Template,
+    variables: &HashMap<String, Value>,
+    source_info: SourceInfo,
+) -> Result<Option<Value>, Error> {
+    let expr = eval_template(expr_template, variables)?;
+    let result = xpath::eval_xml_nodes(xml, &expr);
+    xpath_result(result, &expr, expr_template, source_info)
+}
+
+fn xpath_result(
+    result: Result<Value, xpath::XpathError>,
+    expr: &str,
+    expr_template: &Template,
+    source_info: SourceInfo,
+) -> Result<Option<Value>, Error> {
     match result {
         Ok(value) => Ok(Some(value)),
         Err(xpath::XpathError::InvalidXml) => {
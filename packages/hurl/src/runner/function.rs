@@ -0,0 +1,181 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::cell::RefCell;
+use std::env;
+
+use chrono::Utc;
+use hurl_core::ast::{Function, FunctionValue};
+use rand::distributions::Alphanumeric;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::runner::error::{Error, RunnerError};
+use crate::runner::{Number, Value};
+
+thread_local! {
+    /// Random generator of the functions, one per thread as a Hurl file is run on a single thread.
+    static RNG: RefCell<StdRng> = RefCell::new(StdRng::from_entropy());
+}
+
+/// Seeds the random generator of the functions run on the current thread.
+///
+/// With a `seed`, the random values generated by the functions are reproducible, otherwise the
+/// random generator is seeded from the system entropy.
+pub fn seed(seed: Option<u64>) {
+    let rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    RNG.with(|r| *r.borrow_mut() = rng);
+}
+
+/// Evaluates a built-in `function` and returns its [`Value`].
+pub fn eval(function: &Function) -> Result<Value, Error> {
+    let source_info = function.source_info;
+    match &function.value {
+        FunctionValue::Env { name, .. } => match env::var(name) {
+            Ok(value) => Ok(Value::String(value)),
+            Err(_) => {
+                let inner = RunnerError::EnvVariableNotDefined { name: name.clone() };
+                Err(Error::new(source_info, inner, false))
+            }
+        },
+        FunctionValue::NewUuid { .. } => {
+            let bytes = RNG.with(|r| r.borrow_mut().gen::<[u8; 16]>());
+            let uuid = uuid::Builder::from_random_bytes(bytes).into_uuid();
+            Ok(Value::String(uuid.to_string()))
+        }
+        FunctionValue::Now { .. } => Ok(Value::Date(Utc::now())),
+        FunctionValue::RandomInt { min, max, .. } => {
+            if min > max {
+                let message = format!("randomInt minimum {min} is greater than maximum {max}");
+                let inner = RunnerError::FunctionInvalidArgument(message);
+                return Err(Error::new(source_info, inner, false));
+            }
+            let value = RNG.with(|r| r.borrow_mut().gen_range(*min..=*max));
+            Ok(Value::Number(Number::Integer(value)))
+        }
+        FunctionValue::RandomString { length, .. } => {
+            let value = RNG.with(|r| {
+                let mut rng = r.borrow_mut();
+                (0..*length)
+                    .map(|_| rng.sample(Alphanumeric) as char)
+                    .collect::<String>()
+            });
+            Ok(Value::String(value))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Pos, SourceInfo, Whitespace};
+
+    use super::*;
+
+    fn function(value: FunctionValue) -> Function {
+        Function {
+            source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 13)),
+            value,
+        }
+    }
+
+    fn whitespace() -> Whitespace {
+        Whitespace {
+            value: String::new(),
+            source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
+        }
+    }
+
+    fn random_int(min: i64, max: i64) -> FunctionValue {
+        FunctionValue::RandomInt {
+            space0: whitespace(),
+            min,
+            space1: whitespace(),
+            space2: whitespace(),
+            max,
+            space3: whitespace(),
+        }
+    }
+
+    #[test]
+    fn eval_random_functions_with_seed() {
+        let functions = [
+            function(FunctionValue::NewUuid {
+                space0: whitespace(),
+            }),
+            function(random_int(1, 100)),
+            function(FunctionValue::RandomString {
+                space0: whitespace(),
+                length: 12,
+                space1: whitespace(),
+            }),
+        ];
+        seed(Some(42));
+        let values = functions
+            .iter()
+            .map(|f| eval(f).unwrap())
+            .collect::<Vec<_>>();
+        seed(Some(42));
+        let other_values = functions
+            .iter()
+            .map(|f| eval(f).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(values, other_values);
+
+        match &values[0] {
+            Value::String(uuid) => assert!(uuid::Uuid::parse_str(uuid).is_ok()),
+            v => panic!("unexpected value {v:?}"),
+        }
+        match &values[1] {
+            Value::Number(Number::Integer(n)) => assert!((1..=100).contains(n)),
+            v => panic!("unexpected value {v:?}"),
+        }
+        match &values[2] {
+            Value::String(s) => {
+                assert_eq!(s.len(), 12);
+                assert!(s.chars().all(|c| c.is_ascii_alphanumeric()));
+            }
+            v => panic!("unexpected value {v:?}"),
+        }
+    }
+
+    #[test]
+    fn eval_function_error() {
+        let error = eval(&function(random_int(10, 1))).unwrap_err();
+        assert_eq!(
+            error.inner,
+            RunnerError::FunctionInvalidArgument(
+                "randomInt minimum 10 is greater than maximum 1".to_string()
+            )
+        );
+
+        let name = "HURL_UNDEFINED_ENV_VARIABLE".to_string();
+        let env = FunctionValue::Env {
+            space0: whitespace(),
+            name: name.clone(),
+            space1: whitespace(),
+        };
+        let error = eval(&function(env)).unwrap_err();
+        assert_eq!(error.inner, RunnerError::EnvVariableNotDefined { name });
+        assert_eq!(
+            error.source_info,
+            SourceInfo::new(Pos::new(1, 3), Pos::new(1, 13))
+        );
+    }
+}
@@ -17,19 +17,28 @@
  */
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::thread;
 use std::time::Instant;
 
 use chrono::Utc;
 use hurl_core::ast::VersionValue::VersionAnyLegacy;
-use hurl_core::ast::{Body, Bytes, Entry, HurlFile, MultilineString, Request, Response, Retry};
+use hurl_core::ast::{
+    Body, Bytes, Entry, MultilineString, Pos, Request, Response, Retry, SourceInfo,
+};
 use hurl_core::error::Error;
 use hurl_core::parser;
 
 use crate::http::Call;
-use crate::runner::runner_options::RunnerOptions;
-use crate::runner::{entry, options, EntryResult, HurlResult, RunnerError, Value};
-use crate::util::logger::{ErrorFormat, Logger, LoggerOptions, LoggerOptionsBuilder};
+use crate::openapi::OpenApi;
+use crate::runner::include::{ExpandedEntry, IncludeError, IncludedFile};
+use crate::runner::mock::MockServer;
+use crate::runner::runner_options::{FromEntry, RunnerOptions};
+use crate::runner::{
+    entry, function, include, options, retry, undefined, DryRunResult, EntryResult, HurlResult,
+    RunnerError, Value,
+};
+use crate::util::logger::{ErrorFormat, Logger, LoggerOptions, LoggerOptionsBuilder, Verbosity};
 use crate::{http, runner};
 
 /// Runs a Hurl `content` and returns a [`HurlResult`] upon completion.
@@ -77,48 +86,75 @@ pub fn run(
     logger_options: &LoggerOptions,
 ) -> Result<HurlResult, String> {
     let logger = Logger::from(logger_options);
+    let hurl_entries = parse_entries(content, runner_options, logger_options)?;
 
-    // Try to parse the content
-    let hurl_file = parser::parse_hurl_file(content);
-    let hurl_file = match hurl_file {
-        Ok(h) => h,
-        Err(e) => {
-            logger.error_rich(content, &e);
-            return Err(e.description());
-        }
-    };
+    log_run_info(&hurl_entries, runner_options, variables, &logger);
 
-    log_run_info(&hurl_file, runner_options, variables, &logger);
+    // Random values of the template functions are reproducible for a given seed.
+    function::seed(runner_options.seed);
+
+    let n = last_entry_index(&hurl_entries, runner_options);
+    let start = Instant::now();
+    let timestamp = Utc::now().timestamp();
+    let secrets = logger_options.secrets.clone();
+
+    // Before sending any request, we check that the variables used by the entries to run are
+    // defined, so a typo fails fast.
+    let entries = check_variables(
+        &hurl_entries,
+        n,
+        content,
+        runner_options,
+        variables,
+        &logger,
+    );
+    if !entries.is_empty() {
+        return Ok(HurlResult {
+            entries,
+            time_in_ms: start.elapsed().as_millis(),
+            success: false,
+            cookies: vec![],
+            timestamp,
+            secrets,
+        });
+    }
 
     // Now, we have a syntactically correct HurlFile instance, we can run it.
     let mut http_client = http::Client::new();
+    if let Some(cassette) = &runner_options.replay {
+        http_client.set_cassette(cassette.clone());
+    }
     let mut entries = vec![];
     let mut variables = variables.clone();
+    let mut secrets = secrets;
+    let first_index = first_entry_index(&hurl_entries, runner_options);
     let mut entry_index = 1;
+    let mut iteration = 0;
+    let mut for_each = options::ForEachScope::default();
     let mut retry_count = 1;
-    let n = if let Some(to_entry) = runner_options.to_entry {
-        to_entry
-    } else {
-        hurl_file.entries.len()
-    };
-    let start = Instant::now();
-    let timestamp = Utc::now().timestamp();
+    let mut retry_start = Instant::now();
 
     // Main loop processing each entry.
     // The `entry_index` is not always incremented of each loop tick: an entry can be retried upon
-    // errors for instance. Each entry is executed with options that are computed from the global
-    // runner options and the "overridden" request options.
+    // errors for instance, or repeated with `repeat` and `for-each` options. Each entry is executed
+    // with options that are computed from the global runner options and the "overridden" request
+    // options.
     loop {
         if entry_index > n {
             break;
         }
-        let entry = &hurl_file.entries[entry_index - 1];
+        let (entry, file) = &hurl_entries[entry_index - 1];
+        let file = file.as_ref();
+        if !is_entry_selected(entry, entry_index, first_index, runner_options) {
+            entry_index += 1;
+            continue;
+        }
 
         // We compute the new logger for this entry, before entering into the `run` function because
         // entry options can modify the logger and we want the preamble "Executing entry..." to be
         // displayed based on the entry level verbosity.
-        let logger =
-            get_entry_logger(entry, logger_options, &variables).map_err(|e| e.description())?;
+        let logger = get_entry_logger(entry, file, logger_options, &variables)
+            .map_err(|e| e.description())?;
         if let Some(pre_entry) = runner_options.pre_entry {
             let exit = pre_entry(entry.clone());
             if exit {
@@ -135,17 +171,39 @@ pub fn run(
 
         logger.test_progress(entry_index, n);
 
+        if retry_count == 1 {
+            retry_start = Instant::now();
+        }
+
         // The real execution of the entry happens here, with the overridden entry options.
-        let options = options::get_entry_options(entry, runner_options, &mut variables, &logger);
-        let entry_result = match &options {
+        for_each.enter(entry, entry_index, &mut variables);
+        let options =
+            options::get_entry_options(entry, iteration, runner_options, &mut variables, &logger);
+        // The variables of an entry with a `skip` or `only-if` expression are checked once we
+        // know that the entry is run.
+        let undefined = match &options {
+            Ok(options) if !options.skip && options.repeat != Some(0) => {
+                undefined::check_conditional(entry, &variables)
+            }
+            _ => vec![],
+        };
+        let mut entry_result = match &options {
+            Ok(_) if !undefined.is_empty() => error_result(entry_index, entry, file, undefined),
             Ok(options) => {
-                if options.skip {
+                if options.skip || options.repeat == Some(0) {
                     logger
                         .debug_important(format!("Entry {entry_index} has been skipped").as_str());
                     logger.debug("");
-                    entry_index += 1;
+                    (entry_index, iteration) = next_iteration(entry_index, iteration, options);
                     continue;
                 }
+                if let Some(count) = options.repeat {
+                    logger.debug("");
+                    logger.debug_important(
+                        format!("Iteration {}/{count} of entry {entry_index}", iteration + 1)
+                            .as_str(),
+                    );
+                }
 
                 let delay = options.delay;
                 let delay_ms = delay.as_millis();
@@ -158,39 +216,43 @@ pub fn run(
                     thread::sleep(delay);
                 };
 
-                entry::run(
+                let mut entry_result = entry::run(
                     entry,
                     entry_index,
+                    file,
                     &mut http_client,
                     &mut variables,
                     options,
                     &logger,
-                )
+                );
+                if let Some(openapi) = &runner_options.openapi {
+                    let errors = openapi_errors(openapi, entry, &entry_result.calls);
+                    entry_result.errors.extend(errors);
+                }
+                if options.repeat.is_some() {
+                    entry_result.iteration = Some(iteration + 1);
+                }
+                entry_result
             }
-            Err(error) => EntryResult {
-                entry_index,
-                source_info: entry.source_info(),
-                calls: vec![],
-                captures: vec![],
-                asserts: vec![],
-                errors: vec![error.clone()],
-                time_in_ms: 0,
-                compressed: false,
-            },
+            Err(error) => error_result(entry_index, entry, file, vec![error.clone()]),
         };
 
         // Check if we need to retry.
         let has_error = !entry_result.errors.is_empty();
-        let (retry_opts, retry_interval) = match &options {
-            Ok(options) => (options.retry, options.retry_interval),
-            Err(_) => (runner_options.retry, runner_options.retry_interval),
-        };
+        let retry_options = options.as_ref().unwrap_or(runner_options);
+        let retry_opts = retry_options.retry;
         // The retry threshold can only reached with a finite positive number of retries
         let retry_max_reached = if let Retry::Finite(r) = retry_opts {
             retry_count > r
         } else {
             false
         };
+        let retry_attempt = retry::new_attempt(&entry_result, retry_count, retry_options);
+        // The retries of an entry can also be limited by their overall duration.
+        let retry_max_time_reached = match retry_options.retry_max_time {
+            Some(max_time) => retry_start.elapsed() + retry_attempt.delay > max_time,
+            None => false,
+        };
         // If `retry_max_reached` is true, we print now a warning, before displaying any assert
         // error so any potential error is the last thing displayed to the user.
         // If `retry_max_reached` is not true (for instance `retry`is true, or there is no error
@@ -199,11 +261,18 @@ pub fn run(
         if retry_max_reached {
             logger.debug_important("Retry max count reached, no more retry");
             logger.debug("");
+        } else if retry_max_time_reached && has_error {
+            logger.debug_important("Retry max time reached, no more retry");
+            logger.debug("");
         }
 
         // We logs eventual errors, only if we're not retrying the current entry...
-        let retry = !matches!(retry_opts, Retry::None) && !retry_max_reached && has_error;
+        let retry = !matches!(retry_opts, Retry::None)
+            && !retry_max_reached
+            && !retry_max_time_reached
+            && has_error;
         if has_error {
+            let content = file.map_or(content, |f| f.content.as_str());
             log_errors(&entry_result, content, retry, &logger);
         }
 
@@ -212,7 +281,7 @@ pub fn run(
         if let Ok(RunnerOptions {
             output: Some(output),
             ..
-        }) = options
+        }) = &options
         {
             if !has_error {
                 // TODO: make output write and access error as part of entry result errors.
@@ -220,17 +289,22 @@ pub fn run(
                 // an error. If we want to treat it as an error, we've to add it to the current
                 // `entry_result` errors, and optionally deals with retry if we can't write to the
                 // specified path.
-                if !runner_options.context_dir.is_access_allowed(&output) {
+                if !runner_options.context_dir.is_access_allowed(output) {
                     let inner = RunnerError::UnauthorizedFileAccess {
                         path: PathBuf::from(output.clone()),
                     };
                     let error = runner::Error::new(entry.request.source_info, inner, false);
                     logger.warning(&error.fixme());
-                } else if let Err(error) = entry_result.write_response(output) {
+                } else if let Err(error) = entry_result.write_response(output.clone()) {
                     logger.warning(&error.fixme());
                 }
             }
         }
+        secrets.extend(entry::redacted_values(entry, &entry_result.captures));
+        let retry_interval = retry_attempt.delay;
+        if retry {
+            entry_result.retry = Some(retry_attempt);
+        }
         entries.push(entry_result);
 
         if retry {
@@ -259,8 +333,11 @@ pub fn run(
             break;
         }
 
-        // We pass to the next entry
-        entry_index += 1;
+        // We pass to the next iteration of this entry, or to the next entry
+        (entry_index, iteration) = match &options {
+            Ok(options) => next_iteration(entry_index, iteration, options),
+            Err(_) => (entry_index + 1, 0),
+        };
         retry_count = 1;
     }
 
@@ -273,12 +350,227 @@ pub fn run(
         success,
         cookies,
         timestamp,
+        secrets,
     })
 }
 
+/// Parses a Hurl `content`, with its included files, without running it.
+///
+/// Parsing errors are logged as in [`run`], and their description is returned.
+pub fn parse(
+    content: &str,
+    runner_options: &RunnerOptions,
+    logger_options: &LoggerOptions,
+) -> Result<(), String> {
+    parse_entries(content, runner_options, logger_options).map(|_| ())
+}
+
+/// Renders the requests of a Hurl `content` without sending them, and returns a [`DryRunResult`].
+///
+/// Templates are evaluated with `variables` and entry options as in [`run`]. The values captured by
+/// an entry being unknown, they're rendered as placeholders (`{{name}}`) in the next entries.
+/// Rendering stops at the first entry in error.
+pub fn dry_run(
+    content: &str,
+    runner_options: &RunnerOptions,
+    variables: &HashMap<String, Value>,
+    logger_options: &LoggerOptions,
+) -> Result<DryRunResult, String> {
+    let logger = Logger::from(logger_options);
+    let hurl_entries = parse_entries(content, runner_options, logger_options)?;
+
+    log_run_info(&hurl_entries, runner_options, variables, &logger);
+
+    function::seed(runner_options.seed);
+
+    let n = last_entry_index(&hurl_entries, runner_options);
+    let secrets = logger_options.secrets.clone();
+    let mut entries = vec![];
+
+    let errors = check_variables(
+        &hurl_entries,
+        n,
+        content,
+        runner_options,
+        variables,
+        &logger,
+    );
+    if !errors.is_empty() {
+        return Ok(DryRunResult {
+            entries,
+            success: false,
+            secrets,
+        });
+    }
+
+    // The HTTP client is only used to build the curl command lines: no request is sent.
+    let mut http_client = http::Client::new();
+    let mut variables = variables.clone();
+    let first_index = first_entry_index(&hurl_entries, runner_options);
+    let mut entry_index = 1;
+    let mut iteration = 0;
+    let mut for_each = options::ForEachScope::default();
+
+    while entry_index <= n {
+        let (entry, file) = &hurl_entries[entry_index - 1];
+        let file = file.as_ref();
+        if !is_entry_selected(entry, entry_index, first_index, runner_options) {
+            entry_index += 1;
+            continue;
+        }
+
+        let logger = get_entry_logger(entry, file, logger_options, &variables)
+            .map_err(|e| e.description())?;
+        logger.debug_important(
+            "------------------------------------------------------------------------------",
+        );
+        logger.debug_important(format!("Rendering entry {entry_index}").as_str());
+
+        for_each.enter(entry, entry_index, &mut variables);
+        let options =
+            options::get_entry_options(entry, iteration, runner_options, &mut variables, &logger);
+        let result = options.and_then(|options| {
+            let dry_run_entry = if options.skip || options.repeat == Some(0) {
+                logger.debug_important(format!("Entry {entry_index} has been skipped").as_str());
+                None
+            } else {
+                let mut dry_run_entry = entry::dry_run(
+                    entry,
+                    entry_index,
+                    file,
+                    &mut http_client,
+                    &mut variables,
+                    &options,
+                    &logger,
+                )?;
+                if options.repeat.is_some() {
+                    dry_run_entry.iteration = Some(iteration + 1);
+                }
+                Some(dry_run_entry)
+            };
+            Ok((dry_run_entry, options))
+        });
+        match result {
+            Ok((dry_run_entry, options)) => {
+                entries.extend(dry_run_entry);
+                (entry_index, iteration) = next_iteration(entry_index, iteration, &options);
+            }
+            Err(error) => {
+                let content = file.map_or(content, |f| f.content.as_str());
+                logger.error_rich(content, &error);
+                return Ok(DryRunResult {
+                    entries,
+                    success: false,
+                    secrets,
+                });
+            }
+        }
+    }
+
+    Ok(DryRunResult {
+        entries,
+        success: true,
+        secrets,
+    })
+}
+
+/// Serves a Hurl `content` as a mock server listening on `port`, until the process is stopped.
+///
+/// Incoming requests are matched against the requests of the entries, and answered with the
+/// response of the first matching entry. Templates are rendered with `variables`. Errors are
+/// logged.
+pub fn serve(
+    content: &str,
+    runner_options: &RunnerOptions,
+    variables: &HashMap<String, Value>,
+    logger_options: &LoggerOptions,
+    port: u16,
+) -> Result<(), String> {
+    let logger = Logger::from(logger_options);
+    let hurl_entries = parse_entries(content, runner_options, logger_options)?;
+    let entries = hurl_entries.iter().map(|(entry, _)| entry).collect();
+    let server = MockServer::new(entries, variables, &runner_options.context_dir);
+    if let Err(e) = server.listen(port, &logger) {
+        logger.error(&e);
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Returns the index of the next entry to run and its iteration, after the `iteration` of the entry
+/// at `entry_index` run with `options`.
+fn next_iteration(entry_index: usize, iteration: usize, options: &RunnerOptions) -> (usize, usize) {
+    match options.repeat {
+        Some(count) if iteration + 1 < count => (entry_index, iteration + 1),
+        _ => (entry_index + 1, 0),
+    }
+}
+
+/// Returns the violations of the `calls` of an `entry` against an `openapi` document, as errors
+/// located on the line of the entry request.
+fn openapi_errors(openapi: &OpenApi, entry: &Entry, calls: &[Call]) -> Vec<runner::Error> {
+    let line = entry.request.url.source_info.start.line;
+    let source_info = SourceInfo::new(Pos::new(line, 0), Pos::new(line, 0));
+    calls
+        .iter()
+        .flat_map(|call| openapi.validate(call))
+        .map(|violation| {
+            let inner = RunnerError::OpenApiViolation(violation);
+            runner::Error::new(source_info, inner, true)
+        })
+        .collect()
+}
+
+/// Returns the results of the entries with undefined variables `errors`, each error coming with
+/// the index of its entry.
+fn undefined_variables_results(
+    errors: Vec<(usize, runner::Error)>,
+    hurl_entries: &[ExpandedEntry],
+) -> Vec<EntryResult> {
+    let mut entries: Vec<EntryResult> = vec![];
+    for (entry_index, error) in errors {
+        match entries.last_mut() {
+            Some(entry_result) if entry_result.entry_index == entry_index => {
+                entry_result.errors.push(error);
+            }
+            _ => {
+                let (entry, file) = &hurl_entries[entry_index - 1];
+                let entry_result = error_result(entry_index, entry, file.as_ref(), vec![error]);
+                entries.push(entry_result);
+            }
+        }
+    }
+    entries
+}
+
+/// Returns the result of the entry at `entry_index`, that has not been run because of `errors`.
+fn error_result(
+    entry_index: usize,
+    entry: &Entry,
+    file: Option<&Arc<IncludedFile>>,
+    errors: Vec<runner::Error>,
+) -> EntryResult {
+    EntryResult {
+        entry_index,
+        iteration: None,
+        name: entry.name(),
+        tags: entry.tags(),
+        file: file.cloned(),
+        source_info: entry.source_info(),
+        calls: vec![],
+        captures: vec![],
+        asserts: vec![],
+        errors,
+        curl_cmd: String::new(),
+        retry: None,
+        time_in_ms: 0,
+        compressed: false,
+    }
+}
+
 /// Returns `true` if all the entries ar successful, `false` otherwise.
 ///
-/// For a given list of entry, only the last one on the same index is checked.
+/// For a given list of entry, only the last one on the same index (and iteration) is checked.
 /// For instance:
 /// entry a:1, entry b:1, entry c:2, entry d:3, entry e:3
 /// Only the entry b, c and e are checked for the success state.
@@ -288,7 +580,9 @@ fn is_success(entries: &[EntryResult]) -> bool {
         match next_entries.next() {
             None => return entry.errors.is_empty(),
             Some(next) => {
-                if next.entry_index != entry.entry_index && !entry.errors.is_empty() {
+                let is_next_run =
+                    next.entry_index != entry.entry_index || next.iteration != entry.iteration;
+                if is_next_run && !entry.errors.is_empty() {
                     return false;
                 }
             }
@@ -388,6 +682,10 @@ fn get_non_default_options(options: &RunnerOptions) -> Vec<(&'static str, String
         non_default_options.push(("insecure", options.insecure.to_string()));
     }
 
+    if options.json_match != default_options.json_match {
+        non_default_options.push(("json match", options.json_match.to_string()));
+    }
+
     if options.max_redirect != default_options.max_redirect {
         if let Some(n) = options.max_redirect {
             non_default_options.push(("max redirect", n.to_string()));
@@ -404,12 +702,176 @@ fn get_non_default_options(options: &RunnerOptions) -> Vec<(&'static str, String
         non_default_options.push(("retry", options.retry.to_string()));
     }
 
+    if options.retry_after != default_options.retry_after {
+        non_default_options.push(("retry after", options.retry_after.to_string()));
+    }
+
+    if options.retry_backoff != default_options.retry_backoff {
+        non_default_options.push(("retry backoff", options.retry_backoff.to_string()));
+    }
+
+    if options.retry_jitter != default_options.retry_jitter {
+        non_default_options.push(("retry jitter", options.retry_jitter.to_string()));
+    }
+
+    if let Some(retry_max_interval) = options.retry_max_interval {
+        let retry_max_interval = retry_max_interval.as_millis() as u64;
+        non_default_options.push(("retry max interval", format!("{retry_max_interval}ms")));
+    }
+
+    if let Some(retry_max_time) = options.retry_max_time {
+        let retry_max_time = retry_max_time.as_millis() as u64;
+        non_default_options.push(("retry max time", format!("{retry_max_time}ms")));
+    }
+
+    if !options.tags.is_empty() {
+        non_default_options.push(("tags", options.tags.join(", ")));
+    }
+
+    if !options.exclude_tags.is_empty() {
+        non_default_options.push(("exclude tags", options.exclude_tags.join(", ")));
+    }
+
     non_default_options
 }
 
-/// Logs various debug information at the start of `hurl_file` run.
+/// Parses a Hurl `content` and returns its entries, the entries of the included files being
+/// spliced in. Errors are logged.
+fn parse_entries(
+    content: &str,
+    runner_options: &RunnerOptions,
+    logger_options: &LoggerOptions,
+) -> Result<Vec<ExpandedEntry>, String> {
+    let hurl_file = match parser::parse_hurl_file(content) {
+        Ok(h) => h,
+        Err(e) => {
+            let logger = Logger::from(logger_options);
+            logger.error_rich(content, &e);
+            return Err(e.description());
+        }
+    };
+
+    let entries = include::expand_entries(
+        &hurl_file,
+        &logger_options.filename,
+        &runner_options.context_dir,
+    );
+    match entries {
+        Ok(e) => Ok(e),
+        Err(IncludeError::Runner(e, file)) => {
+            log_include_error(e.as_ref(), file.as_ref(), content, logger_options);
+            Err(e.description())
+        }
+        Err(IncludeError::Parser(e, file)) => {
+            log_include_error(e.as_ref(), Some(&file), content, logger_options);
+            Err(e.description())
+        }
+    }
+}
+
+/// Checks that the variables used by the first `n` entries of `hurl_entries` selected to run are
+/// defined, and returns the results of the entries with undefined variables. Errors are logged.
+fn check_variables(
+    hurl_entries: &[ExpandedEntry],
+    n: usize,
+    content: &str,
+    runner_options: &RunnerOptions,
+    variables: &HashMap<String, Value>,
+    logger: &Logger,
+) -> Vec<EntryResult> {
+    let first_index = first_entry_index(hurl_entries, runner_options);
+    let selected = hurl_entries
+        .iter()
+        .take(n)
+        .enumerate()
+        .map(|(index, (entry, _))| (index + 1, entry))
+        .filter(|(index, entry)| is_entry_selected(entry, *index, first_index, runner_options))
+        .collect::<Vec<_>>();
+    let errors = undefined::check(&selected, variables);
+    let entries = undefined_variables_results(errors, hurl_entries);
+    for entry_result in entries.iter() {
+        let content = entry_result
+            .file
+            .as_ref()
+            .map_or(content, |f| f.content.as_str());
+        log_errors(entry_result, content, false, logger);
+    }
+    entries
+}
+
+/// Returns the index (starting at 1) of the first entry to run among `entries`, according to the
+/// `--from-entry` option of `runner_options`.
+///
+/// An index of `--from-entry` refers to an entry of the main Hurl file: the entries of the included
+/// files are spliced before the entry including them, and are run with it. When no entry has the
+/// requested name, the returned index is past the last entry.
+fn first_entry_index(entries: &[ExpandedEntry], runner_options: &RunnerOptions) -> usize {
+    match &runner_options.from_entry {
+        None => 1,
+        Some(FromEntry::Index(index)) => match main_entry_position(entries, index - 1) {
+            Some(position) => position + 1,
+            None if *index == 1 => 1,
+            None => entries.len() + 1,
+        },
+        Some(FromEntry::Name(name)) => entries
+            .iter()
+            .position(|(e, _)| e.name().as_ref() == Some(name))
+            .map_or(entries.len() + 1, |position| position + 1),
+    }
+}
+
+/// Returns the index (starting at 1) of the last entry to run among `entries`, according to the
+/// `--to-entry` option of `runner_options`.
+///
+/// As for [`first_entry_index`], an index of `--to-entry` refers to an entry of the main Hurl file.
+fn last_entry_index(entries: &[ExpandedEntry], runner_options: &RunnerOptions) -> usize {
+    match runner_options.to_entry {
+        None => entries.len(),
+        Some(index) => main_entry_position(entries, index).unwrap_or(entries.len()),
+    }
+}
+
+/// Returns the position (starting at 1) in `entries` of the entry at `index` (starting at 1) in
+/// the main Hurl file, `None` if there is no such entry.
+fn main_entry_position(entries: &[ExpandedEntry], index: usize) -> Option<usize> {
+    if index == 0 {
+        return None;
+    }
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, file))| file.is_none())
+        .nth(index - 1)
+        .map(|(position, _)| position + 1)
+}
+
+/// Returns `true` if the `entry` at `entry_index` (starting at 1) has to be run, according to the
+/// first entry index `first_index` and the entries selection of `runner_options` (entry name and
+/// tags).
+fn is_entry_selected(
+    entry: &Entry,
+    entry_index: usize,
+    first_index: usize,
+    runner_options: &RunnerOptions,
+) -> bool {
+    if entry_index < first_index {
+        return false;
+    }
+    if let Some(entry_name) = &runner_options.entry_name {
+        if entry.name().as_ref() != Some(entry_name) {
+            return false;
+        }
+    }
+    let tags = entry.tags();
+    if !runner_options.tags.is_empty() && !tags.iter().any(|t| runner_options.tags.contains(t)) {
+        return false;
+    }
+    !tags.iter().any(|t| runner_options.exclude_tags.contains(t))
+}
+
+/// Logs various debug information at the start of the run of `entries`.
 fn log_run_info(
-    hurl_file: &HurlFile,
+    entries: &[ExpandedEntry],
     runner_options: &RunnerOptions,
     variables: &HashMap<String, Value>,
     logger: &Logger,
@@ -431,8 +893,24 @@ fn log_run_info(
         }
     }
     if let Some(to_entry) = runner_options.to_entry {
-        logger
-            .debug(format!("Executing {}/{} entries", to_entry, hurl_file.entries.len()).as_str());
+        let count = entries.iter().filter(|(_, file)| file.is_none()).count();
+        logger.debug(format!("Executing {to_entry}/{count} entries").as_str());
+    }
+    if let Some(from_entry) = &runner_options.from_entry {
+        logger.debug(format!("Executing entries from {from_entry}").as_str());
+        if let FromEntry::Name(name) = from_entry {
+            if entries.iter().all(|(e, _)| e.name().as_ref() != Some(name)) {
+                logger.warning(format!("No entry named <{name}>").as_str());
+            }
+        }
+    }
+    if let Some(entry_name) = &runner_options.entry_name {
+        if entries
+            .iter()
+            .all(|(e, _)| e.name().as_ref() != Some(entry_name))
+        {
+            logger.warning(format!("No entry named <{entry_name}>").as_str());
+        }
     }
 }
 
@@ -461,25 +939,54 @@ fn log_errors(entry_result: &EntryResult, content: &str, retry: bool, logger: &L
         .for_each(|e| logger.error_rich(content, e));
 }
 
-/// Creates a new logger for this entry.
+/// Creates a new logger for this entry, included from `file` (`None` for the main file).
 /// Verbosity can be overridden at entry level with an Options section so each
 /// entry has its own logger.
 fn get_entry_logger(
     entry: &Entry,
+    file: Option<&Arc<IncludedFile>>,
     logger_options: &LoggerOptions,
     variables: &HashMap<String, Value>,
 ) -> Result<Logger, runner::Error> {
     let entry_verbosity =
         options::get_entry_verbosity(entry, &logger_options.verbosity, variables)?;
-    let entry_logger_options = LoggerOptionsBuilder::new()
+    let filename = file.map_or(logger_options.filename.as_str(), |f| f.filename.as_str());
+    Ok(get_file_logger(filename, logger_options, entry_verbosity))
+}
+
+/// Creates a new logger for the file `filename`, with a given `verbosity`.
+fn get_file_logger(
+    filename: &str,
+    logger_options: &LoggerOptions,
+    verbosity: Option<Verbosity>,
+) -> Logger {
+    let logger_options = LoggerOptionsBuilder::new()
         .color(logger_options.color)
-        .filename(&logger_options.filename)
+        .filename(filename)
         .error_format(logger_options.error_format)
-        .progress_bar(entry_verbosity.is_none() && logger_options.progress_bar)
-        .verbosity(entry_verbosity)
+        .progress_bar(verbosity.is_none() && logger_options.progress_bar)
+        .secrets(&logger_options.secrets)
+        .stderr(&logger_options.stderr)
+        .verbosity(verbosity)
         .test(logger_options.test)
         .build();
-    Ok(Logger::from(&entry_logger_options))
+    Logger::from(&logger_options)
+}
+
+/// Logs an `error` raised while processing `include` options, located in `file` (`None` for
+/// the main file of source `content`).
+fn log_include_error(
+    error: &dyn Error,
+    file: Option<&Arc<IncludedFile>>,
+    content: &str,
+    logger_options: &LoggerOptions,
+) {
+    let logger = match file {
+        Some(file) => get_file_logger(&file.filename, logger_options, logger_options.verbosity),
+        None => Logger::from(logger_options),
+    };
+    let content = file.map_or(content, |f| f.content.as_str());
+    logger.error_rich(content, error);
 }
 
 #[cfg(test)]
@@ -507,4 +1014,136 @@ mod test {
         assert_eq!(first_non_default.0, "delay");
         assert_eq!(first_non_default.1, "500ms");
     }
+
+    #[test]
+    fn next_iteration_repeats_entry() {
+        let options = RunnerOptions::default();
+        assert_eq!(next_iteration(2, 0, &options), (3, 0));
+
+        let options = RunnerOptions {
+            repeat: Some(3),
+            ..RunnerOptions::default()
+        };
+        assert_eq!(next_iteration(2, 0, &options), (2, 1));
+        assert_eq!(next_iteration(2, 1, &options), (2, 2));
+        assert_eq!(next_iteration(2, 2, &options), (3, 0));
+    }
+
+    #[test]
+    fn select_entries() {
+        let content = r#"GET http://localhost:8000/a
+[Options]
+name: login
+tag: smoke
+
+GET http://localhost:8000/b
+[Options]
+name: search
+tag: smoke
+tag: slow
+
+GET http://localhost:8000/c
+"#;
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let entries = hurl_file
+            .entries
+            .into_iter()
+            .map(|e| (e, None))
+            .collect::<Vec<_>>();
+        let selected = |options: &RunnerOptions| {
+            let first_index = first_entry_index(&entries, options);
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(i, (e, _))| is_entry_selected(e, i + 1, first_index, options))
+                .map(|(i, _)| i + 1)
+                .collect::<Vec<_>>()
+        };
+
+        let options = RunnerOptions::default();
+        assert_eq!(selected(&options), vec![1, 2, 3]);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Index(2)))
+            .build();
+        assert_eq!(selected(&options), vec![2, 3]);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Name("search".to_string())))
+            .build();
+        assert_eq!(selected(&options), vec![2, 3]);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Name("unknown".to_string())))
+            .build();
+        assert!(selected(&options).is_empty());
+
+        let options = RunnerOptionsBuilder::new()
+            .entry_name(Some("login".to_string()))
+            .build();
+        assert_eq!(selected(&options), vec![1]);
+
+        let options = RunnerOptionsBuilder::new()
+            .tags(&["smoke".to_string()])
+            .build();
+        assert_eq!(selected(&options), vec![1, 2]);
+
+        let options = RunnerOptionsBuilder::new()
+            .exclude_tags(&["slow".to_string()])
+            .build();
+        assert_eq!(selected(&options), vec![1, 3]);
+
+        let options = RunnerOptionsBuilder::new()
+            .tags(&["smoke".to_string()])
+            .exclude_tags(&["slow".to_string()])
+            .build();
+        assert_eq!(selected(&options), vec![1]);
+    }
+    #[test]
+    fn entries_range_with_included_entries() {
+        // The main file has 3 entries, the second one including 2 entries.
+        let content = "GET http://localhost:8000/a\nGET http://localhost:8000/b\n";
+        let included = Arc::new(IncludedFile {
+            filename: "included.hurl".to_string(),
+            content: content.to_string(),
+        });
+        let included_entries = parser::parse_hurl_file(content).unwrap().entries;
+        let main_entries = parser::parse_hurl_file(
+            "GET http://localhost:8000/1\nGET http://localhost:8000/2\nGET http://localhost:8000/3\n",
+        )
+        .unwrap()
+        .entries;
+        let entries = vec![
+            (main_entries[0].clone(), None),
+            (included_entries[0].clone(), Some(included.clone())),
+            (included_entries[1].clone(), Some(included)),
+            (main_entries[1].clone(), None),
+            (main_entries[2].clone(), None),
+        ];
+
+        let options = RunnerOptions::default();
+        assert_eq!(first_entry_index(&entries, &options), 1);
+        assert_eq!(last_entry_index(&entries, &options), 5);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Index(2)))
+            .to_entry(Some(2))
+            .build();
+        assert_eq!(first_entry_index(&entries, &options), 2);
+        assert_eq!(last_entry_index(&entries, &options), 4);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Index(3)))
+            .to_entry(Some(1))
+            .build();
+        assert_eq!(first_entry_index(&entries, &options), 5);
+        assert_eq!(last_entry_index(&entries, &options), 1);
+
+        let options = RunnerOptionsBuilder::new()
+            .from_entry(Some(FromEntry::Index(4)))
+            .to_entry(Some(4))
+            .build();
+        assert_eq!(first_entry_index(&entries, &options), 6);
+        assert_eq!(last_entry_index(&entries, &options), 5);
+    }
 }
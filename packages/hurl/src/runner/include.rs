@@ -0,0 +1,223 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use hurl_core::ast::{Entry, Filename, HurlFile};
+use hurl_core::parser;
+
+use crate::runner::error::{Error, RunnerError};
+use crate::util::path::ContextDir;
+
+/// A Hurl file included in another Hurl file with an `include` option.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncludedFile {
+    /// Path of the included file (relative to the current directory or absolute).
+    pub filename: String,
+    /// Source of the included file.
+    pub content: String,
+}
+
+/// An entry to run, with the file it has been included from (`None` for the entries of the main
+/// Hurl file).
+pub type ExpandedEntry = (Entry, Option<Arc<IncludedFile>>);
+
+/// An error raised while processing `include` options.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncludeError {
+    /// An `include` option can't be processed (unauthorized access, unreadable file, include
+    /// cycle). The error is located in `file`, `None` being the main Hurl file.
+    Runner(Box<Error>, Option<Arc<IncludedFile>>),
+    /// An included file can't be parsed.
+    Parser(Box<parser::Error>, Arc<IncludedFile>),
+}
+
+/// Returns the entries to run for `hurl_file`, where the entries of the included files are
+/// spliced before the entry including them.
+///
+/// Each entry comes with the file it has been included from, `None` for the entries of `hurl_file`.
+/// `filename` is the path of `hurl_file`, and is used to detect include cycles.
+pub fn expand_entries(
+    hurl_file: &HurlFile,
+    filename: &str,
+    context_dir: &ContextDir,
+) -> Result<Vec<ExpandedEntry>, IncludeError> {
+    let mut entries = vec![];
+    let mut stack = vec![canonical_path(Path::new(filename))];
+    add_entries(hurl_file, None, &mut stack, context_dir, &mut entries)?;
+    Ok(entries)
+}
+
+/// Adds the entries of `hurl_file` (included from `file`) to `entries`, processing recursively
+/// the `include` options.
+///
+/// `stack` is the list of the files being currently included.
+fn add_entries(
+    hurl_file: &HurlFile,
+    file: Option<&Arc<IncludedFile>>,
+    stack: &mut Vec<PathBuf>,
+    context_dir: &ContextDir,
+    entries: &mut Vec<ExpandedEntry>,
+) -> Result<(), IncludeError> {
+    for entry in hurl_file.entries.iter() {
+        for filename in entry.includes() {
+            let (path, included) = match read_file(&filename, stack, context_dir) {
+                Ok(f) => f,
+                Err(e) => return Err(IncludeError::Runner(Box::new(e), file.cloned())),
+            };
+            let included_hurl_file = match parser::parse_hurl_file(&included.content) {
+                Ok(h) => h,
+                Err(e) => return Err(IncludeError::Parser(Box::new(e), Arc::new(included))),
+            };
+            // The included file is shared by all its entries.
+            let included = Arc::new(included);
+            stack.push(path);
+            add_entries(
+                &included_hurl_file,
+                Some(&included),
+                stack,
+                context_dir,
+                entries,
+            )?;
+            stack.pop();
+        }
+        entries.push((entry.clone(), file.cloned()));
+    }
+    Ok(())
+}
+
+/// Reads the included file `filename`, and returns its canonical path along its content.
+fn read_file(
+    filename: &Filename,
+    stack: &[PathBuf],
+    context_dir: &ContextDir,
+) -> Result<(PathBuf, IncludedFile), Error> {
+    // As for request bodies, included files must be children of the context directory.
+    let file = filename.value.clone();
+    if !context_dir.is_access_allowed(&file) {
+        let inner = RunnerError::UnauthorizedFileAccess {
+            path: PathBuf::from(file),
+        };
+        return Err(Error::new(filename.source_info, inner, false));
+    }
+    let resolved_file = context_dir.get_path(&file);
+    let path = canonical_path(&resolved_file);
+    if stack.contains(&path) {
+        let inner = RunnerError::IncludeCycle { file };
+        return Err(Error::new(filename.source_info, inner, false));
+    }
+    let content = match std::fs::read(&resolved_file) {
+        Ok(mut bytes) => {
+            if bytes.starts_with(&[0xefu8, 0xbb, 0xbf]) {
+                bytes.drain(0..3);
+            }
+            String::from_utf8(bytes).ok()
+        }
+        Err(_) => None,
+    };
+    let content = match content {
+        Some(c) => c,
+        None => {
+            let inner = RunnerError::FileReadAccess { file };
+            return Err(Error::new(filename.source_info, inner, false));
+        }
+    };
+    let included = IncludedFile {
+        filename: resolved_file.display().to_string(),
+        content,
+    };
+    Ok((path, included))
+}
+
+/// Returns the canonical form of `path`, or `path` itself if it can't be canonicalized.
+fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expand_included_entries() {
+        let dir = std::env::temp_dir().join(format!("hurl_include_test_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("a.hurl"), "GET http://localhost:8000/a\n").unwrap();
+        std::fs::write(
+            dir.join("b.hurl"),
+            "GET http://localhost:8000/b\n[Options]\ninclude: a.hurl\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("cycle.hurl"),
+            "GET http://localhost:8000/cycle\n[Options]\ninclude: cycle.hurl\n",
+        )
+        .unwrap();
+        let context_dir = ContextDir::new(&dir, &dir);
+
+        let content = "GET http://localhost:8000/main\n[Options]\ninclude: b.hurl\n";
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let entries = expand_entries(&hurl_file, "main.hurl", &context_dir).unwrap();
+        let urls = entries
+            .iter()
+            .map(|(e, _)| e.request.url.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            urls,
+            vec![
+                "http://localhost:8000/a",
+                "http://localhost:8000/b",
+                "http://localhost:8000/main"
+            ]
+        );
+        let files = entries
+            .iter()
+            .map(|(_, f)| f.as_ref().map(|f| f.filename.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                Some(dir.join("a.hurl").display().to_string()),
+                Some(dir.join("b.hurl").display().to_string()),
+                None
+            ]
+        );
+
+        let content = "GET http://localhost:8000/main\n[Options]\ninclude: cycle.hurl\n";
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let error = expand_entries(&hurl_file, "main.hurl", &context_dir).unwrap_err();
+        let (error, file) = match error {
+            IncludeError::Runner(error, Some(file)) => (error, file),
+            _ => panic!("expecting an include cycle error"),
+        };
+        assert_eq!(
+            error.inner,
+            RunnerError::IncludeCycle {
+                file: "cycle.hurl".to_string()
+            }
+        );
+        assert_eq!(file.filename, dir.join("cycle.hurl").display().to_string());
+
+        let content = "GET http://localhost:8000/main\n[Options]\ninclude: unknown.hurl\n";
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let error = expand_entries(&hurl_file, "main.hurl", &context_dir).unwrap_err();
+        assert!(matches!(error, IncludeError::Runner(_, None)));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -16,12 +16,14 @@
  *
  */
 use std::collections::HashMap;
+use std::str::FromStr;
 
-use hurl_core::ast::{JsonListElement, JsonObjectElement, JsonValue, Template, TemplateElement};
-use hurl_core::parser::{parse_json_boolean, parse_json_null, parse_json_number, Reader};
+use hurl_core::ast::{
+    Expr, JsonListElement, JsonObjectElement, JsonValue, Template, TemplateElement,
+};
 
 use crate::runner::error::{Error, RunnerError};
-use crate::runner::template::render_expression;
+use crate::runner::template::{eval_template, render_expression};
 use crate::runner::value::Value;
 
 /// Evaluates a JSON value to a string given a set of `variables`.
@@ -66,24 +68,68 @@ pub fn eval_json_value(
         }
         JsonValue::Expression(exp) => {
             let s = render_expression(exp, variables)?;
+            parse_json_scalar(&s, exp)?;
+            Ok(s)
+        }
+    }
+}
 
-            // The String can only be null, a bool, a number
-            // It will be easier when your variables value have a type
-            let mut reader = Reader::new(s.as_str());
-            let start = reader.state;
-            if parse_json_number(&mut reader).is_ok() {
-                return Ok(s);
-            }
-            reader.state = start;
-            if parse_json_boolean(&mut reader).is_ok() {
-                return Ok(s);
+/// Evaluates a JSON value to a `serde_json::Value` given a set of `variables`.
+pub fn eval_json(
+    json_value: &JsonValue,
+    variables: &HashMap<String, Value>,
+) -> Result<serde_json::Value, Error> {
+    match json_value {
+        JsonValue::Null => Ok(serde_json::Value::Null),
+        JsonValue::Boolean(v) => Ok(serde_json::Value::Bool(*v)),
+        // Numbers have been checked to be valid JSON numbers by the parser.
+        JsonValue::Number(s) => Ok(serde_json::Value::Number(
+            serde_json::Number::from_str(s).unwrap(),
+        )),
+        JsonValue::String(template) => {
+            let s = eval_template(template, variables)?;
+            Ok(serde_json::Value::String(s))
+        }
+        JsonValue::List { elements, .. } => {
+            let mut values = vec![];
+            for element in elements {
+                values.push(eval_json(&element.value, variables)?);
             }
-            reader.state = start;
-            if parse_json_null(&mut reader).is_ok() {
-                return Ok(s);
+            Ok(serde_json::Value::Array(values))
+        }
+        JsonValue::Object { elements, .. } => {
+            let mut map = serde_json::Map::new();
+            for element in elements {
+                let name = eval_template(&element.name, variables)?;
+                let value = eval_json(&element.value, variables)?;
+                map.insert(name, value);
             }
-            let inner = RunnerError::InvalidJson { value: s };
-            Err(Error::new(exp.variable.source_info, inner, false))
+            Ok(serde_json::Value::Object(map))
+        }
+        JsonValue::Expression(exp) => eval_json_expr(exp, variables),
+    }
+}
+
+/// Evaluates the expression `expr` of a JSON value given a set of `variables`.
+///
+/// The expression must be rendered as a JSON null, boolean or number.
+pub fn eval_json_expr(
+    expr: &Expr,
+    variables: &HashMap<String, Value>,
+) -> Result<serde_json::Value, Error> {
+    let s = render_expression(expr, variables)?;
+    parse_json_scalar(&s, expr)
+}
+
+/// Parses the rendered string `s` of the expression `expr` as a JSON null, boolean or number.
+fn parse_json_scalar(s: &str, expr: &Expr) -> Result<serde_json::Value, Error> {
+    match serde_json::from_str::<serde_json::Value>(s) {
+        Ok(value) if value.is_null() || value.is_boolean() || value.is_number() => Ok(value),
+        _ => {
+            let inner = RunnerError::InvalidJson {
+                value: s.to_string(),
+            };
+            Err(Error::new(expr.source_info(), inner, false))
         }
     }
 }
@@ -195,10 +241,12 @@ mod tests {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 15), Pos::new(1, 15)),
                     },
-                    variable: Variable {
+                    kind: ExprKind::Variable(Variable {
                         name: "name".to_string(),
                         source_info: SourceInfo::new(Pos::new(1, 15), Pos::new(1, 19)),
-                    },
+                    }),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 19), Pos::new(1, 19)),
@@ -281,6 +329,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_expression() {
+        // {{v}}
+        let value = JsonValue::Expression(Expr {
+            space0: Whitespace {
+                value: String::new(),
+                source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3)),
+            },
+            kind: ExprKind::Variable(Variable {
+                name: "v".to_string(),
+                source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 4)),
+            }),
+            default: None,
+            filters: vec![],
+            space1: Whitespace {
+                value: String::new(),
+                source_info: SourceInfo::new(Pos::new(1, 4), Pos::new(1, 4)),
+            },
+        });
+        let mut variables = HashMap::new();
+        variables.insert("v".to_string(), Value::String("12".to_string()));
+        assert_eq!(eval_json_value(&value, &variables, false).unwrap(), "12");
+        assert_eq!(
+            eval_json(&value, &variables).unwrap(),
+            serde_json::json!(12)
+        );
+
+        variables.insert("v".to_string(), Value::String("12abc".to_string()));
+        let error = eval_json_value(&value, &variables, false).unwrap_err();
+        assert_eq!(
+            error.source_info,
+            SourceInfo::new(Pos::new(1, 3), Pos::new(1, 4))
+        );
+        assert_eq!(
+            error.inner,
+            RunnerError::InvalidJson {
+                value: "12abc".to_string()
+            }
+        );
+        assert_eq!(eval_json(&value, &variables).unwrap_err(), error);
+    }
+
+    #[test]
+    fn test_eval_json() {
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), Value::String("\u{1}Bob".to_string()));
+        assert_eq!(
+            eval_json(&json_hello_world_value(), &variables).unwrap(),
+            serde_json::json!("Hello \u{1}Bob!")
+        );
+        assert_eq!(
+            eval_json(&json_person_value(), &variables).unwrap(),
+            serde_json::json!({"firstName": "John"})
+        );
+    }
+
     #[test]
     fn test_list_value() {
         let mut variables = HashMap::new();
@@ -437,10 +541,12 @@ mod tests {
                         },
                         TemplateElement::Expression(Expr {
                             space0: whitespace(),
-                            variable: Variable {
+                            kind: ExprKind::Variable(Variable {
                                 name: "quote".to_string(),
                                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-                            },
+                            }),
+                            default: None,
+                            filters: vec![],
                             space1: whitespace(),
                         }),
                     ],
@@ -0,0 +1,553 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Structural matching of actual JSON values against expected JSON values.
+//!
+//! By default, values must be equal: objects have the same properties, and arrays the same items
+//! in the same order. In JSON match mode (`--json-match` or the `json-match` option):
+//! - extra properties of actual objects are allowed,
+//! - arrays are compared ignoring the order of their items,
+//! - placeholders like `{{*}}`, `{{uuid}}` or `{{number}}` match any value of a kind.
+//!
+//! Each mismatch is reported with the JSON pointer of the actual value that differs.
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use hurl_core::ast::{Expr, ExprKind, JsonValue};
+
+use crate::runner::error::Error;
+use crate::runner::json::eval_json_expr;
+use crate::runner::template::eval_template;
+use crate::runner::value::Value;
+
+/// An expected JSON value, where placeholders match any value of a kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    /// A null, boolean, number or string value
+    Value(serde_json::Value),
+    List(Vec<Pattern>),
+    Object(Vec<(String, Pattern)>),
+    Placeholder(Placeholder),
+}
+
+/// A placeholder of a JSON match mode pattern.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Placeholder {
+    Any,
+    Array,
+    Boolean,
+    Integer,
+    Number,
+    Object,
+    String,
+    Uuid,
+}
+
+/// A difference between an expected pattern and an actual JSON value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// JSON pointer of the actual value, as an URI fragment (`#/users/0/id`)
+    pub pointer: String,
+    pub message: String,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.pointer, self.message)
+    }
+}
+
+impl Placeholder {
+    /// Returns the placeholder of an expression `expr`, `None` if it's not a placeholder.
+    ///
+    /// A placeholder is a variable without default value or filters, whose name is a kind of
+    /// value, like `{{uuid}}`. A defined variable, checked with `is_defined`, is never a
+    /// placeholder: a variable named `uuid` is still evaluated to its value.
+    pub fn from_expr(expr: &Expr, is_defined: impl Fn(&str) -> bool) -> Option<Placeholder> {
+        if expr.default.is_some() || !expr.filters.is_empty() {
+            return None;
+        }
+        let name = match &expr.kind {
+            ExprKind::Variable(variable) => variable.name.as_str(),
+            ExprKind::Function(_) => return None,
+        };
+        if is_defined(name) {
+            return None;
+        }
+        match name {
+            "*" => Some(Placeholder::Any),
+            "array" => Some(Placeholder::Array),
+            "boolean" => Some(Placeholder::Boolean),
+            "integer" => Some(Placeholder::Integer),
+            "number" => Some(Placeholder::Number),
+            "object" => Some(Placeholder::Object),
+            "string" => Some(Placeholder::String),
+            "uuid" => Some(Placeholder::Uuid),
+            _ => None,
+        }
+    }
+
+    /// Returns true if an `actual` JSON value is of the kind of this placeholder.
+    fn matches(&self, actual: &serde_json::Value) -> bool {
+        match self {
+            Placeholder::Any => true,
+            Placeholder::Array => actual.is_array(),
+            Placeholder::Boolean => actual.is_boolean(),
+            Placeholder::Integer => actual.is_i64() || actual.is_u64(),
+            Placeholder::Number => actual.is_number(),
+            Placeholder::Object => actual.is_object(),
+            Placeholder::String => actual.is_string(),
+            Placeholder::Uuid => actual.as_str().is_some_and(is_uuid),
+        }
+    }
+}
+
+impl fmt::Display for Placeholder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Placeholder::Any => "*",
+            Placeholder::Array => "array",
+            Placeholder::Boolean => "boolean",
+            Placeholder::Integer => "integer",
+            Placeholder::Number => "number",
+            Placeholder::Object => "object",
+            Placeholder::String => "string",
+            Placeholder::Uuid => "uuid",
+        };
+        write!(f, "{{{{{name}}}}}")
+    }
+}
+
+impl Pattern {
+    /// Returns the pattern of an actual JSON `value`, without placeholders.
+    fn from_json(value: &serde_json::Value) -> Pattern {
+        match value {
+            serde_json::Value::Array(values) => {
+                Pattern::List(values.iter().map(Pattern::from_json).collect())
+            }
+            serde_json::Value::Object(map) => Pattern::Object(
+                map.iter()
+                    .map(|(key, value)| (key.clone(), Pattern::from_json(value)))
+                    .collect(),
+            ),
+            value => Pattern::Value(value.clone()),
+        }
+    }
+}
+
+/// Displays a pattern as compact JSON, placeholders being displayed as is (`{{uuid}}`).
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Pattern::Value(value) => write!(f, "{value}"),
+            Pattern::List(patterns) => {
+                let patterns = patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+                write!(f, "[{}]", patterns.join(","))
+            }
+            Pattern::Object(properties) => {
+                let properties = properties
+                    .iter()
+                    .map(|(key, p)| format!("{}:{p}", serde_json::Value::String(key.clone())))
+                    .collect::<Vec<_>>();
+                write!(f, "{{{}}}", properties.join(","))
+            }
+            Pattern::Placeholder(placeholder) => write!(f, "{placeholder}"),
+        }
+    }
+}
+
+/// Evaluates the pattern of an expected JSON `value`, given a set of `variables`.
+///
+/// If `json_match` is true, placeholders expressions are evaluated to placeholders, and not to
+/// the value of their variable.
+pub fn eval_pattern(
+    value: &JsonValue,
+    variables: &HashMap<String, Value>,
+    json_match: bool,
+) -> Result<Pattern, Error> {
+    let pattern = match value {
+        JsonValue::Null => Pattern::Value(serde_json::Value::Null),
+        JsonValue::Boolean(value) => Pattern::Value(serde_json::Value::Bool(*value)),
+        // Numbers have been checked to be valid JSON numbers by the parser.
+        JsonValue::Number(value) => Pattern::Value(serde_json::Value::Number(
+            serde_json::Number::from_str(value).unwrap(),
+        )),
+        JsonValue::String(template) => {
+            let value = eval_template(template, variables)?;
+            Pattern::Value(serde_json::Value::String(value))
+        }
+        JsonValue::List { elements, .. } => {
+            let mut patterns = vec![];
+            for element in elements {
+                patterns.push(eval_pattern(&element.value, variables, json_match)?);
+            }
+            Pattern::List(patterns)
+        }
+        JsonValue::Object { elements, .. } => {
+            let mut properties = vec![];
+            for element in elements {
+                let key = eval_template(&element.name, variables)?;
+                let pattern = eval_pattern(&element.value, variables, json_match)?;
+                properties.push((key, pattern));
+            }
+            Pattern::Object(properties)
+        }
+        JsonValue::Expression(expr) => {
+            match Placeholder::from_expr(expr, |name| variables.contains_key(name)) {
+                Some(placeholder) if json_match => Pattern::Placeholder(placeholder),
+                _ => {
+                    let value = eval_json_expr(expr, variables)?;
+                    Pattern::from_json(&value)
+                }
+            }
+        }
+    };
+    Ok(pattern)
+}
+
+/// Returns the mismatches between an `expected` pattern and an `actual` JSON value.
+///
+/// If `json_match` is true, extra properties of actual objects are allowed and arrays are compared
+/// ignoring the order of their items.
+pub fn diff(expected: &Pattern, actual: &serde_json::Value, json_match: bool) -> Vec<Mismatch> {
+    let mut mismatches = vec![];
+    diff_value(expected, actual, "#", json_match, &mut mismatches);
+    mismatches
+}
+
+fn diff_value(
+    expected: &Pattern,
+    actual: &serde_json::Value,
+    pointer: &str,
+    json_match: bool,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    let mismatch = |message: String| Mismatch {
+        pointer: pointer.to_string(),
+        message,
+    };
+    match (expected, actual) {
+        (Pattern::Placeholder(placeholder), actual) => {
+            if !placeholder.matches(actual) {
+                mismatches.push(mismatch(format!(
+                    "expected <{placeholder}>, actual <{actual}>"
+                )));
+            }
+        }
+        (Pattern::Value(expected), actual) => {
+            if !values_equal(expected, actual) {
+                mismatches.push(mismatch(format!(
+                    "expected <{expected}>, actual <{actual}>"
+                )));
+            }
+        }
+        (Pattern::Object(properties), serde_json::Value::Object(map)) => {
+            for (key, pattern) in properties {
+                let pointer = format!("{pointer}/{}", escape_pointer(key));
+                match map.get(key) {
+                    Some(value) => diff_value(pattern, value, &pointer, json_match, mismatches),
+                    None => mismatches.push(Mismatch {
+                        pointer,
+                        message: "property is missing".to_string(),
+                    }),
+                }
+            }
+            if !json_match {
+                for key in map.keys() {
+                    if !properties.iter().any(|(k, _)| k == key) {
+                        mismatches.push(Mismatch {
+                            pointer: format!("{pointer}/{}", escape_pointer(key)),
+                            message: "property is not expected".to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        (Pattern::List(patterns), serde_json::Value::Array(values)) => {
+            if patterns.len() != values.len() {
+                mismatches.push(mismatch(format!(
+                    "expected {} items, actual {} items",
+                    patterns.len(),
+                    values.len()
+                )));
+            } else if json_match {
+                diff_unordered(patterns, values, pointer, mismatches);
+            } else {
+                for (index, (pattern, value)) in patterns.iter().zip(values).enumerate() {
+                    let pointer = format!("{pointer}/{index}");
+                    diff_value(pattern, value, &pointer, json_match, mismatches);
+                }
+            }
+        }
+        (expected, actual) => {
+            mismatches.push(mismatch(format!(
+                "expected <{expected}>, actual <{actual}>"
+            )));
+        }
+    }
+}
+
+/// Matches the `patterns` of an expected array against the `values` of an actual array of the
+/// same size, ignoring the order of the items.
+fn diff_unordered(
+    patterns: &[Pattern],
+    values: &[serde_json::Value],
+    pointer: &str,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    let matching = patterns
+        .iter()
+        .map(|p| values.iter().map(|v| diff(p, v, true).is_empty()).collect())
+        .collect::<Vec<Vec<bool>>>();
+    let assignment = max_matching(&matching, values.len());
+
+    let unmatched_patterns = (0..patterns.len())
+        .filter(|i| !assignment.contains(&Some(*i)))
+        .collect::<Vec<_>>();
+    let unmatched_values = (0..values.len())
+        .filter(|j| assignment[*j].is_none())
+        .collect::<Vec<_>>();
+    // With only one remaining item, we can report its precise differences.
+    if let ([i], [j]) = (unmatched_patterns.as_slice(), unmatched_values.as_slice()) {
+        let pointer = format!("{pointer}/{j}");
+        diff_value(&patterns[*i], &values[*j], &pointer, true, mismatches);
+        return;
+    }
+    for i in unmatched_patterns {
+        mismatches.push(Mismatch {
+            pointer: pointer.to_string(),
+            message: format!("no item matches expected item <{}>", patterns[i]),
+        });
+    }
+}
+
+/// Returns a maximum matching of a bipartite graph between expected items and `count` actual
+/// items, given the `matching` matrix of the items.
+///
+/// The matching is returned as the index of the expected item assigned to each actual item.
+fn max_matching(matching: &[Vec<bool>], count: usize) -> Vec<Option<usize>> {
+    let mut assignment = vec![None; count];
+    for i in 0..matching.len() {
+        let mut visited = vec![false; count];
+        augment(i, matching, &mut assignment, &mut visited);
+    }
+    assignment
+}
+
+/// Tries to assign the expected item `i` to an actual item, reassigning the previous assignments
+/// if needed (augmenting path).
+fn augment(
+    i: usize,
+    matching: &[Vec<bool>],
+    assignment: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for j in 0..assignment.len() {
+        if !matching[i][j] || visited[j] {
+            continue;
+        }
+        visited[j] = true;
+        let free = match assignment[j] {
+            None => true,
+            Some(other) => augment(other, matching, assignment, visited),
+        };
+        if free {
+            assignment[j] = Some(i);
+            return true;
+        }
+    }
+    false
+}
+
+/// Returns true if two JSON values are equal, numbers being compared by value (`1.0` equals `1`).
+fn values_equal(expected: &serde_json::Value, actual: &serde_json::Value) -> bool {
+    match (expected, actual) {
+        (serde_json::Value::Number(expected), serde_json::Value::Number(actual)) => {
+            expected == actual
+                || (expected.as_f64().is_some() && expected.as_f64() == actual.as_f64())
+        }
+        _ => expected == actual,
+    }
+}
+
+/// Returns true if `value` is a UUID (`8-4-4-4-12` hexadecimal digits).
+fn is_uuid(value: &str) -> bool {
+    value.len() == 36
+        && value.char_indices().all(|(index, c)| match index {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        })
+}
+
+/// Escapes a JSON pointer token.
+fn escape_pointer(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Pos, SourceInfo};
+    use hurl_core::parser::{parse_json, Reader};
+    use serde_json::json;
+
+    use super::*;
+    use crate::runner::{Number, RunnerError};
+
+    fn pattern(value: serde_json::Value) -> Pattern {
+        Pattern::from_json(&value)
+    }
+
+    fn messages(expected: &Pattern, actual: serde_json::Value, json_match: bool) -> Vec<String> {
+        diff(expected, &actual, json_match)
+            .iter()
+            .map(|m| m.to_string())
+            .collect()
+    }
+
+    fn json_value(s: &str) -> JsonValue {
+        let mut reader = Reader::new(s);
+        parse_json(&mut reader).unwrap()
+    }
+
+    #[test]
+    fn test_eval_pattern() {
+        let value = json_value(r#"{"id": {{uuid}}, "count": {{number}}}"#);
+        let variables = HashMap::new();
+        assert_eq!(
+            eval_pattern(&value, &variables, true).unwrap().to_string(),
+            r#"{"id":{{uuid}},"count":{{number}}}"#
+        );
+
+        // A defined variable is evaluated to its value, even if it's named like a placeholder.
+        let variables = HashMap::from([("number".to_string(), Value::Number(Number::Integer(2)))]);
+        assert_eq!(
+            eval_pattern(&value, &variables, true).unwrap().to_string(),
+            r#"{"id":{{uuid}},"count":2}"#
+        );
+    }
+
+    #[test]
+    fn test_eval_pattern_invalid_json() {
+        let value = json_value(r#"{"a": {{v}}}"#);
+        let variables = HashMap::from([("v".to_string(), Value::String("12abc".to_string()))]);
+        let error = eval_pattern(&value, &variables, true).unwrap_err();
+        assert_eq!(
+            error.source_info,
+            SourceInfo::new(Pos::new(1, 9), Pos::new(1, 10))
+        );
+        assert_eq!(
+            error.inner,
+            RunnerError::InvalidJson {
+                value: "12abc".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_diff_exact() {
+        let expected = pattern(json!({"id": 1, "tags": ["a", "b"], "user": {"name": "Bob"}}));
+        assert!(messages(
+            &expected,
+            json!({"id": 1.0, "tags": ["a", "b"], "user": {"name": "Bob"}}),
+            false
+        )
+        .is_empty());
+        assert_eq!(
+            messages(
+                &expected,
+                json!({"id": 2, "tags": ["b", "a"], "user": {"name": "Bob", "age": 20}}),
+                false
+            ),
+            vec![
+                "#/id: expected <1>, actual <2>",
+                "#/tags/0: expected <\"a\">, actual <\"b\">",
+                "#/tags/1: expected <\"b\">, actual <\"a\">",
+                "#/user/age: property is not expected",
+            ]
+        );
+        assert_eq!(
+            messages(&expected, json!({"tags": ["a"], "user": []}), false),
+            vec![
+                "#/id: property is missing",
+                "#/tags: expected 2 items, actual 1 items",
+                "#/user: expected <{\"name\":\"Bob\"}>, actual <[]>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_json_match() {
+        let expected = Pattern::Object(vec![
+            ("id".to_string(), Pattern::Placeholder(Placeholder::Uuid)),
+            ("tags".to_string(), pattern(json!(["a", {"b": 1}]))),
+            (
+                "count".to_string(),
+                Pattern::Placeholder(Placeholder::Integer),
+            ),
+            ("data".to_string(), Pattern::Placeholder(Placeholder::Any)),
+        ]);
+        let actual = json!({
+            "id": "a1b2c3d4-0000-4000-8000-000000000000",
+            "tags": [{"b": 1, "c": 2}, "a"],
+            "count": 3,
+            "data": null,
+            "extra": true,
+        });
+        assert!(messages(&expected, actual, true).is_empty());
+
+        let actual = json!({
+            "id": "a1b2c3d4",
+            "tags": [{"b": 2}, "a"],
+            "count": 3.5,
+            "data": [],
+        });
+        assert_eq!(
+            messages(&expected, actual, true),
+            vec![
+                "#/id: expected <{{uuid}}>, actual <\"a1b2c3d4\">",
+                "#/tags/0/b: expected <1>, actual <2>",
+                "#/count: expected <{{integer}}>, actual <3.5>",
+            ]
+        );
+
+        let expected = pattern(json!([1, 2, 3]));
+        assert!(messages(&expected, json!([3, 1, 2]), true).is_empty());
+        assert_eq!(
+            messages(&expected, json!([4, 5, 1]), true),
+            vec![
+                "#: no item matches expected item <2>",
+                "#: no item matches expected item <3>",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_matching() {
+        // The first expected item matches both actual items, the second one only the first: the
+        // first expected item must be reassigned.
+        let matching = vec![vec![true, true], vec![true, false]];
+        assert_eq!(max_matching(&matching, 2), vec![Some(1), Some(0)]);
+    }
+
+    #[test]
+    fn test_is_uuid() {
+        assert!(is_uuid("123e4567-e89b-12d3-a456-426614174000"));
+        assert!(!is_uuid("123e4567e89b12d3a456426614174000"));
+        assert!(!is_uuid("123e4567-e89b-12d3-a456-42661417400g"));
+    }
+}
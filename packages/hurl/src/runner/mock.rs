@@ -0,0 +1,650 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! A mock server answering HTTP requests with the responses of a Hurl file.
+//!
+//! Incoming requests are matched against the request part of each entry: method, URL path,
+//! query parameters and headers. Template expressions that can't be rendered with the variables
+//! of the run match any value. The response is synthesized from the response part of the
+//! first matching entry.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+use hurl_core::ast::{Bytes, Entry, StatusValue, Template, TemplateElement};
+use hurl_core::error::Error;
+
+use crate::runner::body::eval_body;
+use crate::runner::template::{eval_template, render_expression};
+use crate::runner::Value;
+use crate::util::logger::Logger;
+use crate::util::path::ContextDir;
+
+/// Maximum duration to wait for the data of a request, before closing its connection.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A mock server built from the entries of a Hurl file.
+pub struct MockServer<'a> {
+    entries: Vec<&'a Entry>,
+    variables: &'a HashMap<String, Value>,
+    context_dir: &'a ContextDir,
+}
+
+/// An HTTP request received by the mock server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockRequest {
+    pub method: String,
+    pub path: String,
+    pub query: Vec<(String, String)>,
+    pub headers: Vec<(String, String)>,
+}
+
+/// An HTTP response sent by the mock server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u64,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// A part of a template to match: a literal string, or any string for an expression that can't
+/// be rendered.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Pattern {
+    Literal(String),
+    Any,
+}
+
+impl<'a> MockServer<'a> {
+    /// Creates a new mock server for `entries`, rendering templates with `variables`.
+    pub fn new(
+        entries: Vec<&'a Entry>,
+        variables: &'a HashMap<String, Value>,
+        context_dir: &'a ContextDir,
+    ) -> Self {
+        MockServer {
+            entries,
+            variables,
+            context_dir,
+        }
+    }
+
+    /// Listens on `port` and answers the incoming requests, until the process is stopped.
+    ///
+    /// Each connection is handled in its own thread, so a slow client doesn't block the others.
+    pub fn listen(&self, port: u16, logger: &Logger) -> Result<(), String> {
+        let listener = match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => listener,
+            Err(e) => return Err(format!("Can not listen on port {port}: {e}")),
+        };
+        logger.info(format!("Listening on http://localhost:{port}").as_str());
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        scope.spawn(|| self.handle(stream, logger));
+                    }
+                    Err(e) => logger.warning(format!("Connection failed: {e}").as_str()),
+                }
+            }
+        });
+        Ok(())
+    }
+
+    /// Reads a request from `stream` and answers it.
+    fn handle(&self, mut stream: TcpStream, logger: &Logger) {
+        if let Err(e) = stream.set_read_timeout(Some(READ_TIMEOUT)) {
+            logger.warning(format!("Connection failed: {e}").as_str());
+            return;
+        }
+        let request = match read_request(&mut stream) {
+            Some(request) => request,
+            None => return,
+        };
+        let (entry_index, response) = match self.find_entry(&request) {
+            Some(index) => (Some(index), self.response(index)),
+            None => (None, not_found(&request)),
+        };
+        let target = match entry_index {
+            Some(index) => format!("entry {index}"),
+            None => "no entry".to_string(),
+        };
+        logger.info(
+            format!(
+                "{} {} => {target} ({})",
+                request.method, request.path, response.status
+            )
+            .as_str(),
+        );
+        if let Err(e) = write_response(&mut stream, &response) {
+            logger.warning(format!("Response can not be sent: {e}").as_str());
+        }
+    }
+
+    /// Returns the index (starting at 1) of the first entry matching `request`.
+    pub fn find_entry(&self, request: &MockRequest) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|entry| self.is_match(entry, request))
+            .map(|index| index + 1)
+    }
+
+    /// Returns true if the request of `entry` matches `request`.
+    fn is_match(&self, entry: &Entry, request: &MockRequest) -> bool {
+        let spec = &entry.request;
+        if !spec.method.0.eq_ignore_ascii_case(&request.method) {
+            return false;
+        }
+
+        // The URL is split in a path and query parameters, the origin being ignored.
+        let url = self.patterns(&spec.url);
+        let mut parts = split(&url, '?');
+        let path = strip_origin(parts.remove(0));
+        if !is_match(&path, &request.path, Some('/')) {
+            return false;
+        }
+        let mut params = parts
+            .into_iter()
+            .flat_map(|query| split(&query, '&'))
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                // Query parameters of incoming requests are decoded, and so are the URL literals.
+                let mut param = split(&param, '=');
+                let name = literal(&decode(param.remove(0)));
+                let value = param.into_iter().next().map(decode).unwrap_or_default();
+                (name, value)
+            })
+            .collect::<Vec<_>>();
+        for param in spec.querystring_params() {
+            let name = literal(&self.patterns(&param.key));
+            params.push((name, self.patterns(&param.value)));
+        }
+        let query_match = params.iter().all(|(name, value)| {
+            request
+                .query
+                .iter()
+                .any(|(n, v)| n == name && is_match(value, v, None))
+        });
+        if !query_match {
+            return false;
+        }
+
+        spec.headers.iter().all(|header| {
+            let name = literal(&self.patterns(&header.key));
+            let value = self.patterns(&header.value);
+            request
+                .headers
+                .iter()
+                .any(|(n, v)| n.eq_ignore_ascii_case(&name) && is_match(&value, v, None))
+        })
+    }
+
+    /// Returns the response of the entry at `entry_index` (starting at 1).
+    pub fn response(&self, entry_index: usize) -> MockResponse {
+        let entry = self.entries[entry_index - 1];
+        let spec = match &entry.response {
+            Some(spec) => spec,
+            None => {
+                return MockResponse {
+                    status: 200,
+                    headers: vec![],
+                    body: vec![],
+                }
+            }
+        };
+        let status = match spec.status.value {
+            StatusValue::Any => 200,
+            StatusValue::Specific(status) => status,
+        };
+        let mut headers = spec
+            .headers
+            .iter()
+            .map(|h| (self.render(&h.key), self.render(&h.value)))
+            .collect::<Vec<_>>();
+        let body = match &spec.body {
+            Some(body) => match eval_body(body, self.variables, self.context_dir) {
+                Ok(body) => body.bytes(),
+                Err(e) => {
+                    let message = format!(
+                        "Response of entry {entry_index} can not be rendered: {}",
+                        e.fixme()
+                    );
+                    return MockResponse {
+                        status: 500,
+                        headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+                        body: message.into_bytes(),
+                    };
+                }
+            },
+            None => vec![],
+        };
+        let content_type = match spec.body.as_ref().map(|b| &b.value) {
+            Some(Bytes::Json(_)) => Some("application/json"),
+            Some(Bytes::Xml(_)) => Some("application/xml"),
+            _ => None,
+        };
+        let has_content_type = headers
+            .iter()
+            .any(|(name, _)| name.eq_ignore_ascii_case("Content-Type"));
+        if let (Some(content_type), false) = (content_type, has_content_type) {
+            headers.push(("Content-Type".to_string(), content_type.to_string()));
+        }
+        MockResponse {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    /// Returns the patterns of a `template`, rendering the expressions that can be rendered.
+    fn patterns(&self, template: &Template) -> Vec<Pattern> {
+        let mut patterns = vec![];
+        for element in &template.elements {
+            let pattern = match element {
+                TemplateElement::String { value, .. } => Pattern::Literal(value.clone()),
+                TemplateElement::Expression(expr) => {
+                    match render_expression(expr, self.variables) {
+                        Ok(value) => Pattern::Literal(value),
+                        Err(_) => Pattern::Any,
+                    }
+                }
+            };
+            match (patterns.last_mut(), pattern) {
+                (Some(Pattern::Literal(prev)), Pattern::Literal(value)) => prev.push_str(&value),
+                (_, pattern) => patterns.push(pattern),
+            }
+        }
+        patterns
+    }
+
+    /// Renders a `template`, or returns its source if it can't be rendered.
+    fn render(&self, template: &Template) -> String {
+        eval_template(template, self.variables).unwrap_or_else(|_| template.to_string())
+    }
+}
+
+/// Splits `patterns` at each occurrence of `separator` in its literals.
+fn split(patterns: &[Pattern], separator: char) -> Vec<Vec<Pattern>> {
+    let mut parts = vec![vec![]];
+    for pattern in patterns {
+        match pattern {
+            Pattern::Any => parts.last_mut().unwrap().push(Pattern::Any),
+            Pattern::Literal(value) => {
+                for (i, s) in value.split(separator).enumerate() {
+                    if i > 0 {
+                        parts.push(vec![]);
+                    }
+                    if !s.is_empty() {
+                        parts
+                            .last_mut()
+                            .unwrap()
+                            .push(Pattern::Literal(s.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    parts
+}
+
+/// Removes the scheme and the authority of URL `patterns`, keeping the path.
+///
+/// The origin can be made of literals and expressions, like `http://{{host}}:{{port}}/users` or
+/// `{{host}}/users`: the path starts at the first slash that is not part of the scheme separator.
+fn strip_origin(patterns: Vec<Pattern>) -> Vec<Pattern> {
+    let mut start = find_slash(&patterns, 0, 0);
+    if let Some((index, offset)) = start {
+        if let Pattern::Literal(value) = &patterns[index] {
+            if value[..offset].ends_with(':') && value[offset..].starts_with("//") {
+                start = find_slash(&patterns, index, offset + 2);
+            }
+        }
+    }
+    match start {
+        Some((index, offset)) => {
+            let mut path = patterns[index..].to_vec();
+            if let Pattern::Literal(value) = &mut path[0] {
+                *value = value[offset..].to_string();
+            }
+            path
+        }
+        None => vec![Pattern::Literal("/".to_string())],
+    }
+}
+
+/// Returns the position of the first slash of `patterns` (index of the literal and offset in this
+/// literal), from the literal at `index` and its `offset`.
+fn find_slash(patterns: &[Pattern], index: usize, offset: usize) -> Option<(usize, usize)> {
+    patterns
+        .iter()
+        .enumerate()
+        .skip(index)
+        .find_map(|(i, pattern)| match pattern {
+            Pattern::Literal(value) => {
+                let from = if i == index { offset } else { 0 };
+                value[from..].find('/').map(|o| (i, from + o))
+            }
+            Pattern::Any => None,
+        })
+}
+
+/// Decodes the literals of URL query `patterns`, as an `application/x-www-form-urlencoded` query.
+fn decode(patterns: Vec<Pattern>) -> Vec<Pattern> {
+    patterns
+        .into_iter()
+        .map(|pattern| match pattern {
+            Pattern::Literal(value) => {
+                let value = value.replace('+', " ");
+                let value = percent_encoding::percent_decode_str(&value).decode_utf8_lossy();
+                Pattern::Literal(value.to_string())
+            }
+            Pattern::Any => Pattern::Any,
+        })
+        .collect()
+}
+
+/// Returns the string of literal `patterns`, expressions being ignored.
+fn literal(patterns: &[Pattern]) -> String {
+    patterns
+        .iter()
+        .filter_map(|p| match p {
+            Pattern::Literal(value) => Some(value.as_str()),
+            Pattern::Any => None,
+        })
+        .collect()
+}
+
+/// Returns true if `text` matches `patterns`. Any pattern matches a non-empty string, that doesn't
+/// contain `stop` if it's defined.
+fn is_match(patterns: &[Pattern], text: &str, stop: Option<char>) -> bool {
+    match patterns.split_first() {
+        None => text.is_empty(),
+        Some((Pattern::Literal(value), rest)) => match text.strip_prefix(value.as_str()) {
+            Some(text) => is_match(rest, text, stop),
+            None => false,
+        },
+        Some((Pattern::Any, rest)) => {
+            for (i, c) in text.char_indices() {
+                if Some(c) == stop {
+                    return false;
+                }
+                if is_match(rest, &text[i + c.len_utf8()..], stop) {
+                    return true;
+                }
+            }
+            false
+        }
+    }
+}
+
+/// Returns a 404 response for a `request` that doesn't match any entry.
+fn not_found(request: &MockRequest) -> MockResponse {
+    let message = format!("No entry matches {} {}", request.method, request.path);
+    MockResponse {
+        status: 404,
+        headers: vec![("Content-Type".to_string(), "text/plain".to_string())],
+        body: message.into_bytes(),
+    }
+}
+
+/// Reads an HTTP/1.1 request from `stream`, returns `None` if the request is malformed.
+fn read_request(stream: &mut TcpStream) -> Option<MockRequest> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut tokens = line.split_whitespace();
+    let method = tokens.next()?.to_string();
+    let target = tokens.next()?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, query),
+        None => (target, ""),
+    };
+    let query = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect::<Vec<_>>();
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':')?;
+        headers.push((name.trim().to_string(), value.trim().to_string()));
+    }
+
+    // The body is not matched, but it has to be read before answering.
+    let length = headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("Content-Length"))
+        .and_then(|(_, value)| value.parse::<u64>().ok())
+        .unwrap_or(0);
+    let mut body = vec![];
+    reader.take(length).read_to_end(&mut body).ok()?;
+
+    Some(MockRequest {
+        method,
+        path: path.to_string(),
+        query,
+        headers,
+    })
+}
+
+/// Writes an HTTP/1.1 `response` to `stream`, the connection being closed after the response.
+fn write_response(stream: &mut impl Write, response: &MockResponse) -> std::io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason(response.status)
+    );
+    // The length of the body and the connection are managed by the server.
+    let headers = response.headers.iter().filter(|(name, _)| {
+        !name.eq_ignore_ascii_case("Content-Length") && !name.eq_ignore_ascii_case("Connection")
+    });
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    head.push_str("Connection: close\r\n\r\n");
+    stream.write_all(head.as_bytes())?;
+    stream.write_all(&response.body)?;
+    stream.flush()
+}
+
+/// Returns the reason phrase of an HTTP `status`.
+fn reason(status: u64) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        303 => "See Other",
+        304 => "Not Modified",
+        307 => "Temporary Redirect",
+        308 => "Permanent Redirect",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::parser::parse_hurl_file;
+
+    use super::*;
+
+    fn new_request(method: &str, path: &str, query: &[(&str, &str)]) -> MockRequest {
+        MockRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            query: query
+                .iter()
+                .map(|(n, v)| (n.to_string(), v.to_string()))
+                .collect(),
+            headers: vec![("Accept".to_string(), "application/json".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_is_match() {
+        let patterns = vec![
+            Pattern::Literal("/users/".to_string()),
+            Pattern::Any,
+            Pattern::Literal("/orders".to_string()),
+        ];
+        assert!(is_match(&patterns, "/users/42/orders", Some('/')));
+        assert!(!is_match(&patterns, "/users//orders", Some('/')));
+        assert!(!is_match(&patterns, "/users/42/43/orders", Some('/')));
+        assert!(is_match(&patterns, "/users/42/43/orders", None));
+    }
+
+    #[test]
+    fn test_strip_origin() {
+        let any = || Pattern::Any;
+        let literal = |s: &str| Pattern::Literal(s.to_string());
+        assert_eq!(
+            strip_origin(vec![literal("http://localhost:8000/users/"), any()]),
+            vec![literal("/users/"), any()]
+        );
+        assert_eq!(
+            strip_origin(vec![literal("http://"), any(), literal("/users")]),
+            vec![literal("/users")]
+        );
+        assert_eq!(
+            strip_origin(vec![
+                literal("http://"),
+                any(),
+                literal(":"),
+                any(),
+                literal("/")
+            ]),
+            vec![literal("/")]
+        );
+        assert_eq!(
+            strip_origin(vec![any(), literal("/users")]),
+            vec![literal("/users")]
+        );
+        assert_eq!(
+            strip_origin(vec![literal("http://localhost:8000")]),
+            vec![literal("/")]
+        );
+    }
+
+    #[test]
+    fn test_write_response() {
+        let response = MockResponse {
+            status: 200,
+            headers: vec![
+                ("Content-Type".to_string(), "text/plain".to_string()),
+                ("Content-Length".to_string(), "5".to_string()),
+            ],
+            body: b"Hello".to_vec(),
+        };
+        let mut stream = vec![];
+        write_response(&mut stream, &response).unwrap();
+        assert_eq!(
+            String::from_utf8(stream).unwrap(),
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: 5\r\nConnection: close\r\n\r\nHello"
+        );
+    }
+
+    #[test]
+    fn test_find_entry() {
+        let content = r#"
+GET http://localhost:8000/users/{{id}}?active=true
+Accept: application/json
+HTTP 200
+{"id": 1}
+
+GET {{host}}/users
+[QueryStringParams]
+page: {{page}}
+HTTP 200
+
+POST http://localhost:8000/users
+HTTP 201
+
+GET http://localhost:8000/search?q=a%20b+c&tag=%7B{{tag}}
+HTTP 200
+"#;
+        let hurl_file = parse_hurl_file(content).unwrap();
+        let entries = hurl_file.entries.iter().collect::<Vec<_>>();
+        let mut variables = HashMap::new();
+        variables.insert("page".to_string(), Value::String("2".to_string()));
+        let context_dir = ContextDir::default();
+        let server = MockServer::new(entries, &variables, &context_dir);
+
+        let request = new_request("GET", "/users/1", &[("active", "true")]);
+        assert_eq!(server.find_entry(&request), Some(1));
+        let request = new_request("GET", "/users/1", &[]);
+        assert_eq!(server.find_entry(&request), None);
+        let request = new_request("GET", "/users", &[("page", "2")]);
+        assert_eq!(server.find_entry(&request), Some(2));
+        let request = new_request("GET", "/users", &[("page", "3")]);
+        assert_eq!(server.find_entry(&request), None);
+        let request = new_request("POST", "/users", &[]);
+        assert_eq!(server.find_entry(&request), Some(3));
+        let request = new_request("GET", "/search", &[("q", "a b c"), ("tag", "{x")]);
+        assert_eq!(server.find_entry(&request), Some(4));
+        let request = new_request("GET", "/search", &[("q", "a%20b+c"), ("tag", "{x")]);
+        assert_eq!(server.find_entry(&request), None);
+    }
+
+    #[test]
+    fn test_response() {
+        let content = r#"
+GET http://localhost:8000/users/1
+HTTP 200
+X-Name: {{name}}
+{"id": 1}
+"#;
+        let hurl_file = parse_hurl_file(content).unwrap();
+        let entries = hurl_file.entries.iter().collect::<Vec<_>>();
+        let mut variables = HashMap::new();
+        variables.insert("name".to_string(), Value::String("bob".to_string()));
+        let context_dir = ContextDir::default();
+        let server = MockServer::new(entries, &variables, &context_dir);
+        assert_eq!(
+            server.response(1),
+            MockResponse {
+                status: 200,
+                headers: vec![
+                    ("X-Name".to_string(), "bob".to_string()),
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                ],
+                body: br#"{"id": 1}"#.to_vec(),
+            }
+        );
+    }
+}
@@ -19,11 +19,15 @@
 //! A runner for Hurl files. If you want to execute an Hurl file, this is the right place.
 
 pub use self::error::{Error, RunnerError};
-pub use self::hurl_file::run;
+pub use self::hurl_file::{dry_run, parse, run, serve};
+pub use self::include::IncludedFile;
 pub use self::number::Number;
 pub use self::output::Output;
-pub use self::result::{AssertResult, CaptureResult, EntryResult, HurlResult};
-pub use self::runner_options::{RunnerOptions, RunnerOptionsBuilder};
+pub use self::result::{
+    AssertResult, CaptureResult, DryRunEntry, DryRunResult, EntryResult, HurlResult, RetryAttempt,
+    RetryReason,
+};
+pub use self::runner_options::{FromEntry, RunnerOptions, RunnerOptionsBuilder};
 pub use self::value::Value;
 
 mod assert;
@@ -33,8 +37,12 @@ mod entry;
 mod error;
 mod expr;
 mod filter;
+mod function;
 mod hurl_file;
+mod include;
 mod json;
+mod json_match;
+mod mock;
 mod multiline;
 mod multipart;
 mod number;
@@ -47,7 +55,11 @@ mod regex;
 mod request;
 mod response;
 mod result;
+mod retry;
 mod runner_options;
+mod snapshot;
 mod template;
+mod undefined;
 mod value;
+mod xmlschema;
 mod xpath;
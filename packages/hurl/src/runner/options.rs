@@ -19,20 +19,69 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use hurl_core::ast::{
-    BooleanOption, Entry, EntryOption, Float, NaturalOption, Number as AstNumber, OptionKind,
-    Retry, RetryOption, SectionValue, VariableDefinition, VariableValue,
+    BooleanOption, Condition, ConditionOption, Entry, EntryOption, Expr, Float, NaturalOption,
+    Number as AstNumber, OptionKind, Retry, RetryOption, SectionValue, VariableDefinition,
+    VariableValue,
 };
 
 use crate::http::{IpResolve, RequestedHttpVersion};
+use crate::runner::predicate::eval_predicate;
 use crate::runner::template::{eval_expression, eval_template};
 use crate::runner::{template, Error, Number, RunnerError, RunnerOptions, Value};
 use crate::util::logger::{Logger, Verbosity};
+use crate::util::path::ContextDir;
+
+/// Name of the variable holding the current element of a `for-each` option.
+pub const FOR_EACH_VARIABLE: &str = "item";
+
+/// Scope of the [`FOR_EACH_VARIABLE`] variable.
+///
+/// The variable is only defined during the iterations of a `for-each` entry: the value it had
+/// before the loop (if any) is restored when the runner moves to another entry.
+#[derive(Default)]
+pub struct ForEachScope {
+    /// Index of the `for-each` entry and previous value of the variable.
+    saved: Option<(usize, Option<Value>)>,
+}
+
+impl ForEachScope {
+    /// Updates the scope before running the `entry` at `entry_index`.
+    pub fn enter(
+        &mut self,
+        entry: &Entry,
+        entry_index: usize,
+        variables: &mut HashMap<String, Value>,
+    ) {
+        if let Some((index, _)) = &self.saved {
+            if *index == entry_index {
+                return;
+            }
+        }
+        if let Some((_, previous)) = self.saved.take() {
+            match previous {
+                Some(value) => variables.insert(FOR_EACH_VARIABLE.to_string(), value),
+                None => variables.remove(FOR_EACH_VARIABLE),
+            };
+        }
+        let has_for_each = entry
+            .request
+            .options()
+            .iter()
+            .any(|o| matches!(o.kind, OptionKind::ForEach(_)));
+        if has_for_each {
+            let previous = variables.get(FOR_EACH_VARIABLE).cloned();
+            self.saved = Some((entry_index, previous));
+        }
+    }
+}
 
 /// Returns a new [`RunnerOptions`] based on the `entry` optional Options section
 /// and a default `runner_options`.
-/// The [`variables`] can also be updated if `variable` keys are present in the section.
+/// The [`variables`] can also be updated if `variable` keys are present in the section, or with
+/// the element at `iteration` of a `for-each` option.
 pub fn get_entry_options(
     entry: &Entry,
+    iteration: usize,
     runner_options: &RunnerOptions,
     variables: &mut HashMap<String, Value>,
     logger: &Logger,
@@ -45,6 +94,12 @@ pub fn get_entry_options(
     logger.debug("");
     logger.debug_important("Entry options:");
 
+    // The current element of a `for-each` option is defined first, so it can be used by the other
+    // options.
+    if let Some(count) = eval_for_each(entry, iteration, variables)? {
+        runner_options.repeat = Some(count);
+    }
+
     for section in &entry.request.sections {
         if let SectionValue::Options(options) = &section.value {
             for option in options.iter() {
@@ -132,6 +187,9 @@ pub fn get_entry_options(
                         let value = eval_boolean_option(value, variables)?;
                         runner_options.follow_location = value;
                     }
+                    OptionKind::ForEach(_) => {}
+                    // Included files are processed before running the entries.
+                    OptionKind::Include(_) => {}
                     OptionKind::Insecure(value) => {
                         let value = eval_boolean_option(value, variables)?;
                         runner_options.insecure = value
@@ -152,10 +210,25 @@ pub fn get_entry_options(
                             IpResolve::IpV4
                         }
                     }
+                    OptionKind::JsonMatch(value) => {
+                        let value = eval_boolean_option(value, variables)?;
+                        runner_options.json_match = value
+                    }
                     OptionKind::MaxRedirect(value) => {
                         let value = eval_natural_option(value, variables)?;
                         runner_options.max_redirect = Some(value as usize)
                     }
+                    // Name and tags are used to select the entries to run, before evaluating
+                    // the entry options.
+                    OptionKind::Name(_) => {}
+                    // An entry is skipped if its `only-if` condition is false.
+                    OptionKind::OnlyIf(value) => {
+                        let context_dir = &runner_options.context_dir;
+                        let value = eval_condition_option(value, variables, context_dir)?;
+                        if !value {
+                            runner_options.skip = true
+                        }
+                    }
                     OptionKind::Output(filename) => {
                         runner_options.output = Some(filename.value.clone())
                     }
@@ -167,6 +240,10 @@ pub fn get_entry_options(
                         let value = eval_template(value, variables)?;
                         runner_options.proxy = Some(value.to_string())
                     }
+                    OptionKind::Repeat(value) => {
+                        let value = eval_natural_option(value, variables)?;
+                        runner_options.repeat = Some(value as usize)
+                    }
                     OptionKind::Resolve(value) => {
                         let value = eval_template(value, variables)?;
                         runner_options.resolves.push(value)
@@ -175,14 +252,36 @@ pub fn get_entry_options(
                         let value = eval_retry_option(value, variables)?;
                         runner_options.retry = value
                     }
+                    OptionKind::RetryAfter(value) => {
+                        let value = eval_boolean_option(value, variables)?;
+                        runner_options.retry_after = value
+                    }
+                    OptionKind::RetryBackoff(value) => {
+                        let value = eval_boolean_option(value, variables)?;
+                        runner_options.retry_backoff = value
+                    }
                     OptionKind::RetryInterval(value) => {
                         let value = eval_natural_option(value, variables)?;
                         runner_options.retry_interval = Duration::from_millis(value)
                     }
-                    OptionKind::Skip(value) => {
+                    OptionKind::RetryJitter(value) => {
                         let value = eval_boolean_option(value, variables)?;
+                        runner_options.retry_jitter = value
+                    }
+                    OptionKind::RetryMaxInterval(value) => {
+                        let value = eval_natural_option(value, variables)?;
+                        runner_options.retry_max_interval = Some(Duration::from_millis(value))
+                    }
+                    OptionKind::RetryMaxTime(value) => {
+                        let value = eval_natural_option(value, variables)?;
+                        runner_options.retry_max_time = Some(Duration::from_millis(value))
+                    }
+                    OptionKind::Skip(value) => {
+                        let context_dir = &runner_options.context_dir;
+                        let value = eval_condition_option(value, variables, context_dir)?;
                         runner_options.skip = value
                     }
+                    OptionKind::Tag(_) => {}
                     OptionKind::Variable(VariableDefinition { name, value, .. }) => {
                         let value = eval_variable_value(value, variables)?;
                         variables.insert(name.clone(), value);
@@ -199,6 +298,41 @@ pub fn get_entry_options(
     Ok(runner_options)
 }
 
+/// Evaluates the `for-each` option of an `entry` and returns its number of elements.
+///
+/// The element at `iteration` is set to the [`FOR_EACH_VARIABLE`] variable.
+fn eval_for_each(
+    entry: &Entry,
+    iteration: usize,
+    variables: &mut HashMap<String, Value>,
+) -> Result<Option<usize>, Error> {
+    let options = entry.request.options();
+    let expr = options.iter().find_map(|o| match &o.kind {
+        OptionKind::ForEach(expr) => Some(expr),
+        _ => None,
+    });
+    let expr = match expr {
+        Some(expr) => expr,
+        None => return Ok(None),
+    };
+    match eval_expression(expr, variables)? {
+        Value::List(values) => {
+            if let Some(value) = values.get(iteration) {
+                variables.insert(FOR_EACH_VARIABLE.to_string(), value.clone());
+            }
+            Ok(Some(values.len()))
+        }
+        v => {
+            let inner = RunnerError::TemplateVariableInvalidType {
+                name: expr.to_string(),
+                value: v.to_string(),
+                expecting: "list".to_string(),
+            };
+            Err(Error::new(expr.source_info(), inner, false))
+        }
+    }
+}
+
 /// Logs an entry option.
 fn log_option(option: &EntryOption, logger: &Logger) {
     let name = option.kind.name();
@@ -257,17 +391,56 @@ fn eval_boolean_option(
 ) -> Result<bool, Error> {
     match boolean_value {
         BooleanOption::Literal(value) => Ok(*value),
-        BooleanOption::Expression(expr) => match eval_expression(expr, variables)? {
-            Value::Bool(value) => Ok(value),
-            v => {
-                let inner = RunnerError::TemplateVariableInvalidType {
-                    name: expr.variable.name.clone(),
-                    value: v.to_string(),
-                    expecting: "boolean".to_string(),
-                };
-                Err(Error::new(expr.variable.source_info, inner, false))
+        BooleanOption::Expression(expr) => eval_boolean_expr(expr, variables),
+    }
+}
+
+/// Evaluates the condition of a `skip` or `only-if` option.
+///
+/// A condition with a predicate is true if the value of its expression satisfies the predicate,
+/// as the actual value of an assert.
+fn eval_condition_option(
+    condition: &ConditionOption,
+    variables: &HashMap<String, Value>,
+    context_dir: &ContextDir,
+) -> Result<bool, Error> {
+    match condition {
+        ConditionOption::Literal(value) => Ok(*value),
+        ConditionOption::Expression(expr) => eval_boolean_expr(expr, variables),
+        ConditionOption::Predicate(Condition {
+            expr, predicate, ..
+        }) => {
+            let value = eval_expression(expr, variables)?;
+            match eval_predicate(
+                predicate,
+                variables,
+                &Some(value),
+                context_dir,
+                false,
+                false,
+            ) {
+                Ok(()) => Ok(true),
+                Err(Error {
+                    inner: RunnerError::AssertFailure { .. } | RunnerError::AssertJsonMatch { .. },
+                    ..
+                }) => Ok(false),
+                Err(e) => Err(e),
             }
-        },
+        }
+    }
+}
+
+fn eval_boolean_expr(expr: &Expr, variables: &HashMap<String, Value>) -> Result<bool, Error> {
+    match eval_expression(expr, variables)? {
+        Value::Bool(value) => Ok(value),
+        v => {
+            let inner = RunnerError::TemplateVariableInvalidType {
+                name: expr.to_string(),
+                value: v.to_string(),
+                expecting: "boolean".to_string(),
+            };
+            Err(Error::new(expr.source_info(), inner, false))
+        }
     }
 }
 
@@ -281,22 +454,22 @@ fn eval_natural_option(
             Value::Number(Number::Integer(value)) => {
                 if value < 0 {
                     let inner = RunnerError::TemplateVariableInvalidType {
-                        name: expr.variable.name.clone(),
+                        name: expr.to_string(),
                         value: value.to_string(),
                         expecting: "positive integer".to_string(),
                     };
-                    Err(Error::new(expr.variable.source_info, inner, false))
+                    Err(Error::new(expr.source_info(), inner, false))
                 } else {
                     Ok(value as u64)
                 }
             }
             v => {
                 let inner = RunnerError::TemplateVariableInvalidType {
-                    name: expr.variable.name.clone(),
+                    name: expr.to_string(),
                     value: v.to_string(),
                     expecting: "positive integer".to_string(),
                 };
-                Err(Error::new(expr.variable.source_info, inner, false))
+                Err(Error::new(expr.source_info(), inner, false))
             }
         },
     }
@@ -318,28 +491,30 @@ fn eval_retry_option(
                     Ok(Retry::Finite(value as usize))
                 } else {
                     let inner = RunnerError::TemplateVariableInvalidType {
-                        name: expr.variable.name.clone(),
+                        name: expr.to_string(),
                         value: value.to_string(),
                         expecting: "integer".to_string(),
                     };
-                    Err(Error::new(expr.variable.source_info, inner, false))
+                    Err(Error::new(expr.source_info(), inner, false))
                 }
             }
             v => {
                 let inner = RunnerError::TemplateVariableInvalidType {
-                    name: expr.variable.name.clone(),
+                    name: expr.to_string(),
                     value: v.to_string(),
                     expecting: "integer".to_string(),
                 };
-                Err(Error::new(expr.variable.source_info, inner, false))
+                Err(Error::new(expr.source_info(), inner, false))
             }
         },
     }
 }
 
-fn eval_variable_value(
+/// Evaluates a variable value, which can be a variable definition or the default value of an
+/// expression.
+pub fn eval_variable_value(
     variable_value: &VariableValue,
-    variables: &mut HashMap<String, Value>,
+    variables: &HashMap<String, Value>,
 ) -> Result<Value, Error> {
     match variable_value {
         VariableValue::Null => Ok(Value::Null),
@@ -362,7 +537,7 @@ fn eval_number(number: &AstNumber) -> Value {
 
 #[cfg(test)]
 mod tests {
-    use hurl_core::ast::{Expr, Pos, SourceInfo, Variable, Whitespace};
+    use hurl_core::ast::{Expr, ExprKind, Pos, SourceInfo, Variable, Whitespace};
 
     use super::*;
     use crate::runner::RunnerError;
@@ -374,10 +549,12 @@ mod tests {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
             },
-            variable: Variable {
+            kind: ExprKind::Variable(Variable {
                 name: "verbose".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-            },
+            }),
+            default: None,
+            filters: vec![],
             space1: Whitespace {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
@@ -392,10 +569,12 @@ mod tests {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
             },
-            variable: Variable {
+            kind: ExprKind::Variable(Variable {
                 name: "retry".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-            },
+            }),
+            default: None,
+            filters: vec![],
             space1: Whitespace {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
@@ -412,6 +591,107 @@ mod tests {
         assert!(eval_boolean_option(&verbose_option_template(), &variables).unwrap());
     }
 
+    fn condition_option(condition: &str) -> ConditionOption {
+        let content = format!("GET http://localhost:8000\n[Options]\nskip: {condition}\n");
+        let hurl_file = hurl_core::parser::parse_hurl_file(&content).unwrap();
+        match &hurl_file.entries[0].request.options()[0].kind {
+            OptionKind::Skip(condition) => condition.clone(),
+            _ => panic!("skip option expected"),
+        }
+    }
+
+    #[test]
+    fn test_eval_condition_option() {
+        let variables = HashMap::from([
+            ("env".to_string(), Value::String("prod".to_string())),
+            (
+                "flags".to_string(),
+                Value::List(vec![Value::String("beta".to_string())]),
+            ),
+        ]);
+        let context_dir = ContextDir::default();
+        let eval = |condition: &str| {
+            eval_condition_option(&condition_option(condition), &variables, &context_dir)
+        };
+        assert!(eval("true").unwrap());
+        assert!(eval(r#"{{env}} == "prod""#).unwrap());
+        assert!(!eval(r#"{{env}} != "prod""#).unwrap());
+        assert!(eval(r#"{{flags}} includes "beta""#).unwrap());
+        assert!(eval(r#"{{env}} not startsWith "dev""#).unwrap());
+        assert!(!eval("{{env}} isInteger").unwrap());
+
+        let error = eval(r#"{{env}} == "{{expected}}""#).err().unwrap();
+        assert_eq!(
+            error.inner,
+            RunnerError::TemplateVariableNotDefined {
+                name: "expected".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_eval_for_each() {
+        let content = "GET http://localhost:8000/{{item}}\n[Options]\nfor-each: {{ids}}\n";
+        let hurl_file = hurl_core::parser::parse_hurl_file(content).unwrap();
+        let entry = &hurl_file.entries[0];
+        let mut variables = HashMap::from([(
+            "ids".to_string(),
+            Value::List(vec![
+                Value::Number(Number::Integer(7)),
+                Value::Number(Number::Integer(12)),
+            ]),
+        )]);
+        assert_eq!(eval_for_each(entry, 1, &mut variables).unwrap(), Some(2));
+        assert_eq!(
+            variables.get(FOR_EACH_VARIABLE),
+            Some(&Value::Number(Number::Integer(12)))
+        );
+
+        variables.insert("ids".to_string(), Value::String("7,12".to_string()));
+        let error = eval_for_each(entry, 0, &mut variables).err().unwrap();
+        assert_eq!(
+            error.inner,
+            RunnerError::TemplateVariableInvalidType {
+                name: "ids".to_string(),
+                value: "7,12".to_string(),
+                expecting: "list".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_for_each_scope() {
+        let content = "GET http://localhost:8000/{{item}}\n[Options]\nfor-each: {{ids}}\n\nGET http://localhost:8000/{{item}}\n";
+        let hurl_file = hurl_core::parser::parse_hurl_file(content).unwrap();
+        let (for_each_entry, entry) = (&hurl_file.entries[0], &hurl_file.entries[1]);
+        let ids = Value::List(vec![Value::Number(Number::Integer(7))]);
+        let item = Value::String("user".to_string());
+
+        // A variable defined before the loop is restored after it.
+        let mut variables = HashMap::from([
+            ("ids".to_string(), ids.clone()),
+            (FOR_EACH_VARIABLE.to_string(), item.clone()),
+        ]);
+        let mut scope = ForEachScope::default();
+        scope.enter(for_each_entry, 1, &mut variables);
+        eval_for_each(for_each_entry, 0, &mut variables).unwrap();
+        scope.enter(for_each_entry, 1, &mut variables);
+        assert_eq!(
+            variables.get(FOR_EACH_VARIABLE),
+            Some(&Value::Number(Number::Integer(7)))
+        );
+        scope.enter(entry, 2, &mut variables);
+        assert_eq!(variables.get(FOR_EACH_VARIABLE), Some(&item));
+
+        // Otherwise, the variable is removed after the loop.
+        let mut variables = HashMap::from([("ids".to_string(), ids)]);
+        let mut scope = ForEachScope::default();
+        scope.enter(for_each_entry, 1, &mut variables);
+        eval_for_each(for_each_entry, 0, &mut variables).unwrap();
+        scope.enter(entry, 2, &mut variables);
+        assert_eq!(variables.get(FOR_EACH_VARIABLE), None);
+    }
+
     #[test]
     fn test_eval_boolean_option_error() {
         let mut variables = HashMap::default();
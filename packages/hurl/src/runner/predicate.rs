@@ -21,11 +21,16 @@ use std::collections::HashMap;
 use hurl_core::ast::*;
 use regex;
 
+use crate::jsonschema::Schema;
+use crate::runner::body::eval_file;
 use crate::runner::error::Error;
+use crate::runner::json_match;
 use crate::runner::predicate_value::{eval_predicate_value, eval_predicate_value_template};
 use crate::runner::result::PredicateResult;
+use crate::runner::snapshot;
 use crate::runner::template::eval_template;
 use crate::runner::value::Value;
+use crate::runner::xmlschema::{XmlSchema, XmlSchemaKind};
 use crate::runner::{Number, RunnerError};
 use crate::util::path::ContextDir;
 
@@ -47,17 +52,25 @@ use crate::util::path::ContextDir;
 /// ```
 ///
 /// In this case, the predicate is `startsWith "{{name}}"`.
+///
+/// If `update_snapshots` is true, snapshot files of `matchesSnapshot` predicates are rewritten with
+/// the actual value (except for negated predicates). If `json_match` is true, JSON values are
+/// matched structurally (see [`json_match`]).
 pub fn eval_predicate(
     predicate: &Predicate,
     variables: &HashMap<String, Value>,
     value: &Option<Value>,
     context_dir: &ContextDir,
+    update_snapshots: bool,
+    json_match: bool,
 ) -> PredicateResult {
     let assert_result = eval_predicate_func(
         &predicate.predicate_func,
         variables,
         value.as_ref(),
         context_dir,
+        update_snapshots && !predicate.not,
+        json_match,
     )?;
 
     // Column error is set to 0 to disable the error display of "^----"
@@ -83,10 +96,37 @@ pub fn eval_predicate(
         };
         Err(Error::new(source_info, inner, true))
     } else if !predicate.not && !assert_result.success {
-        let inner = RunnerError::AssertFailure {
-            actual: assert_result.actual,
-            expected: assert_result.expected,
-            type_mismatch: false,
+        let inner = match (&predicate.predicate_func.value, value) {
+            // Snapshots are usually large, so we display a diff rather than the two values.
+            (PredicateFuncValue::MatchSnapshot { value: file, .. }, Some(_)) => {
+                let file = eval_predicate_value_template(file, variables)?;
+                RunnerError::AssertSnapshot {
+                    file,
+                    diff: snapshot::diff(&assert_result.expected, &assert_result.actual),
+                }
+            }
+            (PredicateFuncValue::MatchSchema { .. }, Some(_)) => RunnerError::AssertSchema {
+                file: schema_filename(&predicate.predicate_func.value),
+                errors: assert_result.actual.lines().map(str::to_string).collect(),
+            },
+            (PredicateFuncValue::MatchXmlSchema { .. }, Some(_)) => RunnerError::AssertXmlSchema {
+                file: schema_filename(&predicate.predicate_func.value),
+                errors: assert_result.actual.lines().map(str::to_string).collect(),
+            },
+            (
+                PredicateFuncValue::Equal {
+                    value: PredicateValue::Json(_),
+                    ..
+                },
+                Some(_),
+            ) => RunnerError::AssertJsonMatch {
+                errors: assert_result.actual.lines().map(str::to_string).collect(),
+            },
+            _ => RunnerError::AssertFailure {
+                actual: assert_result.actual,
+                expected: assert_result.expected,
+                type_mismatch: false,
+            },
         };
         Err(Error::new(source_info, inner, true))
     } else {
@@ -229,6 +269,20 @@ fn expected_no_value(
             let expected = eval_predicate_value_template(expected, variables)?;
             Ok(format!("matches regex <{expected}>"))
         }
+        PredicateFuncValue::MatchSnapshot {
+            value: expected, ..
+        } => {
+            let expected = eval_predicate_value_template(expected, variables)?;
+            Ok(format!("matches snapshot <{expected}>"))
+        }
+        PredicateFuncValue::MatchSchema { .. } => Ok(format!(
+            "matches schema <{}>",
+            schema_filename(predicate_func_value)
+        )),
+        PredicateFuncValue::MatchXmlSchema { .. } => Ok(format!(
+            "matches XML schema <{}>",
+            schema_filename(predicate_func_value)
+        )),
         PredicateFuncValue::IsInteger => Ok("integer".to_string()),
         PredicateFuncValue::IsFloat => Ok("float".to_string()),
         PredicateFuncValue::IsBoolean => Ok("boolean".to_string()),
@@ -248,6 +302,8 @@ fn eval_predicate_func(
     variables: &HashMap<String, Value>,
     value: Option<&Value>,
     context_dir: &ContextDir,
+    update_snapshots: bool,
+    json_match: bool,
 ) -> Result<AssertResult, Error> {
     let value = match value {
         Some(value) => value,
@@ -263,6 +319,14 @@ fn eval_predicate_func(
     };
 
     match &predicate_func.value {
+        PredicateFuncValue::Equal {
+            value: PredicateValue::Json(expected),
+            ..
+        } => eval_json_equal(expected, variables, value, json_match, false),
+        PredicateFuncValue::NotEqual {
+            value: PredicateValue::Json(expected),
+            ..
+        } => eval_json_equal(expected, variables, value, json_match, true),
         PredicateFuncValue::Equal {
             value: expected, ..
         } => eval_equal(expected, variables, value, context_dir),
@@ -296,6 +360,22 @@ fn eval_predicate_func(
         PredicateFuncValue::Match {
             value: expected, ..
         } => eval_match(expected, predicate_func.source_info, variables, value),
+        PredicateFuncValue::MatchSnapshot {
+            value: expected, ..
+        } => eval_match_snapshot(
+            expected,
+            predicate_func.source_info,
+            variables,
+            value,
+            context_dir,
+            update_snapshots,
+        ),
+        PredicateFuncValue::MatchSchema {
+            value: expected, ..
+        } => eval_match_schema(expected, value, context_dir),
+        PredicateFuncValue::MatchXmlSchema {
+            value: expected, ..
+        } => eval_match_xml_schema(expected, value, context_dir),
         PredicateFuncValue::IsInteger => eval_is_integer(value),
         PredicateFuncValue::IsFloat => eval_is_float(value),
         PredicateFuncValue::IsBoolean => eval_is_boolean(value),
@@ -520,6 +600,258 @@ fn eval_match(
     }
 }
 
+/// Evaluates if an `actual` value matches the snapshot file `expected` (using a `variables` set).
+///
+/// On success, actual and expected values of the assert result are the snapshot texts of the
+/// actual value and of the snapshot file.
+fn eval_match_snapshot(
+    expected: &PredicateValue,
+    source_info: SourceInfo,
+    variables: &HashMap<String, Value>,
+    actual: &Value,
+    context_dir: &ContextDir,
+    update_snapshots: bool,
+) -> Result<AssertResult, Error> {
+    let file = eval_predicate_value_template(expected, variables)?;
+    let actual_text = match snapshot::snapshot_text(actual) {
+        Some(text) => text,
+        None => {
+            return Ok(AssertResult {
+                success: false,
+                actual: actual.display(),
+                expected: format!("matches snapshot <{file}>"),
+                type_mismatch: true,
+            })
+        }
+    };
+    let (success, expected_text) = snapshot::eval_snapshot(
+        &file,
+        source_info,
+        &actual_text,
+        context_dir,
+        update_snapshots,
+    )?;
+    Ok(AssertResult {
+        success,
+        actual: actual_text,
+        expected: expected_text,
+        type_mismatch: false,
+    })
+}
+
+/// Evaluates if an `actual` value is equal to the JSON value `expected` (or not equal if `not` is
+/// true).
+///
+/// When the values are not equal, the actual value of the assert result lists the mismatches, one
+/// per line.
+fn eval_json_equal(
+    expected: &JsonValue,
+    variables: &HashMap<String, Value>,
+    actual: &Value,
+    json_match: bool,
+    not: bool,
+) -> Result<AssertResult, Error> {
+    let pattern = json_match::eval_pattern(expected, variables, json_match)?;
+    let expected_display = if not {
+        format!("not json <{pattern}>")
+    } else {
+        format!("json <{pattern}>")
+    };
+    let instance = match json_instance(actual) {
+        Some(instance) => instance,
+        None => {
+            return Ok(AssertResult {
+                success: false,
+                actual: actual.display(),
+                expected: expected_display,
+                type_mismatch: true,
+            })
+        }
+    };
+    let mismatches = json_match::diff(&pattern, &instance, json_match);
+    let actual_display = if mismatches.is_empty() || not {
+        actual.display()
+    } else {
+        mismatches
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    Ok(AssertResult {
+        success: mismatches.is_empty() != not,
+        actual: actual_display,
+        expected: expected_display,
+        type_mismatch: false,
+    })
+}
+
+/// Evaluates if an `actual` value is valid against the JSON schema file `expected`.
+///
+/// When the value is not valid, the actual value of the assert result lists the validation errors,
+/// one per line.
+fn eval_match_schema(
+    expected: &PredicateValue,
+    actual: &Value,
+    context_dir: &ContextDir,
+) -> Result<AssertResult, Error> {
+    let file = match expected {
+        PredicateValue::File(file) => file,
+        _ => panic!("expect a file predicate value"), // should have failed in parsing
+    };
+    let filename = &file.filename;
+    let content = eval_file(filename, context_dir)?;
+    let schema = serde_json::from_slice::<serde_json::Value>(&content)
+        .map_err(|e| e.to_string())
+        .and_then(|schema| Schema::new(schema).map_err(|e| e.to_string()));
+    let schema = match schema {
+        Ok(schema) => schema,
+        Err(message) => {
+            let inner = RunnerError::InvalidJsonSchema {
+                file: filename.value.clone(),
+                message,
+            };
+            return Err(Error::new(filename.source_info, inner, false));
+        }
+    };
+    let expected_display = format!("matches schema <{}>", filename.value);
+    let instance = match json_instance(actual) {
+        Some(instance) => instance,
+        None => {
+            return Ok(AssertResult {
+                success: false,
+                actual: actual.display(),
+                expected: expected_display,
+                type_mismatch: true,
+            })
+        }
+    };
+    let errors = schema.validate(&instance);
+    let actual_display = if errors.is_empty() {
+        actual.display()
+    } else {
+        errors
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    Ok(AssertResult {
+        success: errors.is_empty(),
+        actual: actual_display,
+        expected: expected_display,
+        type_mismatch: false,
+    })
+}
+
+/// Returns the JSON instance of an actual `value` to validate against a JSON schema or to match
+/// against a JSON value, `None` if this value can't be converted to JSON.
+///
+/// Strings are JSON strings: a response body is parsed beforehand, when the assert is evaluated.
+fn json_instance(value: &Value) -> Option<serde_json::Value> {
+    match value {
+        Value::Bool(_)
+        | Value::Date(_)
+        | Value::List(_)
+        | Value::Null
+        | Value::Number(_)
+        | Value::Object(_)
+        | Value::String(_) => Some(value.to_json()),
+        Value::Bytes(_) | Value::Nodeset(_) | Value::Regex(_) | Value::Unit => None,
+    }
+}
+
+/// Evaluates if an `actual` value is valid against the XML schema file `expected` (a RelaxNG
+/// schema for `.rng` files, a XSD schema otherwise).
+///
+/// The actual value is either a XML text (like a response body), or a list of XML texts (the nodes
+/// selected by an xpath query). When the value is not valid, the actual value of the assert result
+/// lists the validation errors, one per line.
+fn eval_match_xml_schema(
+    expected: &PredicateValue,
+    actual: &Value,
+    context_dir: &ContextDir,
+) -> Result<AssertResult, Error> {
+    let file = match expected {
+        PredicateValue::File(file) => file,
+        _ => panic!("expect a file predicate value"), // should have failed in parsing
+    };
+    let filename = &file.filename;
+    // The schema is read here to check the file access, but is parsed from its path so that
+    // included schemas can be resolved relatively to it.
+    eval_file(filename, context_dir)?;
+    let path = context_dir.get_path(&filename.value);
+    let kind = XmlSchemaKind::from_filename(&filename.value);
+    let mut schema = match XmlSchema::new(&path, kind) {
+        Ok(schema) => schema,
+        Err(errors) => {
+            let message = errors
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let inner = RunnerError::InvalidXmlSchema {
+                file: filename.value.clone(),
+                message,
+            };
+            return Err(Error::new(filename.source_info, inner, false));
+        }
+    };
+    let expected_display = format!("matches XML schema <{}>", filename.value);
+    let documents = match actual {
+        Value::String(xml) => vec![xml],
+        Value::List(values) if values.iter().all(|v| matches!(v, Value::String(_))) => values
+            .iter()
+            .filter_map(|v| match v {
+                Value::String(xml) => Some(xml),
+                _ => None,
+            })
+            .collect(),
+        _ => {
+            return Ok(AssertResult {
+                success: false,
+                actual: actual.display(),
+                expected: expected_display,
+                type_mismatch: true,
+            })
+        }
+    };
+    let errors = documents
+        .iter()
+        .flat_map(|xml| schema.validate(xml))
+        .collect::<Vec<_>>();
+    let actual_display = if errors.is_empty() {
+        actual.display()
+    } else {
+        errors
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    Ok(AssertResult {
+        success: errors.is_empty(),
+        actual: actual_display,
+        expected: expected_display,
+        type_mismatch: false,
+    })
+}
+
+/// Returns the schema filename of a `matchesSchema` or `matchesXmlSchema` predicate function.
+fn schema_filename(predicate_func_value: &PredicateFuncValue) -> String {
+    match predicate_func_value {
+        PredicateFuncValue::MatchSchema {
+            value: PredicateValue::File(file),
+            ..
+        }
+        | PredicateFuncValue::MatchXmlSchema {
+            value: PredicateValue::File(file),
+            ..
+        } => file.filename.value.clone(),
+        _ => panic!("expect a schema predicate with a file value"),
+    }
+}
+
 /// Evaluates if an `actual` value is an integer.
 fn eval_is_integer(actual: &Value) -> Result<AssertResult, Error> {
     Ok(AssertResult {
@@ -933,7 +1265,9 @@ mod tests {
             &predicate,
             &variables,
             &Some(Value::Bool(true)),
-            &context_dir
+            &context_dir,
+            false,
+            false,
         )
         .is_ok());
 
@@ -942,6 +1276,8 @@ mod tests {
             &variables,
             &Some(Value::Number(Number::Integer(10))),
             &context_dir,
+            false,
+            false,
         )
         .unwrap_err();
         assert_eq!(
@@ -961,7 +1297,9 @@ mod tests {
             &predicate,
             &variables,
             &Some(Value::Number(Number::Integer(1))),
-            &context_dir
+            &context_dir,
+            false,
+            false,
         )
         .is_ok());
     }
@@ -1061,14 +1399,14 @@ mod tests {
 
         let value = Some(&Value::Unit);
         let assert_result =
-            eval_predicate_func(&pred_func, &variables, value, &context_dir).unwrap();
+            eval_predicate_func(&pred_func, &variables, value, &context_dir, false, false).unwrap();
         assert!(assert_result.success);
         assert_eq!(assert_result.actual.as_str(), "unit");
         assert_eq!(assert_result.expected.as_str(), "something");
 
         let value = None;
         let assert_result =
-            eval_predicate_func(&pred_func, &variables, value, &context_dir).unwrap();
+            eval_predicate_func(&pred_func, &variables, value, &context_dir, false, false).unwrap();
         assert!(!assert_result.success);
         assert!(!assert_result.type_mismatch);
         assert_eq!(assert_result.actual.as_str(), "none");
@@ -1203,10 +1541,12 @@ mod tests {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 11)),
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: String::from("base_url"),
                     source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 19)),
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 19), Pos::new(1, 19)),
@@ -1395,7 +1735,9 @@ mod tests {
             &predicate,
             &variables,
             &Some(Value::Number(Number::Integer(1))),
-            &context_dir
+            &context_dir,
+            false,
+            false,
         )
         .is_ok());
 
@@ -1425,6 +1767,8 @@ mod tests {
             &variables,
             &Some(Value::Number(Number::Integer(1))),
             &context_dir,
+            false,
+            false,
         )
         .unwrap_err();
         assert_eq!(
@@ -1437,6 +1781,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_json_equal_predicate() {
+        let variables = HashMap::new();
+        let context_dir = ContextDir::default();
+        let mut reader = hurl_core::parser::Reader::new("{\"id\": {{uuid}}, \"tags\": [1, 2]}");
+        let expected = hurl_core::parser::parse_json(&mut reader).unwrap();
+        let predicate = Predicate {
+            not: false,
+            space0: whitespace(),
+            predicate_func: PredicateFunc {
+                source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
+                value: PredicateFuncValue::Equal {
+                    space0: whitespace(),
+                    value: PredicateValue::Json(expected),
+                    operator: true,
+                },
+            },
+        };
+        let actual = Value::from_json(&serde_json::json!({
+            "id": "123e4567-e89b-12d3-a456-426614174000",
+            "tags": [2, 1],
+            "name": "Bob"
+        }));
+        assert!(eval_predicate(
+            &predicate,
+            &variables,
+            &Some(actual),
+            &context_dir,
+            false,
+            true
+        )
+        .is_ok());
+
+        let actual = Value::from_json(&serde_json::json!({"id": "123", "tags": [1, 3]}));
+        let error = eval_predicate(
+            &predicate,
+            &variables,
+            &Some(actual),
+            &context_dir,
+            false,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.inner,
+            RunnerError::AssertJsonMatch {
+                errors: vec![
+                    "#/id: expected <{{uuid}}>, actual <\"123\">".to_string(),
+                    "#/tags/1: expected <2>, actual <3>".to_string(),
+                ],
+            }
+        );
+
+        // Outside JSON match mode, placeholders are variables.
+        let actual = Value::Bool(true);
+        let error = eval_predicate(
+            &predicate,
+            &variables,
+            &Some(actual),
+            &context_dir,
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.inner,
+            RunnerError::TemplateVariableNotDefined {
+                name: "uuid".to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_date_predicate() {
         // predicate: `isDate`
@@ -1484,7 +1900,7 @@ mod tests {
             },
         };
 
-        let error = eval_predicate(&predicate, &variables, &None, &context_dir)
+        let error = eval_predicate(&predicate, &variables, &None, &context_dir, false, false)
             .err()
             .unwrap();
         assert_eq!(
@@ -1511,7 +1927,7 @@ mod tests {
         };
 
         let variables = HashMap::new();
-        assert!(eval_predicate(&predicate, &variables, &None, &context_dir).is_ok());
+        assert!(eval_predicate(&predicate, &variables, &None, &context_dir, false, false).is_ok());
     }
 
     #[test]
@@ -22,6 +22,7 @@ use hurl_core::ast::*;
 use crate::runner::body::eval_file; // TODO move function out of body module
 use crate::runner::error::Error;
 use crate::runner::expr::eval_expr;
+use crate::runner::json::eval_json;
 use crate::runner::multiline::eval_multiline;
 use crate::runner::template::eval_template;
 use crate::runner::{Number as ValueNumber, Value};
@@ -55,6 +56,10 @@ pub fn eval_predicate_value(
             Ok(value)
         }
         PredicateValue::Regex(regex) => Ok(Value::Regex(regex.inner.clone())),
+        PredicateValue::Json(value) => {
+            let value = eval_json(value, variables)?;
+            Ok(Value::from_json(&value))
+        }
     }
 }
 
@@ -137,6 +137,20 @@ fn eval_query_xpath(
     }
 }
 
+/// Evaluates an xpath query `expr` against a `response`, returning the selected nodes as a list of
+/// XML texts (used to validate them against a XML schema).
+pub fn eval_query_xpath_nodes(
+    response: &http::Response,
+    expr: &Template,
+    variables: &HashMap<String, Value>,
+    query_source_info: SourceInfo,
+) -> QueryResult {
+    match response.text() {
+        Ok(xml) => filter::eval_xpath_nodes_string(&xml, expr, variables, query_source_info),
+        Err(inner) => Err(Error::new(query_source_info, inner.into(), false)),
+    }
+}
+
 fn eval_query_jsonpath(
     response: &http::Response,
     expr: &Template,
@@ -194,10 +194,12 @@ mod tests {
                 elements: vec![
                     TemplateElement::Expression(Expr {
                         space0: whitespace(),
-                        variable: Variable {
+                        kind: ExprKind::Variable(Variable {
                             name: String::from("base_url"),
                             source_info: SourceInfo::new(Pos::new(1, 7), Pos::new(1, 15)),
-                        },
+                        }),
+                        default: None,
+                        filters: vec![],
                         space1: whitespace(),
                     }),
                     TemplateElement::String {
@@ -272,10 +274,12 @@ mod tests {
                             delimiter: None,
                             elements: vec![TemplateElement::Expression(Expr {
                                 space0: whitespace(),
-                                variable: Variable {
+                                kind: ExprKind::Variable(Variable {
                                     name: String::from("param1"),
                                     source_info: SourceInfo::new(Pos::new(1, 7), Pos::new(1, 15)),
-                                },
+                                }),
+                                default: None,
+                                filters: vec![],
                                 space1: whitespace(),
                             })],
                             source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
@@ -25,6 +25,7 @@ use crate::runner::body::eval_body;
 use crate::runner::capture::eval_capture;
 use crate::runner::error::{Error, RunnerError};
 use crate::runner::json::eval_json_value;
+use crate::runner::json_match;
 use crate::runner::multiline::eval_multiline;
 use crate::runner::result::{AssertResult, CaptureResult};
 use crate::runner::template::eval_template;
@@ -60,12 +61,15 @@ pub fn eval_version_status_asserts(
 /// Returns a list of assert results, given a set of `variables`, an actual `http_response` and a spec `response`.
 ///
 /// Asserts on status and version and not run in this function, there are run with `eval_version_status_asserts`
-/// as they're semantically stronger.
+/// as they're semantically stronger. If `update_snapshots` is true, snapshot files are rewritten
+/// with the actual values. If `json_match` is true, JSON bodies and values are matched structurally.
 pub fn eval_asserts(
     response: &Response,
     variables: &HashMap<String, Value>,
     http_response: &http::Response,
     context_dir: &ContextDir,
+    update_snapshots: bool,
+    json_match: bool,
 ) -> Vec<AssertResult> {
     let mut asserts = vec![];
 
@@ -142,17 +146,56 @@ pub fn eval_asserts(
     }
 
     if let Some(body) = &response.body {
-        let assert = eval_implicit_body_asserts(body, variables, http_response, context_dir);
+        let assert = match &body.value {
+            Bytes::Json(value) if json_match => {
+                eval_implicit_json_match(body, value, variables, http_response)
+            }
+            _ => eval_implicit_body_asserts(body, variables, http_response, context_dir),
+        };
         asserts.push(assert);
     }
 
     for assert in response.asserts().iter() {
-        let assert_result = eval_assert(assert, variables, http_response, context_dir);
+        let assert_result = eval_assert(
+            assert,
+            variables,
+            http_response,
+            context_dir,
+            update_snapshots,
+            json_match,
+        );
         asserts.push(assert_result);
     }
     asserts
 }
 
+/// Matches structurally the body of an actual HTTP response against a JSON spec body `value`,
+/// given a set of variables.
+fn eval_implicit_json_match(
+    spec_body: &Body,
+    value: &JsonValue,
+    variables: &HashMap<String, Value>,
+    http_response: &http::Response,
+) -> AssertResult {
+    let source_info = SourceInfo {
+        start: spec_body.space0.source_info.end,
+        end: spec_body.space0.source_info.end,
+    };
+    let mismatches = json_match::eval_pattern(value, variables, true).and_then(|pattern| {
+        let actual = http_response
+            .text()
+            .map_err(|e| Error::new(source_info, e.into(), true))?;
+        let actual = serde_json::from_str::<serde_json::Value>(&actual)
+            .map_err(|_| Error::new(source_info, RunnerError::QueryInvalidJson, true))?;
+        let mismatches = json_match::diff(&pattern, &actual, true);
+        Ok(mismatches.iter().map(|m| m.to_string()).collect())
+    });
+    AssertResult::JsonBody {
+        mismatches,
+        source_info,
+    }
+}
+
 /// Check the body of an actual HTTP response against a spec body, given a set of variables.
 fn eval_implicit_body_asserts(
     spec_body: &Body,
@@ -397,6 +440,8 @@ mod tests {
                 &variables,
                 &http::xml_two_users_http_response(),
                 &context_dir,
+                false,
+                false,
             ),
             vec![AssertResult::Explicit {
                 actual: Ok(Some(Value::Number(Number::Integer(2)))),
@@ -15,10 +15,15 @@
  * limitations under the License.
  *
  */
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
 use hurl_core::ast::{Pos, SourceInfo};
 
-use crate::http::{Call, Cookie};
+use crate::http::{Call, Cookie, RequestSpec};
 use crate::runner::error::Error;
+use crate::runner::include::IncludedFile;
 use crate::runner::output::Output;
 use crate::runner::value::Value;
 
@@ -29,6 +34,8 @@ pub struct HurlResult {
     pub success: bool,
     pub cookies: Vec<Cookie>,
     pub timestamp: i64,
+    /// Secret values of this run (`--secret` values and redacted captures), hidden in reports
+    pub secrets: Vec<String>,
 }
 
 impl HurlResult {
@@ -37,16 +44,24 @@ impl HurlResult {
     /// The errors are only the "effective" ones: those that are due to retry are
     /// ignored.
     pub fn errors(&self) -> Vec<&Error> {
+        self.entry_errors().into_iter().map(|(_, e)| e).collect()
+    }
+
+    /// Returns all the effective errors of this `HurlResult`, with the entry they belong to.
+    ///
+    /// The entry gives the file where the error is located, see [`EntryResult::file`].
+    pub fn entry_errors(&self) -> Vec<(&EntryResult, &Error)> {
         let mut errors = vec![];
         let mut next_entries = self.entries.iter().skip(1);
         for entry in self.entries.iter() {
-            match next_entries.next() {
-                None => errors.extend(&entry.errors),
+            let effective = match next_entries.next() {
+                None => true,
                 Some(next) => {
-                    if next.entry_index != entry.entry_index {
-                        errors.extend(&entry.errors)
-                    }
+                    next.entry_index != entry.entry_index || next.iteration != entry.iteration
                 }
+            };
+            if effective {
+                errors.extend(entry.errors.iter().map(|e| (entry, e)));
             }
         }
         errors
@@ -56,11 +71,24 @@ impl HurlResult {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EntryResult {
     pub entry_index: usize,
+    /// Iteration of the entry, starting at 1, when it's run several times with a `repeat` or a
+    /// `for-each` option
+    pub iteration: Option<usize>,
+    /// Name of the entry, defined with the `name` option
+    pub name: Option<String>,
+    /// Tags of the entry, defined with `tag` options
+    pub tags: Vec<String>,
+    /// The file this entry has been included from, `None` for entries of the main file
+    pub file: Option<Arc<IncludedFile>>,
     pub source_info: SourceInfo,
     pub calls: Vec<Call>,
     pub captures: Vec<CaptureResult>,
     pub asserts: Vec<AssertResult>,
     pub errors: Vec<Error>,
+    /// The curl command line of the request, empty if the request has not been executed
+    pub curl_cmd: String,
+    /// The retry of the entry, when this attempt has failed and the entry is run again
+    pub retry: Option<RetryAttempt>,
     pub time_in_ms: u128,
     // The entry has been executed with `--compressed` option:
     // server is requested to send compressed response, and the response should be uncompressed
@@ -68,6 +96,63 @@ pub struct EntryResult {
     pub compressed: bool,
 }
 
+/// The requests of a Hurl file, rendered by a dry run without being sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DryRunResult {
+    pub entries: Vec<DryRunEntry>,
+    /// Have all the selected entries been rendered?
+    pub success: bool,
+    /// Secret values of this run, hidden in the output
+    pub secrets: Vec<String>,
+}
+
+/// The request of an entry, rendered by a dry run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DryRunEntry {
+    pub entry_index: usize,
+    /// Iteration of the entry, starting at 1, when it's run several times with a `repeat` or a
+    /// `for-each` option
+    pub iteration: Option<usize>,
+    /// The file this entry has been included from, `None` for entries of the main file
+    pub file: Option<Arc<IncludedFile>>,
+    pub source_info: SourceInfo,
+    pub request: RequestSpec,
+    /// The curl command line of the request
+    pub curl: String,
+}
+
+/// A retry of an entry, after a failed attempt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryAttempt {
+    /// Number of the failed attempt, starting at 1
+    pub attempt: usize,
+    pub reason: RetryReason,
+    /// Delay before the next attempt
+    pub delay: Duration,
+}
+
+/// Why an entry is retried.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RetryReason {
+    /// An assert of the entry has failed
+    Assert,
+    /// The entry has failed with an error that's not an assert (HTTP error, capture error etc...)
+    Error,
+    /// The response has a 429 or 503 status code and a `Retry-After` header
+    RetryAfter,
+}
+
+impl fmt::Display for RetryReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            RetryReason::Assert => "assert",
+            RetryReason::Error => "error",
+            RetryReason::RetryAfter => "retry-after",
+        };
+        write!(f, "{reason}")
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum AssertResult {
     Version {
@@ -90,6 +175,11 @@ pub enum AssertResult {
         expected: Result<Value, Error>,
         source_info: SourceInfo,
     },
+    /// A JSON body matched structurally, with the mismatches between the actual and expected body.
+    JsonBody {
+        mismatches: Result<Vec<String>, Error>,
+        source_info: SourceInfo,
+    },
     Explicit {
         actual: Result<Option<Value>, Error>,
         source_info: SourceInfo,
@@ -0,0 +1,238 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rand::Rng;
+
+use crate::http::Response;
+use crate::runner::{EntryResult, RetryAttempt, RetryReason, RunnerOptions};
+
+/// Returns the retry of an entry whose `attempt` (starting at 1) has failed with `entry_result`,
+/// the entry being run with `options`.
+///
+/// The delay before the next attempt is the retry interval, doubled after each attempt with an
+/// exponential backoff and bounded by the maximum retry interval. With a jitter, the delay is
+/// randomized between its half and its full value. If the `Retry-After` header is honoured, the
+/// server can also set the delay, still bounded by the maximum retry interval.
+///
+/// The jitter uses its own random generator, so that retries don't change the values of the
+/// template functions, even with a seed.
+pub fn new_attempt(
+    entry_result: &EntryResult,
+    attempt: usize,
+    options: &RunnerOptions,
+) -> RetryAttempt {
+    if options.retry_after {
+        let delay = entry_result
+            .calls
+            .last()
+            .and_then(|call| retry_after(&call.response));
+        if let Some(mut delay) = delay {
+            if let Some(max_interval) = options.retry_max_interval {
+                delay = delay.min(max_interval);
+            }
+            return RetryAttempt {
+                attempt,
+                reason: RetryReason::RetryAfter,
+                delay,
+            };
+        }
+    }
+
+    let reason = if entry_result.errors.iter().all(|e| e.assert) {
+        RetryReason::Assert
+    } else {
+        RetryReason::Error
+    };
+    let mut delay = options.retry_interval;
+    if options.retry_backoff {
+        let factor = 2_u32.saturating_pow(attempt.saturating_sub(1) as u32);
+        delay = delay.saturating_mul(factor);
+    }
+    if let Some(max_interval) = options.retry_max_interval {
+        delay = delay.min(max_interval);
+    }
+    if options.retry_jitter {
+        let millis = delay.as_millis() as u64;
+        delay = Duration::from_millis(rand::thread_rng().gen_range(millis / 2..=millis));
+    }
+    RetryAttempt {
+        attempt,
+        reason,
+        delay,
+    }
+}
+
+/// Returns the delay of the `Retry-After` header of a 429 (Too Many Requests) or 503 (Service
+/// Unavailable) `response`.
+///
+/// The header value is either a number of seconds, or an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    if response.status != 429 && response.status != 503 {
+        return None;
+    }
+    let values = response.get_header_values("Retry-After");
+    let value = values.first()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&Utc) - Utc::now();
+    // A date in the past means that we can retry now.
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Pos, SourceInfo};
+
+    use super::*;
+    use crate::http::{Call, Header, Request, Timings};
+    use crate::runner::{Error, RunnerError, RunnerOptionsBuilder};
+
+    fn new_entry_result(status: u32, headers: Vec<Header>, assert: bool) -> EntryResult {
+        let source_info = SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1));
+        let inner = RunnerError::AssertStatus {
+            actual: status.to_string(),
+        };
+        EntryResult {
+            entry_index: 1,
+            iteration: None,
+            name: None,
+            tags: vec![],
+            file: None,
+            source_info,
+            calls: vec![Call {
+                request: Request {
+                    url: "http://localhost:8000/retry".to_string(),
+                    method: "GET".to_string(),
+                    headers: vec![],
+                    body: vec![],
+                },
+                response: Response {
+                    status,
+                    headers,
+                    ..Default::default()
+                },
+                timings: Timings::default(),
+            }],
+            captures: vec![],
+            asserts: vec![],
+            errors: vec![Error::new(source_info, inner, assert)],
+            curl_cmd: String::new(),
+            retry: None,
+            time_in_ms: 0,
+            compressed: false,
+        }
+    }
+
+    #[test]
+    fn test_new_attempt_backoff() {
+        let entry_result = new_entry_result(500, vec![], true);
+        let options = RunnerOptionsBuilder::new()
+            .retry_interval(Duration::from_millis(100))
+            .retry_backoff(true)
+            .retry_max_interval(Some(Duration::from_millis(500)))
+            .build();
+        let delays = (1..=5)
+            .map(|attempt| {
+                new_attempt(&entry_result, attempt, &options)
+                    .delay
+                    .as_millis()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(delays, vec![100, 200, 400, 500, 500]);
+
+        let options = RunnerOptionsBuilder::new()
+            .retry_interval(Duration::from_millis(100))
+            .build();
+        assert_eq!(
+            new_attempt(&entry_result, 3, &options),
+            RetryAttempt {
+                attempt: 3,
+                reason: RetryReason::Assert,
+                delay: Duration::from_millis(100),
+            }
+        );
+    }
+
+    #[test]
+    fn test_new_attempt_jitter() {
+        let entry_result = new_entry_result(500, vec![], false);
+        let options = RunnerOptionsBuilder::new()
+            .retry_interval(Duration::from_millis(1000))
+            .retry_jitter(true)
+            .build();
+        let attempt = new_attempt(&entry_result, 1, &options);
+        assert_eq!(attempt.reason, RetryReason::Error);
+        assert!(attempt.delay >= Duration::from_millis(500));
+        assert!(attempt.delay <= Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_new_attempt_retry_after() {
+        let headers = vec![Header::new("Retry-After", "3")];
+        let options = RunnerOptionsBuilder::new().retry_after(true).build();
+
+        let entry_result = new_entry_result(429, headers.clone(), true);
+        assert_eq!(
+            new_attempt(&entry_result, 1, &options),
+            RetryAttempt {
+                attempt: 1,
+                reason: RetryReason::RetryAfter,
+                delay: Duration::from_secs(3),
+            }
+        );
+
+        // The server delay is bounded by the maximum retry interval.
+        let options = RunnerOptionsBuilder::new()
+            .retry_after(true)
+            .retry_max_interval(Some(Duration::from_secs(2)))
+            .build();
+        assert_eq!(
+            new_attempt(&entry_result, 1, &options).delay,
+            Duration::from_secs(2)
+        );
+
+        // `Retry-After` is only honoured on 429 and 503 responses.
+        let entry_result = new_entry_result(500, headers, true);
+        assert_eq!(
+            new_attempt(&entry_result, 1, &options).reason,
+            RetryReason::Assert
+        );
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let response = |value: &str| Response {
+            status: 503,
+            headers: vec![Header::new("Retry-After", value)],
+            ..Default::default()
+        };
+        assert_eq!(
+            retry_after(&response("120")),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after(&response("Wed, 21 Oct 2015 07:28:00 GMT")),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(retry_after(&response("soon")), None);
+    }
+}
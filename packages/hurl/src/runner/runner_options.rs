@@ -15,11 +15,13 @@
  * limitations under the License.
  *
  */
+use std::fmt;
 use std::time::Duration;
 
 use hurl_core::ast::{Entry, Retry};
 
-use crate::http::{IpResolve, RequestedHttpVersion};
+use crate::http::{Cassette, IpResolve, RequestedHttpVersion};
+use crate::openapi::OpenApi;
 use crate::util::path::ContextDir;
 
 pub struct RunnerOptionsBuilder {
@@ -34,25 +36,40 @@ pub struct RunnerOptionsBuilder {
     continue_on_error: bool,
     cookie_input_file: Option<String>,
     delay: Duration,
+    entry_name: Option<String>,
+    exclude_tags: Vec<String>,
     follow_location: bool,
+    from_entry: Option<FromEntry>,
     http_version: RequestedHttpVersion,
     ignore_asserts: bool,
     insecure: bool,
     ip_resolve: IpResolve,
+    json_match: bool,
     max_redirect: Option<usize>,
     no_proxy: Option<String>,
+    openapi: Option<OpenApi>,
     output: Option<String>,
     path_as_is: bool,
     post_entry: Option<fn() -> bool>,
     pre_entry: Option<fn(Entry) -> bool>,
     proxy: Option<String>,
+    repeat: Option<usize>,
+    replay: Option<Cassette>,
     resolves: Vec<String>,
     retry: Retry,
+    retry_after: bool,
+    retry_backoff: bool,
     retry_interval: Duration,
+    retry_jitter: bool,
+    retry_max_interval: Option<Duration>,
+    retry_max_time: Option<Duration>,
+    seed: Option<u64>,
     skip: bool,
     ssl_no_revoke: bool,
+    tags: Vec<String>,
     timeout: Duration,
     to_entry: Option<usize>,
+    update_snapshots: bool,
     user: Option<String>,
     user_agent: Option<String>,
 }
@@ -71,25 +88,40 @@ impl Default for RunnerOptionsBuilder {
             continue_on_error: false,
             cookie_input_file: None,
             delay: Duration::from_millis(0),
+            entry_name: None,
+            exclude_tags: vec![],
             follow_location: false,
+            from_entry: None,
             http_version: RequestedHttpVersion::default(),
             ignore_asserts: false,
             insecure: false,
             ip_resolve: IpResolve::default(),
+            json_match: false,
             max_redirect: Some(50),
             no_proxy: None,
+            openapi: None,
             output: None,
             path_as_is: false,
             post_entry: None,
             pre_entry: None,
             proxy: None,
+            repeat: None,
+            replay: None,
             resolves: vec![],
             retry: Retry::None,
+            retry_after: false,
+            retry_backoff: false,
             retry_interval: Duration::from_millis(1000),
+            retry_jitter: false,
+            retry_max_interval: None,
+            retry_max_time: None,
+            seed: None,
             skip: false,
             ssl_no_revoke: false,
+            tags: vec![],
             timeout: Duration::from_secs(300),
             to_entry: None,
+            update_snapshots: false,
             user: None,
             user_agent: None,
         }
@@ -182,6 +214,20 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Executes only the entry named `entry_name`.
+    pub fn entry_name(&mut self, entry_name: Option<String>) -> &mut Self {
+        self.entry_name = entry_name;
+        self
+    }
+
+    /// Sets the tags of the entries that are not executed.
+    ///
+    /// An entry with any of these tags is ignored, even if it also has a tag of [`self.tags()`].
+    pub fn exclude_tags(&mut self, exclude_tags: &[String]) -> &mut Self {
+        self.exclude_tags = exclude_tags.to_vec();
+        self
+    }
+
     /// Sets stopping or continuing executing requests to the end of the Hurl file even when an assert error occurs.
     ///
     /// By default, Hurl exits after an assert error in the HTTP response. Note that this option does
@@ -199,6 +245,14 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Executes Hurl file from `from_entry`, ignores the beginning of the file.
+    ///
+    /// The first entry to run is given by its index (starting at 1) or by its name.
+    pub fn from_entry(&mut self, from_entry: Option<FromEntry>) -> &mut Self {
+        self.from_entry = from_entry;
+        self
+    }
+
     /// Set requested HTTP version (can be different of the effective HTTP version).
     pub fn http_version(&mut self, version: RequestedHttpVersion) -> &mut Self {
         self.http_version = version;
@@ -223,6 +277,13 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Matches JSON bodies and JSON values structurally (subset objects, unordered arrays and
+    /// placeholders) instead of exactly.
+    pub fn json_match(&mut self, json_match: bool) -> &mut Self {
+        self.json_match = json_match;
+        self
+    }
+
     /// Set maximum number of redirection-followings allowed
     ///
     /// By default, the limit is set to 50 redirections
@@ -243,6 +304,12 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Sets the OpenAPI document against which the HTTP calls are checked.
+    pub fn openapi(&mut self, openapi: Option<OpenApi>) -> &mut Self {
+        self.openapi = openapi;
+        self
+    }
+
     /// Specifies the file to output the HTTP response instead of stdout.
     pub fn output(&mut self, output: Option<String>) -> &mut Self {
         self.output = output;
@@ -271,6 +338,12 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Sets the recorded calls whose responses are replayed instead of sending the requests.
+    pub fn replay(&mut self, replay: Option<Cassette>) -> &mut Self {
+        self.replay = replay;
+        self
+    }
+
     /// Provides a custom address for a specific host and port pair.
     pub fn resolves(&mut self, resolves: &[String]) -> &mut Self {
         self.resolves = resolves.to_vec();
@@ -285,6 +358,22 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Sets if the `Retry-After` header of a 429 or 503 response sets the duration before a retry.
+    ///
+    /// Default is false.
+    pub fn retry_after(&mut self, retry_after: bool) -> &mut Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Sets if the duration between each retry is doubled after each retry (exponential backoff).
+    ///
+    /// Default is false.
+    pub fn retry_backoff(&mut self, retry_backoff: bool) -> &mut Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
     /// Sets duration between each retry.
     ///
     /// Default is 1000 ms.
@@ -293,11 +382,51 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Sets if the duration between each retry is randomized, between its half and its full value.
+    ///
+    /// Default is false.
+    pub fn retry_jitter(&mut self, retry_jitter: bool) -> &mut Self {
+        self.retry_jitter = retry_jitter;
+        self
+    }
+
+    /// Sets the maximum duration between each retry, bounding the exponential backoff.
+    ///
+    /// Default is `None`: the duration is not bounded.
+    pub fn retry_max_interval(&mut self, retry_max_interval: Option<Duration>) -> &mut Self {
+        self.retry_max_interval = retry_max_interval;
+        self
+    }
+
+    /// Sets the maximum duration of the retries of an entry, since its first attempt.
+    ///
+    /// Default is `None`: the retries are only limited by their number.
+    pub fn retry_max_time(&mut self, retry_max_time: Option<Duration>) -> &mut Self {
+        self.retry_max_time = retry_max_time;
+        self
+    }
+
+    /// Sets the seed of the random values generated by the template functions.
+    ///
+    /// Default is `None`: random values are different on each run.
+    pub fn seed(&mut self, seed: Option<u64>) -> &mut Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn ssl_no_revoke(&mut self, ssl_no_revoke: bool) -> &mut Self {
         self.ssl_no_revoke = ssl_no_revoke;
         self
     }
 
+    /// Sets the tags of the entries to execute.
+    ///
+    /// If not empty, only the entries with at least one of these tags are executed.
+    pub fn tags(&mut self, tags: &[String]) -> &mut Self {
+        self.tags = tags.to_vec();
+        self
+    }
+
     /// Sets maximum time allowed for the transfer.
     ///
     /// Default 300 seconds.
@@ -312,6 +441,12 @@ impl RunnerOptionsBuilder {
         self
     }
 
+    /// Rewrites the snapshot files of `matchesSnapshot` asserts with the actual values.
+    pub fn update_snapshots(&mut self, update_snapshots: bool) -> &mut Self {
+        self.update_snapshots = update_snapshots;
+        self
+    }
+
     /// Adds basic Authentication header to each request.
     pub fn user(&mut self, user: Option<String>) -> &mut Self {
         self.user = user;
@@ -338,25 +473,40 @@ impl RunnerOptionsBuilder {
             context_dir: self.context_dir.clone(),
             continue_on_error: self.continue_on_error,
             cookie_input_file: self.cookie_input_file.clone(),
+            entry_name: self.entry_name.clone(),
+            exclude_tags: self.exclude_tags.clone(),
             follow_location: self.follow_location,
+            from_entry: self.from_entry.clone(),
             http_version: self.http_version,
             ignore_asserts: self.ignore_asserts,
             insecure: self.insecure,
             ip_resolve: self.ip_resolve,
+            json_match: self.json_match,
             max_redirect: self.max_redirect,
             no_proxy: self.no_proxy.clone(),
+            openapi: self.openapi.clone(),
             output: self.output.clone(),
             path_as_is: self.path_as_is,
             post_entry: self.post_entry,
             pre_entry: self.pre_entry,
             proxy: self.proxy.clone(),
+            repeat: self.repeat,
+            replay: self.replay.clone(),
             resolves: self.resolves.clone(),
             retry: self.retry,
+            retry_after: self.retry_after,
+            retry_backoff: self.retry_backoff,
             retry_interval: self.retry_interval,
+            retry_jitter: self.retry_jitter,
+            retry_max_interval: self.retry_max_interval,
+            retry_max_time: self.retry_max_time,
+            seed: self.seed,
             skip: self.skip,
             ssl_no_revoke: self.ssl_no_revoke,
+            tags: self.tags.clone(),
             timeout: self.timeout,
             to_entry: self.to_entry,
+            update_snapshots: self.update_snapshots,
             user: self.user.clone(),
             user_agent: self.user_agent.clone(),
         }
@@ -376,25 +526,40 @@ pub struct RunnerOptions {
     pub(crate) context_dir: ContextDir,
     pub(crate) continue_on_error: bool,
     pub(crate) cookie_input_file: Option<String>,
+    pub(crate) entry_name: Option<String>,
+    pub(crate) exclude_tags: Vec<String>,
     pub(crate) follow_location: bool,
+    pub(crate) from_entry: Option<FromEntry>,
     pub(crate) http_version: RequestedHttpVersion,
     pub(crate) ignore_asserts: bool,
     pub(crate) ip_resolve: IpResolve,
     pub(crate) insecure: bool,
+    pub(crate) json_match: bool,
     pub(crate) max_redirect: Option<usize>,
     pub(crate) no_proxy: Option<String>,
+    pub(crate) openapi: Option<OpenApi>,
     pub(crate) output: Option<String>,
     pub(crate) path_as_is: bool,
     pub(crate) post_entry: Option<fn() -> bool>,
     pub(crate) pre_entry: Option<fn(Entry) -> bool>,
     pub(crate) proxy: Option<String>,
+    pub(crate) repeat: Option<usize>,
+    pub(crate) replay: Option<Cassette>,
     pub(crate) resolves: Vec<String>,
     pub(crate) retry: Retry,
+    pub(crate) retry_after: bool,
+    pub(crate) retry_backoff: bool,
     pub(crate) retry_interval: Duration,
+    pub(crate) retry_jitter: bool,
+    pub(crate) retry_max_interval: Option<Duration>,
+    pub(crate) retry_max_time: Option<Duration>,
+    pub(crate) seed: Option<u64>,
     pub(crate) skip: bool,
     pub(crate) ssl_no_revoke: bool,
+    pub(crate) tags: Vec<String>,
     pub(crate) timeout: Duration,
     pub(crate) to_entry: Option<usize>,
+    pub(crate) update_snapshots: bool,
     pub(crate) user: Option<String>,
     pub(crate) user_agent: Option<String>,
 }
@@ -404,3 +569,21 @@ impl Default for RunnerOptions {
         RunnerOptionsBuilder::default().build()
     }
 }
+
+/// The first entry of a Hurl file to run, see [`RunnerOptionsBuilder::from_entry`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FromEntry {
+    /// Index of the entry, starting at 1.
+    Index(usize),
+    /// Name of the entry, the first entry with this name is used.
+    Name(String),
+}
+
+impl fmt::Display for FromEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FromEntry::Index(index) => write!(f, "{index}"),
+            FromEntry::Name(name) => write!(f, "<{name}>"),
+        }
+    }
+}
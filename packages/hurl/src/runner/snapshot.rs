@@ -0,0 +1,284 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Snapshot testing: actual values are compared against the content of snapshot files.
+
+use std::path::PathBuf;
+
+use hurl_core::ast::SourceInfo;
+use similar::{Algorithm, ChangeTag, TextDiff};
+
+use crate::runner::error::Error;
+use crate::runner::value::Value;
+use crate::runner::RunnerError;
+use crate::util::path::ContextDir;
+
+/// Number of unchanged lines displayed around the changes of a diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// Returns the snapshot text of an actual `value`, `None` if this value can't be snapshotted.
+///
+/// JSON values (objects, lists, or strings holding a JSON object or list) are pretty printed so
+/// that snapshots can be reviewed and diffed line by line.
+pub fn snapshot_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => match serde_json::from_str::<serde_json::Value>(s) {
+            Ok(json) if json.is_object() || json.is_array() => Some(pretty(&json)),
+            _ => Some(s.clone()),
+        },
+        Value::Bytes(bytes) => String::from_utf8(bytes.clone()).ok(),
+        Value::List(_) | Value::Object(_) => Some(pretty(&value.to_json())),
+        Value::Bool(_) | Value::Null | Value::Number(_) => Some(value.to_json().to_string()),
+        Value::Date(date) => Some(date.to_string()),
+        Value::Nodeset(_) | Value::Regex(_) | Value::Unit => None,
+    }
+}
+
+fn pretty(json: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(json).unwrap()
+}
+
+/// Compares the snapshot text of an actual value `actual` to the snapshot file `filename`.
+///
+/// Returns whether the actual value matches the snapshot, and the snapshot text. If
+/// `update_snapshots` is true, the snapshot file is (re)written with `actual` and the actual value
+/// always matches.
+pub fn eval_snapshot(
+    filename: &str,
+    source_info: SourceInfo,
+    actual: &str,
+    context_dir: &ContextDir,
+    update_snapshots: bool,
+) -> Result<(bool, String), Error> {
+    // As with files in request bodies, snapshots must be under the file root.
+    if !context_dir.is_access_allowed(filename) {
+        let inner = RunnerError::UnauthorizedFileAccess {
+            path: PathBuf::from(filename),
+        };
+        return Err(Error::new(source_info, inner, false));
+    }
+    let path = context_dir.get_path(filename);
+    let expected = std::fs::read_to_string(&path).ok();
+    if let Some(expected) = &expected {
+        if matches(expected, actual) {
+            return Ok((true, expected.clone()));
+        }
+    }
+    if update_snapshots {
+        if let Err(e) = write_snapshot(&path, actual) {
+            let inner = RunnerError::FileWriteAccess {
+                file: filename.to_string(),
+                error: e.to_string(),
+            };
+            return Err(Error::new(source_info, inner, false));
+        }
+        return Ok((true, actual.to_string()));
+    }
+    match expected {
+        Some(expected) => Ok((false, expected)),
+        None => {
+            let inner = RunnerError::FileReadAccess {
+                file: filename.to_string(),
+            };
+            Err(Error::new(source_info, inner, false))
+        }
+    }
+}
+
+fn write_snapshot(path: &std::path::Path, text: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if text.ends_with('\n') {
+        std::fs::write(path, text)
+    } else {
+        std::fs::write(path, format!("{text}\n"))
+    }
+}
+
+/// Returns true if an `actual` snapshot text matches an `expected` snapshot text.
+///
+/// When both texts are JSON, they are compared as JSON values (so whitespaces and object keys
+/// order are not significant), otherwise they are compared as text, ignoring the trailing newline.
+fn matches(expected: &str, actual: &str) -> bool {
+    let expected_json = serde_json::from_str::<serde_json::Value>(expected);
+    let actual_json = serde_json::from_str::<serde_json::Value>(actual);
+    match (expected_json, actual_json) {
+        (Ok(expected), Ok(actual)) => expected == actual,
+        _ => trim_newline(expected) == trim_newline(actual),
+    }
+}
+
+fn trim_newline(text: &str) -> &str {
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    text.strip_suffix('\r').unwrap_or(text)
+}
+
+/// Returns a line diff between an `expected` and an `actual` text.
+///
+/// Lines only in `expected` are prefixed with `-`, lines only in `actual` with `+`. Unchanged
+/// lines are only displayed around changes, skipped lines being replaced by `...`.
+pub fn diff(expected: &str, actual: &str) -> String {
+    let expected = trim_newline(expected).lines().collect::<Vec<_>>();
+    let actual = trim_newline(actual).lines().collect::<Vec<_>>();
+    let ops = diff_ops(&expected, &actual);
+
+    // Indices of the changed lines, used to select the unchanged lines to display.
+    let changes = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, DiffOp::Same(_)))
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    let is_visible = |index: usize| {
+        changes
+            .iter()
+            .any(|c| index + DIFF_CONTEXT >= *c && index <= c + DIFF_CONTEXT)
+    };
+
+    let mut lines = vec![];
+    let mut skipped = false;
+    for (index, op) in ops.iter().enumerate() {
+        if !is_visible(index) {
+            if !skipped {
+                lines.push("  ...".to_string());
+                skipped = true;
+            }
+            continue;
+        }
+        skipped = false;
+        let line = match op {
+            DiffOp::Same(line) => format!("  {line}"),
+            DiffOp::Removed(line) => format!("- {line}"),
+            DiffOp::Added(line) => format!("+ {line}"),
+        };
+        lines.push(line);
+    }
+    lines.join("\n")
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum DiffOp<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Returns the operations transforming `expected` lines into `actual` lines, using the Myers diff
+/// algorithm (linear in space, so large snapshots can be diffed).
+fn diff_ops<'a>(expected: &[&'a str], actual: &[&'a str]) -> Vec<DiffOp<'a>> {
+    TextDiff::configure()
+        .algorithm(Algorithm::Myers)
+        .diff_slices(expected, actual)
+        .iter_all_changes()
+        .map(|change| match change.tag() {
+            ChangeTag::Equal => DiffOp::Same(change.value()),
+            ChangeTag::Delete => DiffOp::Removed(change.value()),
+            ChangeTag::Insert => DiffOp::Added(change.value()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_text() {
+        assert_eq!(
+            snapshot_text(&Value::String(r#"{"id":1,"tags":["a"]}"#.to_string())).unwrap(),
+            "{\n  \"id\": 1,\n  \"tags\": [\n    \"a\"\n  ]\n}"
+        );
+        assert_eq!(
+            snapshot_text(&Value::String("Hello".to_string())).unwrap(),
+            "Hello"
+        );
+        assert_eq!(snapshot_text(&Value::Bool(true)).unwrap(), "true");
+        assert_eq!(snapshot_text(&Value::Nodeset(2)), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        assert!(matches("{\"a\": 1, \"b\": 2}\n", "{\"b\":2,\"a\":1}"));
+        assert!(!matches("{\"a\": 1}", "{\"a\": 2}"));
+        assert!(matches("Hello\n", "Hello"));
+        assert!(!matches("Hello", "Hello\n\n"));
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = "{\n  \"id\": 1,\n  \"name\": \"Bob\",\n  \"age\": 30\n}\n";
+        let actual = "{\n  \"id\": 1,\n  \"name\": \"Alice\",\n  \"age\": 30\n}";
+        assert_eq!(
+            diff(expected, actual),
+            r#"  {
+    "id": 1,
+-   "name": "Bob",
++   "name": "Alice",
+    "age": 30
+  }"#
+        );
+
+        let expected = (1..=10).map(|i| i.to_string()).collect::<Vec<_>>();
+        let mut actual = expected.clone();
+        actual[0] = "one".to_string();
+        actual.push("11".to_string());
+        assert_eq!(
+            diff(&expected.join("\n"), &actual.join("\n")),
+            "- 1\n+ one\n  2\n  3\n  4\n  ...\n  8\n  9\n  10\n+ 11"
+        );
+    }
+
+    #[test]
+    fn test_eval_snapshot() {
+        let dir =
+            std::env::temp_dir().join(format!("hurl_test_eval_snapshot_{}", uuid::Uuid::new_v4()));
+        let context_dir = ContextDir::new(&dir, &dir);
+        let source_info = SourceInfo::new(
+            hurl_core::ast::Pos::new(1, 1),
+            hurl_core::ast::Pos::new(1, 1),
+        );
+        let filename = "snapshots/test.json";
+
+        let error = eval_snapshot(filename, source_info, "{}", &context_dir, false).unwrap_err();
+        assert_eq!(
+            error.inner,
+            RunnerError::FileReadAccess {
+                file: filename.to_string()
+            }
+        );
+        assert_eq!(
+            eval_snapshot(filename, source_info, "{}", &context_dir, true).unwrap(),
+            (true, "{}".to_string())
+        );
+        assert_eq!(
+            eval_snapshot(filename, source_info, "{ }", &context_dir, false).unwrap(),
+            (true, "{}\n".to_string())
+        );
+        assert_eq!(
+            eval_snapshot(filename, source_info, "[]", &context_dir, false).unwrap(),
+            (false, "{}\n".to_string())
+        );
+        assert!(matches!(
+            eval_snapshot("../test.json", source_info, "{}", &context_dir, true)
+                .unwrap_err()
+                .inner,
+            RunnerError::UnauthorizedFileAccess { .. }
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -17,9 +17,11 @@
  */
 use std::collections::HashMap;
 
+use chrono::SecondsFormat;
 use hurl_core::ast::*;
 
 use crate::runner::error::{Error, RunnerError};
+use crate::runner::expr::eval_expr;
 use crate::runner::Value;
 
 /// Renders to string a `template` given a map of variables.
@@ -49,30 +51,24 @@ fn eval_template_element(
 }
 
 pub fn render_expression(expr: &Expr, variables: &HashMap<String, Value>) -> Result<String, Error> {
-    let source_info = expr.variable.source_info;
-    let name = &expr.variable.name;
+    let source_info = expr.source_info();
     let value = eval_expression(expr, variables)?;
-    if value.is_renderable() {
-        Ok(value.to_string())
-    } else {
-        let inner = RunnerError::UnrenderableVariable {
-            name: name.to_string(),
-            value: value.to_string(),
-        };
-        Err(Error::new(source_info, inner, false))
+    match value {
+        // Dates, returned by the `now()` function for instance, are rendered in RFC 3339 format.
+        Value::Date(date) => Ok(date.to_rfc3339_opts(SecondsFormat::Millis, true)),
+        value if value.is_renderable() => Ok(value.to_string()),
+        value => {
+            let inner = RunnerError::UnrenderableVariable {
+                name: expr.to_string(),
+                value: value.to_string(),
+            };
+            Err(Error::new(source_info, inner, false))
+        }
     }
 }
 
 pub fn eval_expression(expr: &Expr, variables: &HashMap<String, Value>) -> Result<Value, Error> {
-    let source_info = expr.variable.source_info;
-    let name = &expr.variable.name;
-    match variables.get(name.as_str()) {
-        Some(value) => Ok(value.clone()),
-        _ => {
-            let inner = RunnerError::TemplateVariableNotDefined { name: name.clone() };
-            Err(Error::new(source_info, inner, false))
-        }
-    }
+    eval_expr(expr, variables)
 }
 
 impl Value {
@@ -98,10 +94,12 @@ mod tests {
                 value: String::new(),
                 source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3)),
             },
-            variable: Variable {
+            kind: ExprKind::Variable(Variable {
                 name: "name".to_string(),
                 source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 7)),
-            },
+            }),
+            default: None,
+            filters: vec![],
             space1: Whitespace {
                 value: String::new(),
                 source_info: SourceInfo::new(Pos::new(1, 7), Pos::new(1, 7)),
@@ -167,10 +165,12 @@ mod tests {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
             },
-            variable: Variable {
+            kind: ExprKind::Variable(Variable {
                 name: "status".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-            },
+            }),
+            default: None,
+            filters: vec![],
             space1: Whitespace {
                 value: "".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
@@ -0,0 +1,597 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! Checks, before any request is sent, that the variables used by the templates of a Hurl file
+//! are defined. The variables of an entry that may be skipped are checked once its condition has
+//! been evaluated.
+use std::collections::{HashMap, HashSet};
+
+use hurl_core::ast::{
+    BooleanOption, Bytes, Capture, ConditionOption, CookiePath, Entry, Expr, ExprKind, Filter,
+    FilterValue, JsonValue, KeyValue, MultilineString, MultipartParam, NaturalOption, OptionKind,
+    PredicateFuncValue, PredicateValue, Query, QueryValue, RegexValue, RetryOption, Section,
+    SectionValue, Template, TemplateElement, VariableValue,
+};
+
+use crate::runner::error::{Error, RunnerError};
+use crate::runner::json_match::Placeholder;
+use crate::runner::options::FOR_EACH_VARIABLE;
+use crate::runner::Value;
+
+/// Returns the errors of the variables used by `entries` that are not defined, along with the
+/// index of the entry where they're used.
+///
+/// A variable is defined by `variables` (command line, variables files etc...), by a `variable`
+/// option or by a capture of a previous entry. An undefined variable is reported once, at its
+/// first use. Variables with a default value don't need to be defined.
+///
+/// Entries skipped by a literal `skip: true` or `only-if: false` are not checked, except for
+/// their options that are always evaluated. Entries with a `skip` or `only-if` expression may not
+/// be run: their variables are checked with [`check_conditional`], once their condition is known.
+pub fn check(
+    entries: &[(usize, &Entry)],
+    variables: &HashMap<String, Value>,
+) -> Vec<(usize, Error)> {
+    let mut checker = Checker::new(variables);
+    let mut errors = vec![];
+    for (entry_index, entry) in entries {
+        checker.entry(entry);
+        errors.extend(checker.errors.drain(..).map(|e| (*entry_index, e)));
+        // A capture whose name is a template can define any variable: we can't go further.
+        if checker.dynamic {
+            break;
+        }
+    }
+    errors
+}
+
+/// Returns the errors of the variables used by `entry` that are not defined by `variables`, if
+/// the entry has a `skip` or `only-if` expression.
+///
+/// This check is done at runtime, before running an entry whose condition has been evaluated.
+pub fn check_conditional(entry: &Entry, variables: &HashMap<String, Value>) -> Vec<Error> {
+    if condition(entry) != Condition::Runtime {
+        return vec![];
+    }
+    let mut checker = Checker::new(variables);
+    checker.options(entry);
+    checker.request_response(entry);
+    checker.errors
+}
+
+/// When an entry is run, according to its `skip` and `only-if` options.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Condition {
+    Always,
+    Never,
+    /// The entry is run depending on a `skip` or `only-if` expression.
+    Runtime,
+}
+
+/// Returns when `entry` is run according to its `skip` and `only-if` options.
+fn condition(entry: &Entry) -> Condition {
+    let mut condition = Condition::Always;
+    for option in entry.request.options() {
+        match &option.kind {
+            OptionKind::Skip(ConditionOption::Literal(true))
+            | OptionKind::OnlyIf(ConditionOption::Literal(false)) => return Condition::Never,
+            OptionKind::Skip(ConditionOption::Literal(false))
+            | OptionKind::OnlyIf(ConditionOption::Literal(true)) => {}
+            OptionKind::Skip(_) | OptionKind::OnlyIf(_) => condition = Condition::Runtime,
+            _ => {}
+        }
+    }
+    condition
+}
+
+struct Checker {
+    /// Variables defined so far
+    defined: HashSet<String>,
+    /// Undefined variables already reported
+    reported: HashSet<String>,
+    /// Errors of the current entry
+    errors: Vec<Error>,
+    /// Has a capture with a templated name been found?
+    dynamic: bool,
+    /// Are undefined variables not reported, for an entry that may not be run?
+    lenient: bool,
+}
+
+impl Checker {
+    fn new(variables: &HashMap<String, Value>) -> Checker {
+        Checker {
+            defined: variables.keys().cloned().collect(),
+            reported: HashSet::new(),
+            errors: vec![],
+            dynamic: false,
+            lenient: false,
+        }
+    }
+
+    /// Checks `entry`, in the order its templates are rendered at runtime.
+    ///
+    /// The options of an entry are always evaluated, even if the entry is skipped. The variables
+    /// defined by an entry that may not be run are considered as defined.
+    fn entry(&mut self, entry: &Entry) {
+        let condition = condition(entry);
+        self.options(entry);
+        match condition {
+            Condition::Always => self.request_response(entry),
+            Condition::Never => {}
+            Condition::Runtime => {
+                self.lenient = true;
+                self.request_response(entry);
+                self.lenient = false;
+            }
+        }
+    }
+
+    fn options(&mut self, entry: &Entry) {
+        let request = &entry.request;
+        // The current element of a `for-each` option is defined before the other options.
+        for option in request.options() {
+            if let OptionKind::ForEach(expr) = &option.kind {
+                self.expr(expr);
+                self.defined.insert(FOR_EACH_VARIABLE.to_string());
+            }
+        }
+        for option in request.options() {
+            self.option(&option.kind);
+        }
+    }
+
+    fn request_response(&mut self, entry: &Entry) {
+        let request = &entry.request;
+        self.template(&request.url);
+        request.headers.iter().for_each(|h| self.key_value(h));
+        request.sections.iter().for_each(|s| self.section(s));
+        if let Some(body) = &request.body {
+            self.bytes(&body.value);
+        }
+
+        let response = match &entry.response {
+            Some(response) => response,
+            None => return,
+        };
+        // Captures are computed before the asserts, that can use them.
+        response.captures().iter().for_each(|c| self.capture(c));
+        response.headers.iter().for_each(|h| self.key_value(h));
+        for assert in response.asserts() {
+            self.query(&assert.query);
+            assert.filters.iter().for_each(|(_, f)| self.filter(f));
+            self.predicate_func_value(&assert.predicate.predicate_func.value);
+        }
+        if let Some(body) = &response.body {
+            match &body.value {
+                Bytes::Json(value) => self.expected_json_value(value),
+                value => self.bytes(value),
+            }
+        }
+    }
+
+    fn option(&mut self, option: &OptionKind) {
+        match option {
+            OptionKind::AwsSigV4(value)
+            | OptionKind::ConnectTo(value)
+            | OptionKind::Proxy(value)
+            | OptionKind::Resolve(value) => self.template(value),
+            OptionKind::Compressed(value)
+            | OptionKind::Http10(value)
+            | OptionKind::Http11(value)
+            | OptionKind::Http2(value)
+            | OptionKind::Http3(value)
+            | OptionKind::Insecure(value)
+            | OptionKind::IpV4(value)
+            | OptionKind::IpV6(value)
+            | OptionKind::JsonMatch(value)
+            | OptionKind::FollowLocation(value)
+            | OptionKind::PathAsIs(value)
+            | OptionKind::RetryAfter(value)
+            | OptionKind::RetryBackoff(value)
+            | OptionKind::RetryJitter(value)
+            | OptionKind::Verbose(value)
+            | OptionKind::VeryVerbose(value) => {
+                if let BooleanOption::Expression(expr) = value {
+                    self.expr(expr);
+                }
+            }
+            OptionKind::Delay(value)
+            | OptionKind::MaxRedirect(value)
+            | OptionKind::Repeat(value)
+            | OptionKind::RetryInterval(value)
+            | OptionKind::RetryMaxInterval(value)
+            | OptionKind::RetryMaxTime(value) => {
+                if let NaturalOption::Expression(expr) = value {
+                    self.expr(expr);
+                }
+            }
+            OptionKind::Retry(value) => {
+                if let RetryOption::Expression(expr) = value {
+                    self.expr(expr);
+                }
+            }
+            OptionKind::OnlyIf(value) | OptionKind::Skip(value) => match value {
+                ConditionOption::Literal(_) => {}
+                ConditionOption::Expression(expr) => self.expr(expr),
+                ConditionOption::Predicate(condition) => {
+                    self.expr(&condition.expr);
+                    self.predicate_func_value(&condition.predicate.predicate_func.value);
+                }
+            },
+            OptionKind::Variable(definition) => {
+                self.variable_value(&definition.value);
+                self.defined.insert(definition.name.clone());
+            }
+            OptionKind::CaCertificate(_)
+            | OptionKind::ClientCert(_)
+            | OptionKind::ClientKey(_)
+            | OptionKind::ForEach(_)
+            | OptionKind::Include(_)
+            | OptionKind::Name(_)
+            | OptionKind::Output(_)
+            | OptionKind::Tag(_) => {}
+        }
+    }
+
+    fn section(&mut self, section: &Section) {
+        match &section.value {
+            SectionValue::QueryParams(params) | SectionValue::FormParams(params) => {
+                params.iter().for_each(|p| self.key_value(p));
+            }
+            SectionValue::BasicAuth(Some(key_value)) => self.key_value(key_value),
+            SectionValue::MultipartFormData(params) => {
+                for param in params {
+                    match param {
+                        MultipartParam::Param(key_value) => self.key_value(key_value),
+                        MultipartParam::FileParam(file_param) => self.template(&file_param.key),
+                    }
+                }
+            }
+            SectionValue::Cookies(cookies) => {
+                for cookie in cookies {
+                    self.template(&cookie.name);
+                    self.template(&cookie.value);
+                }
+            }
+            // Options are checked first, captures and asserts with the response.
+            SectionValue::BasicAuth(None)
+            | SectionValue::Options(_)
+            | SectionValue::Captures(_)
+            | SectionValue::Asserts(_) => {}
+        }
+    }
+
+    fn capture(&mut self, capture: &Capture) {
+        self.template(&capture.name);
+        self.query(&capture.query);
+        capture.filters.iter().for_each(|(_, f)| self.filter(f));
+        let is_static = capture
+            .name
+            .elements
+            .iter()
+            .all(|e| matches!(e, TemplateElement::String { .. }));
+        if is_static {
+            self.defined.insert(capture.name.to_string());
+        } else {
+            self.dynamic = true;
+        }
+    }
+
+    fn query(&mut self, query: &Query) {
+        match &query.value {
+            QueryValue::Header { name, .. } | QueryValue::Variable { name, .. } => {
+                self.template(name);
+            }
+            QueryValue::Cookie {
+                expr: CookiePath { name, .. },
+                ..
+            } => self.template(name),
+            QueryValue::Xpath { expr, .. } | QueryValue::Jsonpath { expr, .. } => {
+                self.template(expr);
+            }
+            QueryValue::Regex { value, .. } => self.regex_value(value),
+            QueryValue::Status
+            | QueryValue::Url
+            | QueryValue::Body
+            | QueryValue::Duration
+            | QueryValue::Bytes
+            | QueryValue::Sha256
+            | QueryValue::Md5
+            | QueryValue::Certificate { .. } => {}
+        }
+    }
+
+    fn filter(&mut self, filter: &Filter) {
+        match &filter.value {
+            FilterValue::Decode { encoding: t, .. }
+            | FilterValue::Format { fmt: t, .. }
+            | FilterValue::JsonPath { expr: t, .. }
+            | FilterValue::Split { sep: t, .. }
+            | FilterValue::ToDate { fmt: t, .. }
+            | FilterValue::XPath { expr: t, .. } => self.template(t),
+            FilterValue::Regex { value, .. } => self.regex_value(value),
+            FilterValue::Replace {
+                old_value,
+                new_value,
+                ..
+            } => {
+                self.regex_value(old_value);
+                self.template(new_value);
+            }
+            FilterValue::Count
+            | FilterValue::DaysAfterNow
+            | FilterValue::DaysBeforeNow
+            | FilterValue::HtmlEscape
+            | FilterValue::HtmlUnescape
+            | FilterValue::Nth { .. }
+            | FilterValue::ToInt
+            | FilterValue::UrlDecode
+            | FilterValue::UrlEncode => {}
+        }
+    }
+
+    fn predicate_func_value(&mut self, value: &PredicateFuncValue) {
+        match value {
+            PredicateFuncValue::Equal { value, .. }
+            | PredicateFuncValue::NotEqual { value, .. }
+            | PredicateFuncValue::GreaterThan { value, .. }
+            | PredicateFuncValue::GreaterThanOrEqual { value, .. }
+            | PredicateFuncValue::LessThan { value, .. }
+            | PredicateFuncValue::LessThanOrEqual { value, .. }
+            | PredicateFuncValue::StartWith { value, .. }
+            | PredicateFuncValue::EndWith { value, .. }
+            | PredicateFuncValue::Contain { value, .. }
+            | PredicateFuncValue::Include { value, .. }
+            | PredicateFuncValue::Match { value, .. }
+            | PredicateFuncValue::MatchSnapshot { value, .. }
+            | PredicateFuncValue::MatchSchema { value, .. }
+            | PredicateFuncValue::MatchXmlSchema { value, .. } => self.predicate_value(value),
+            PredicateFuncValue::IsInteger
+            | PredicateFuncValue::IsFloat
+            | PredicateFuncValue::IsBoolean
+            | PredicateFuncValue::IsString
+            | PredicateFuncValue::IsCollection
+            | PredicateFuncValue::IsDate
+            | PredicateFuncValue::Exist
+            | PredicateFuncValue::IsEmpty => {}
+        }
+    }
+
+    fn predicate_value(&mut self, value: &PredicateValue) {
+        match value {
+            PredicateValue::Expression(expr) => self.expr(expr),
+            PredicateValue::String(template) => self.template(template),
+            PredicateValue::MultilineString(value) => self.multiline_string(value),
+            PredicateValue::Json(value) => self.expected_json_value(value),
+            PredicateValue::Base64(_)
+            | PredicateValue::Bool(_)
+            | PredicateValue::File(_)
+            | PredicateValue::Hex(_)
+            | PredicateValue::Null
+            | PredicateValue::Number(_)
+            | PredicateValue::Regex(_) => {}
+        }
+    }
+
+    fn bytes(&mut self, bytes: &Bytes) {
+        match bytes {
+            Bytes::Json(value) => self.json_value(value),
+            Bytes::MultilineString(value) => self.multiline_string(value),
+            Bytes::OnelineString(template) => self.template(template),
+            Bytes::Xml(_) | Bytes::Base64(_) | Bytes::File(_) | Bytes::Hex(_) => {}
+        }
+    }
+
+    fn multiline_string(&mut self, value: &MultilineString) {
+        self.template(&value.value());
+        if let MultilineString::GraphQl(graphql) = value {
+            if let Some(variables) = &graphql.variables {
+                self.json_value(&variables.value);
+            }
+        }
+    }
+
+    fn json_value(&mut self, value: &JsonValue) {
+        match value {
+            JsonValue::Expression(expr) => self.expr(expr),
+            JsonValue::String(template) => self.template(template),
+            JsonValue::List { elements, .. } => {
+                elements.iter().for_each(|e| self.json_value(&e.value));
+            }
+            JsonValue::Object { elements, .. } => {
+                for element in elements {
+                    self.template(&element.name);
+                    self.json_value(&element.value);
+                }
+            }
+            JsonValue::Number(_) | JsonValue::Boolean(_) | JsonValue::Null => {}
+        }
+    }
+
+    /// Checks an expected JSON value, whose placeholders (like `{{uuid}}`) are not variables in
+    /// JSON match mode.
+    fn expected_json_value(&mut self, value: &JsonValue) {
+        match value {
+            JsonValue::Expression(expr)
+                if Placeholder::from_expr(expr, |name| self.defined.contains(name)).is_some() => {}
+            JsonValue::List { elements, .. } => {
+                elements
+                    .iter()
+                    .for_each(|e| self.expected_json_value(&e.value));
+            }
+            JsonValue::Object { elements, .. } => {
+                for element in elements {
+                    self.template(&element.name);
+                    self.expected_json_value(&element.value);
+                }
+            }
+            value => self.json_value(value),
+        }
+    }
+
+    fn key_value(&mut self, key_value: &KeyValue) {
+        self.template(&key_value.key);
+        self.template(&key_value.value);
+    }
+
+    fn regex_value(&mut self, value: &RegexValue) {
+        if let RegexValue::Template(template) = value {
+            self.template(template);
+        }
+    }
+
+    fn variable_value(&mut self, value: &VariableValue) {
+        if let VariableValue::String(template) = value {
+            self.template(template);
+        }
+    }
+
+    fn template(&mut self, template: &Template) {
+        for element in &template.elements {
+            if let TemplateElement::Expression(expr) = element {
+                self.expr(expr);
+            }
+        }
+    }
+
+    fn expr(&mut self, expr: &Expr) {
+        if let ExprKind::Variable(variable) = &expr.kind {
+            if !self.defined.contains(&variable.name) {
+                // The default value is only evaluated when the variable is not defined.
+                if let Some(default) = &expr.default {
+                    self.variable_value(&default.value);
+                } else if !self.lenient && self.reported.insert(variable.name.clone()) {
+                    let inner = RunnerError::TemplateVariableNotDefined {
+                        name: variable.name.clone(),
+                    };
+                    self.errors
+                        .push(Error::new(variable.source_info, inner, false));
+                }
+            }
+        }
+        expr.filters.iter().for_each(|f| self.filter(&f.filter));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Pos, SourceInfo};
+    use hurl_core::parser;
+
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let content = r#"
+GET http://localhost:8000/{{host_path}}
+[Options]
+variable: user={{name ?? "bob"}}
+HTTP 200
+[Captures]
+token: header "{{header_name}}"
+
+GET http://localhost:8000/{{user}}/{{token}}/{{host_path}}
+X-Id: {{ id }}
+HTTP 200
+[Asserts]
+jsonpath "$.user" == "{{ user }}"
+jsonpath "$.role" == {{role ?? "admin"}}
+
+DELETE http://localhost:8000/{{item}}
+[Options]
+for-each: {{ids}}
+HTTP 204
+"#;
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let entries = hurl_file
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (index + 1, entry))
+            .collect::<Vec<_>>();
+        let variables = HashMap::from([(
+            "header_name".to_string(),
+            Value::String("X-Token".to_string()),
+        )]);
+
+        let error = |line: usize, start: usize, end: usize, name: &str| {
+            let inner = RunnerError::TemplateVariableNotDefined {
+                name: name.to_string(),
+            };
+            let source_info = SourceInfo::new(Pos::new(line, start), Pos::new(line, end));
+            Error::new(source_info, inner, false)
+        };
+        assert_eq!(
+            check(&entries, &variables),
+            vec![
+                (1, error(2, 29, 38, "host_path")),
+                (2, error(10, 10, 12, "id")),
+                (3, error(18, 13, 16, "ids")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_condition() {
+        let content = r#"
+GET http://localhost:8000/{{prod_only}}
+[Options]
+only-if: {{env}} == "prod"
+variable: host={{host}}
+HTTP 200
+[Captures]
+token: header "X-Token"
+
+GET http://localhost:8000/{{unused}}
+[Options]
+skip: true
+HTTP 200
+
+GET http://localhost:8000/{{token}}/{{id}}
+HTTP 200
+"#;
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        let entries = hurl_file
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| (index + 1, entry))
+            .collect::<Vec<_>>();
+        let variables = HashMap::from([("env".to_string(), Value::String("dev".to_string()))]);
+
+        let error = |line: usize, start: usize, end: usize, name: &str| {
+            let inner = RunnerError::TemplateVariableNotDefined {
+                name: name.to_string(),
+            };
+            let source_info = SourceInfo::new(Pos::new(line, start), Pos::new(line, end));
+            Error::new(source_info, inner, false)
+        };
+        // The options of a conditional entry are always evaluated, and the variables it captures
+        // are considered as defined.
+        assert_eq!(
+            check(&entries, &variables),
+            vec![(1, error(5, 18, 22, "host")), (3, error(15, 39, 41, "id"))]
+        );
+
+        // Once its condition is true, a conditional entry is fully checked.
+        assert_eq!(
+            check_conditional(entries[0].1, &variables),
+            vec![error(5, 18, 22, "host"), error(2, 29, 38, "prod_only")]
+        );
+        assert!(check_conditional(entries[2].1, &variables).is_empty());
+    }
+}
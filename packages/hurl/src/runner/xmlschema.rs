@@ -0,0 +1,380 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+//! XML validation against XML Schema (XSD) and RelaxNG schemas, using libxml2.
+use std::ffi::{c_void, CString};
+use std::fmt;
+use std::os::raw::c_char;
+use std::path::Path;
+use std::ptr;
+
+use libxml::bindings::{
+    xmlCtxtGetLastError, xmlCtxtReadMemory, xmlErrorPtr, xmlFreeDoc, xmlFreeParserCtxt,
+    xmlNewParserCtxt, xmlParserOption_XML_PARSE_NOERROR, xmlParserOption_XML_PARSE_NONET,
+    xmlParserOption_XML_PARSE_NOWARNING, xmlRelaxNGFree, xmlRelaxNGFreeParserCtxt,
+    xmlRelaxNGFreeValidCtxt, xmlRelaxNGNewParserCtxt, xmlRelaxNGNewValidCtxt, xmlRelaxNGParse,
+    xmlRelaxNGPtr, xmlRelaxNGSetParserStructuredErrors, xmlRelaxNGSetValidStructuredErrors,
+    xmlRelaxNGValidateDoc,
+};
+use libxml::error::StructuredError;
+use libxml::schemas::{SchemaParserContext, SchemaValidationContext};
+use libxml::tree::Document;
+
+use crate::runner::xpath::silentErrorFunc;
+
+/// The kind of a XML schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum XmlSchemaKind {
+    Xsd,
+    RelaxNg,
+}
+
+impl XmlSchemaKind {
+    /// Returns the kind of the schema file `filename`: RelaxNG for `.rng` files, XSD otherwise.
+    pub fn from_filename(filename: &str) -> XmlSchemaKind {
+        if filename.to_lowercase().ends_with(".rng") {
+            XmlSchemaKind::RelaxNg
+        } else {
+            XmlSchemaKind::Xsd
+        }
+    }
+}
+
+/// An error found in a XML schema, or in a XML document validated against a schema.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XmlError {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {line}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl From<&StructuredError> for XmlError {
+    fn from(error: &StructuredError) -> Self {
+        let message = error.message.as_deref().unwrap_or("unknown error");
+        XmlError {
+            line: error.line.filter(|l| *l > 0).map(|l| l as usize),
+            message: message.trim_end().to_string(),
+        }
+    }
+}
+
+/// A parsed XML schema, ready to validate XML documents.
+pub enum XmlSchema {
+    Xsd(SchemaValidationContext),
+    RelaxNg(RelaxNgSchema),
+}
+
+/// A parsed RelaxNG schema (which is not wrapped by the libxml crate).
+pub struct RelaxNgSchema {
+    ptr: xmlRelaxNGPtr,
+}
+
+impl Drop for RelaxNgSchema {
+    fn drop(&mut self) {
+        unsafe { xmlRelaxNGFree(self.ptr) }
+    }
+}
+
+impl XmlSchema {
+    /// Parses a XML schema of a given `kind` from its file `path`, returning the schema errors if the
+    /// schema is not valid.
+    ///
+    /// Schemas included or imported by this schema are resolved relatively to `path`.
+    pub fn new(path: &Path, kind: XmlSchemaKind) -> Result<XmlSchema, Vec<XmlError>> {
+        silence_generic_errors();
+        let path = path.to_string_lossy();
+        match kind {
+            XmlSchemaKind::Xsd => {
+                let mut parser = SchemaParserContext::from_file(&path);
+                match SchemaValidationContext::from_parser(&mut parser) {
+                    Ok(context) => Ok(XmlSchema::Xsd(context)),
+                    Err(errors) => Err(xml_errors(&errors)),
+                }
+            }
+            XmlSchemaKind::RelaxNg => parse_relax_ng(&path).map(XmlSchema::RelaxNg),
+        }
+    }
+
+    /// Validates a XML text `xml` against this schema, returning the validation errors.
+    ///
+    /// If `xml` is not well-formed, the parsing error is returned as the only validation error.
+    pub fn validate(&mut self, xml: &str) -> Vec<XmlError> {
+        let doc = match parse_xml(xml) {
+            Ok(doc) => doc,
+            Err(error) => return vec![error],
+        };
+        let errors = match self {
+            XmlSchema::Xsd(context) => match context.validate_document(&doc) {
+                Ok(()) => vec![],
+                Err(errors) => errors,
+            },
+            XmlSchema::RelaxNg(schema) => validate_relax_ng(schema, &doc),
+        };
+        xml_errors(&errors)
+    }
+}
+
+/// Silences the libxml2 errors that are not reported through structured errors (like I/O errors
+/// of included schemas), as libxml2 prints them to stderr.
+fn silence_generic_errors() {
+    unsafe {
+        libxml::bindings::initGenericErrorDefaultFunc(&mut Some(silentErrorFunc));
+    }
+}
+
+fn xml_errors(errors: &[StructuredError]) -> Vec<XmlError> {
+    errors.iter().map(XmlError::from).collect()
+}
+
+/// Collects a libxml2 structured `error` into the `Vec<StructuredError>` pointed by `errors`.
+unsafe fn collect_error(errors: *mut c_void, error: xmlErrorPtr) {
+    let errors = &mut *(errors as *mut Vec<StructuredError>);
+    errors.push(StructuredError::from_raw(error));
+}
+
+/// Parses a XML text `xml`, returning the last parsing error if `xml` is not well-formed.
+fn parse_xml(xml: &str) -> Result<Document, XmlError> {
+    let not_well_formed = XmlError {
+        line: None,
+        message: "XML document is not well-formed".to_string(),
+    };
+    let bytes = xml.as_bytes();
+    let len = match i32::try_from(bytes.len()) {
+        Ok(len) => len,
+        Err(_) => return Err(not_well_formed),
+    };
+    let encoding = CString::new("utf-8").unwrap();
+    let options = xmlParserOption_XML_PARSE_NONET
+        | xmlParserOption_XML_PARSE_NOERROR
+        | xmlParserOption_XML_PARSE_NOWARNING;
+    unsafe {
+        let ctxt = xmlNewParserCtxt();
+        if ctxt.is_null() {
+            return Err(not_well_formed);
+        }
+        let doc = xmlCtxtReadMemory(
+            ctxt,
+            bytes.as_ptr() as *const c_char,
+            len,
+            ptr::null(),
+            encoding.as_ptr(),
+            options as i32,
+        );
+        let error = xmlCtxtGetLastError(ctxt as *mut c_void);
+        let result = if doc.is_null() || !error.is_null() && (*error).code != 0 {
+            if !doc.is_null() {
+                xmlFreeDoc(doc);
+            }
+            if error.is_null() {
+                Err(not_well_formed)
+            } else {
+                Err(XmlError::from(&StructuredError::from_raw(error)))
+            }
+        } else {
+            Ok(Document::new_ptr(doc))
+        };
+        xmlFreeParserCtxt(ctxt);
+        result
+    }
+}
+
+/// Parses a RelaxNG schema from its file `path`.
+fn parse_relax_ng(path: &str) -> Result<RelaxNgSchema, Vec<XmlError>> {
+    let mut errors: Vec<StructuredError> = vec![];
+    let path = match CString::new(path) {
+        Ok(path) => path,
+        Err(_) => {
+            return Err(vec![XmlError {
+                line: None,
+                message: "invalid schema path".to_string(),
+            }])
+        }
+    };
+    unsafe {
+        let parser = xmlRelaxNGNewParserCtxt(path.as_ptr());
+        if parser.is_null() {
+            panic!("error creating a RelaxNG parser context");
+        }
+        xmlRelaxNGSetParserStructuredErrors(
+            parser,
+            Some(collect_error),
+            &mut errors as *mut Vec<StructuredError> as *mut c_void,
+        );
+        let schema = xmlRelaxNGParse(parser);
+        xmlRelaxNGFreeParserCtxt(parser);
+        if schema.is_null() {
+            Err(xml_errors(&errors))
+        } else {
+            Ok(RelaxNgSchema { ptr: schema })
+        }
+    }
+}
+
+/// Validates a XML document `doc` against a RelaxNG `schema`, returning the validation errors.
+fn validate_relax_ng(schema: &RelaxNgSchema, doc: &Document) -> Vec<StructuredError> {
+    let mut errors: Vec<StructuredError> = vec![];
+    unsafe {
+        let context = xmlRelaxNGNewValidCtxt(schema.ptr);
+        if context.is_null() {
+            panic!("error creating a RelaxNG validation context");
+        }
+        xmlRelaxNGSetValidStructuredErrors(
+            context,
+            Some(collect_error),
+            &mut errors as *mut Vec<StructuredError> as *mut c_void,
+        );
+        xmlRelaxNGValidateDoc(context, doc.doc_ptr());
+        xmlRelaxNGFreeValidCtxt(context);
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XSD: &str = r#"<?xml version="1.0"?>
+<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:element name="users">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element name="user" maxOccurs="unbounded">
+          <xs:complexType>
+            <xs:sequence>
+              <xs:element name="name" type="xs:string"/>
+              <xs:element name="age" type="xs:nonNegativeInteger"/>
+            </xs:sequence>
+          </xs:complexType>
+        </xs:element>
+      </xs:sequence>
+    </xs:complexType>
+  </xs:element>
+</xs:schema>
+"#;
+
+    const RNG: &str = r#"<?xml version="1.0"?>
+<element name="users" xmlns="http://relaxng.org/ns/structure/1.0">
+  <oneOrMore>
+    <element name="user">
+      <element name="name"><text/></element>
+      <element name="age"><text/></element>
+    </element>
+  </oneOrMore>
+</element>
+"#;
+
+    const USERS: &str = r#"<?xml version="1.0"?>
+<users>
+  <user>
+    <name>Bob</name>
+    <age>12</age>
+  </user>
+</users>
+"#;
+
+    const INVALID_USERS: &str = r#"<?xml version="1.0"?>
+<users>
+  <user>
+    <name>Bob</name>
+    <age>twelve</age>
+  </user>
+  <user>
+    <name>Alice</name>
+  </user>
+</users>
+"#;
+
+    /// Parses a schema `content`, written to a file `name` in a unique temporary directory (tests
+    /// run in parallel).
+    fn new_schema(name: &str, content: &str) -> Result<XmlSchema, Vec<XmlError>> {
+        let dir =
+            std::env::temp_dir().join(format!("hurl_test_xml_schema_{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        let schema = XmlSchema::new(&path, XmlSchemaKind::from_filename(name));
+        std::fs::remove_dir_all(&dir).unwrap();
+        schema
+    }
+
+    #[test]
+    fn test_kind() {
+        assert_eq!(
+            XmlSchemaKind::from_filename("users.xsd"),
+            XmlSchemaKind::Xsd
+        );
+        assert_eq!(
+            XmlSchemaKind::from_filename("schemas/users.RNG"),
+            XmlSchemaKind::RelaxNg
+        );
+    }
+
+    #[test]
+    fn test_validate_xsd() {
+        let mut schema = new_schema("users.xsd", XSD).unwrap();
+        assert!(schema.validate(USERS).is_empty());
+        let errors = schema
+            .validate(INVALID_USERS)
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                "line 5: Element 'age': 'twelve' is not a valid value of the atomic type 'xs:nonNegativeInteger'.",
+                "line 7: Element 'user': Missing child element(s). Expected is ( age ).",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_relax_ng() {
+        let mut schema = new_schema("users.rng", RNG).unwrap();
+        assert!(schema.validate(USERS).is_empty());
+        let errors = schema.validate(INVALID_USERS);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, Some(7));
+    }
+
+    #[test]
+    fn test_invalid_xml() {
+        let mut schema = new_schema("users.xsd", XSD).unwrap();
+        assert_eq!(
+            schema.validate("<users>\n<user>\n</users>"),
+            vec![XmlError {
+                line: Some(3),
+                message: "Premature end of data in tag users line 1".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let errors = new_schema("invalid.xsd", "<xs:schema/>").err().unwrap();
+        assert!(!errors.is_empty());
+        let errors = new_schema("invalid.rng", "<element/>").err().unwrap();
+        assert!(!errors.is_empty());
+    }
+}
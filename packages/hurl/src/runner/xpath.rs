@@ -20,8 +20,11 @@ use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 use std::ptr;
 
-use libxml::bindings::{htmlReadMemory, xmlReadMemory};
+use libxml::bindings::{
+    htmlReadMemory, xmlDocCopyNode, xmlDocSetRootElement, xmlGetLineNo, xmlReadMemory,
+};
 use libxml::parser::{ParseFormat, Parser, XmlParseError};
+use libxml::readonly::RoNode;
 use libxml::tree::Document;
 
 use crate::runner::{Number, Value};
@@ -42,7 +45,27 @@ pub fn eval_xml(xml: &str, expr: &str) -> Result<Value, XpathError> {
             if doc.get_root_element().is_none() {
                 Err(XpathError::InvalidXml)
             } else {
-                eval(&doc, expr, true)
+                eval(&doc, expr, true, false)
+            }
+        }
+        Err(_) => Err(XpathError::InvalidXml),
+    }
+}
+
+/// Evaluates a XPath 1.0 expression `expr` against a XML text `xml`, returning the selected nodes
+/// as a list of XML texts instead of a nodeset.
+///
+/// Each element node is returned as a standalone XML document (declaring the namespaces it uses),
+/// preceded by blank lines so that its lines are the same as in `xml`: errors found in these
+/// documents can then be reported at their position in `xml`.
+pub fn eval_xml_nodes(xml: &str, expr: &str) -> Result<Value, XpathError> {
+    let parser = Parser::default();
+    match parse_html_string_patched(xml, &parser) {
+        Ok(doc) => {
+            if doc.get_root_element().is_none() {
+                Err(XpathError::InvalidXml)
+            } else {
+                eval(&doc, expr, true, true)
             }
         }
         Err(_) => Err(XpathError::InvalidXml),
@@ -59,7 +82,7 @@ pub fn eval_html(html: &str, expr: &str) -> Result<Value, XpathError> {
             if doc.get_root_element().is_none() {
                 Err(XpathError::InvalidHtml)
             } else {
-                eval(&doc, expr, false)
+                eval(&doc, expr, false, false)
             }
         }
         Err(_) => Err(XpathError::InvalidHtml),
@@ -143,7 +166,14 @@ fn register_namespaces(doc: &Document, context: &libxml::xpath::Context) {
 }
 
 /// Evaluates a XPath 1.0 expression `expr` against an libxml2 document `doc`, optionally using namespace.
-fn eval(doc: &Document, expr: &str, support_ns: bool) -> Result<Value, XpathError> {
+///
+/// If `nodes_as_xml` is true, a nodeset is returned as the list of the XML texts of its nodes.
+fn eval(
+    doc: &Document,
+    expr: &str,
+    support_ns: bool,
+    nodes_as_xml: bool,
+) -> Result<Value, XpathError> {
     let context = libxml::xpath::Context::new(doc).expect("error setting context in xpath module");
 
     // libxml2 prints to sdtout warning and errors, so we mut it.
@@ -177,6 +207,14 @@ fn eval(doc: &Document, expr: &str, support_ns: bool) -> Result<Value, XpathErro
 
             Ok(Value::String(s))
         }
+        libxml::bindings::xmlXPathObjectType_XPATH_NODESET if nodes_as_xml => {
+            let nodes = result
+                .get_readonly_nodes_as_vec()
+                .into_iter()
+                .map(node_xml)
+                .collect();
+            Ok(Value::List(nodes))
+        }
         libxml::bindings::xmlXPathObjectType_XPATH_NODESET => {
             Ok(Value::Nodeset(result.get_number_of_nodes()))
         }
@@ -184,6 +222,26 @@ fn eval(doc: &Document, expr: &str, support_ns: bool) -> Result<Value, XpathErro
     }
 }
 
+/// Returns the XML text of a `node`, as a standalone document whose lines match the lines of `node`
+/// in its original document. Nodes that are not elements (text, attributes etc...) are returned as
+/// their text content.
+fn node_xml(node: RoNode) -> Value {
+    if !node.is_element_node() {
+        return Value::String(node.get_content());
+    }
+    let doc = Document::new().expect("error creating a XML document");
+    // Copying a node into a new document also copies its line, and declares the namespaces of its
+    // ancestors that it uses.
+    let line = unsafe {
+        let copy = xmlDocCopyNode(node.node_ptr(), doc.doc_ptr(), 1);
+        xmlDocSetRootElement(doc.doc_ptr(), copy);
+        xmlGetLineNo(node.node_ptr())
+    };
+    let root = doc.get_root_element().expect("root element");
+    let padding = "\n".repeat((line.max(1) - 1) as usize);
+    Value::String(format!("{padding}{}", doc.node_to_string(&root)))
+}
+
 /// A XML namespace
 #[derive(Debug, PartialEq, Eq)]
 struct Namespace {
@@ -274,6 +332,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_xml_nodes() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<f:food xmlns:f="https://example.org/food">
+  <f:banana type="fruit"/>
+  <f:apple type="fruit">
+    <f:color>red</f:color>
+  </f:apple>
+</f:food>
+"#;
+        assert_eq!(
+            eval_xml_nodes(xml, "//f:food/*").unwrap(),
+            Value::List(vec![
+                Value::String(
+                    "\n\n<f:banana xmlns:f=\"https://example.org/food\" type=\"fruit\"/>"
+                        .to_string()
+                ),
+                Value::String(
+                    "\n\n\n<f:apple xmlns:f=\"https://example.org/food\" type=\"fruit\">\n    <f:color>red</f:color>\n  </f:apple>"
+                        .to_string()
+                ),
+            ])
+        );
+        assert_eq!(
+            eval_xml_nodes(xml, "string(//f:color)").unwrap(),
+            Value::String("red".to_string())
+        );
+        assert_eq!(
+            eval_xml_nodes(xml, "//f:color/text()").unwrap(),
+            Value::List(vec![Value::String("red".to_string())])
+        );
+    }
+
     #[test]
     fn test_error_eval() {
         assert_eq!(eval_xml("<a/>", "^^^").err().unwrap(), XpathError::Eval);
@@ -21,21 +21,37 @@ use colored::*;
 use hurl_core::error::Error;
 
 use crate::runner::{HurlResult, Value};
+use crate::util::term::Stderr;
 
 /// A simple logger to log app related event (start, high levels error, etc...).
 /// When we run an [`hurl_core::ast::HurlFile`], user has to provide a dedicated Hurl logger (see [`Logger`]).
 pub struct BaseLogger {
     pub color: bool,
     pub verbose: bool,
+    stderr: Stderr,
 }
 
 impl BaseLogger {
-    pub fn new(color: bool, verbose: bool) -> BaseLogger {
-        BaseLogger { color, verbose }
+    /// Creates a new base logger, the `secrets` values being redacted from the logs.
+    pub fn new(color: bool, verbose: bool, secrets: &[String]) -> BaseLogger {
+        let stderr = Stderr::default();
+        for secret in secrets {
+            stderr.add_secret(secret);
+        }
+        BaseLogger {
+            color,
+            verbose,
+            stderr,
+        }
+    }
+
+    /// Adds a `secret` value, redacted from the logs written from now on.
+    pub fn add_secret(&self, secret: &str) {
+        self.stderr.add_secret(secret);
     }
 
     pub fn info(&self, message: &str) {
-        log_info(message)
+        log_info(&self.stderr, message)
     }
 
     pub fn debug(&self, message: &str) {
@@ -43,25 +59,25 @@ impl BaseLogger {
             return;
         }
         if self.color {
-            log_debug(message)
+            log_debug(&self.stderr, message)
         } else {
-            log_debug_no_color(message)
+            log_debug_no_color(&self.stderr, message)
         }
     }
 
     pub fn warning(&self, message: &str) {
         if self.color {
-            log_warning(message)
+            log_warning(&self.stderr, message)
         } else {
-            log_warning_no_color(message)
+            log_warning_no_color(&self.stderr, message)
         }
     }
 
     pub fn error(&self, message: &str) {
         if self.color {
-            log_error(message)
+            log_error(&self.stderr, message)
         } else {
-            log_error_no_color(message)
+            log_error_no_color(&self.stderr, message)
         }
     }
 }
@@ -95,17 +111,22 @@ pub struct Logger {
     pub(crate) error_format: ErrorFormat,
     pub(crate) filename: String,
     pub(crate) progress_bar: bool,
+    pub(crate) stderr: Stderr,
     pub(crate) test: bool,
     pub(crate) verbosity: Option<Verbosity>,
 }
 
 impl From<&LoggerOptions> for Logger {
     fn from(options: &LoggerOptions) -> Self {
+        for secret in &options.secrets {
+            options.stderr.add_secret(secret);
+        }
         Logger {
             color: options.color,
             error_format: options.error_format,
             filename: options.filename.clone(),
             progress_bar: options.progress_bar,
+            stderr: options.stderr.clone(),
             test: options.test,
             verbosity: options.verbosity,
         }
@@ -117,6 +138,8 @@ pub struct LoggerOptions {
     pub(crate) error_format: ErrorFormat,
     pub(crate) filename: String,
     pub(crate) progress_bar: bool,
+    pub(crate) secrets: Vec<String>,
+    pub(crate) stderr: Stderr,
     pub(crate) test: bool,
     pub(crate) verbosity: Option<Verbosity>,
 }
@@ -126,6 +149,8 @@ pub struct LoggerOptionsBuilder {
     error_format: ErrorFormat,
     filename: String,
     progress_bar: bool,
+    secrets: Vec<String>,
+    stderr: Stderr,
     test: bool,
     verbosity: Option<Verbosity>,
 }
@@ -162,6 +187,18 @@ impl LoggerOptionsBuilder {
         self
     }
 
+    /// Sets the secret values, redacted from the logs.
+    pub fn secrets(&mut self, secrets: &[String]) -> &mut Self {
+        self.secrets = secrets.to_vec();
+        self
+    }
+
+    /// Sets the standard error used to write logs.
+    pub fn stderr(&mut self, stderr: &Stderr) -> &mut Self {
+        self.stderr = stderr.clone();
+        self
+    }
+
     /// Sets test.
     pub fn test(&mut self, test: bool) -> &mut Self {
         self.test = test;
@@ -181,6 +218,8 @@ impl LoggerOptionsBuilder {
             error_format: self.error_format,
             filename: self.filename.clone(),
             progress_bar: self.progress_bar,
+            secrets: self.secrets.clone(),
+            stderr: self.stderr.clone(),
             test: self.test,
             verbosity: self.verbosity,
         }
@@ -194,6 +233,8 @@ impl Default for LoggerOptionsBuilder {
             error_format: ErrorFormat::Short,
             filename: String::new(),
             progress_bar: false,
+            secrets: vec![],
+            stderr: Stderr::default(),
             test: false,
             verbosity: None,
         }
@@ -202,7 +243,7 @@ impl Default for LoggerOptionsBuilder {
 
 impl Logger {
     pub fn info(&self, message: &str) {
-        log_info(message)
+        log_info(&self.stderr, message)
     }
 
     pub fn debug(&self, message: &str) {
@@ -210,9 +251,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug(message)
+            log_debug(&self.stderr, message)
         } else {
-            log_debug_no_color(message)
+            log_debug_no_color(&self.stderr, message)
         }
     }
 
@@ -221,9 +262,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_curl(message)
+            log_debug_curl(&self.stderr, message)
         } else {
-            log_debug_curl_no_color(message)
+            log_debug_curl_no_color(&self.stderr, message)
         }
     }
 
@@ -232,9 +273,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_error(&self.filename, content, error)
+            log_debug_error(&self.stderr, &self.filename, content, error)
         } else {
-            log_debug_error_no_color(&self.filename, content, error)
+            log_debug_error_no_color(&self.stderr, &self.filename, content, error)
         }
     }
 
@@ -243,9 +284,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_header_in(name, value)
+            log_debug_header_in(&self.stderr, name, value)
         } else {
-            log_debug_header_in_no_color(name, value)
+            log_debug_header_in_no_color(&self.stderr, name, value)
         }
     }
 
@@ -254,9 +295,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_header_out(name, value)
+            log_debug_header_out(&self.stderr, name, value)
         } else {
-            log_debug_header_out_no_color(name, value)
+            log_debug_header_out_no_color(&self.stderr, name, value)
         }
     }
 
@@ -265,9 +306,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_important(message)
+            log_debug_important(&self.stderr, message)
         } else {
-            log_debug_no_color(message)
+            log_debug_no_color(&self.stderr, message)
         }
     }
 
@@ -276,33 +317,33 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_status_version_in(line)
+            log_debug_status_version_in(&self.stderr, line)
         } else {
-            log_debug_status_version_in_no_color(line)
+            log_debug_status_version_in_no_color(&self.stderr, line)
         }
     }
 
     pub fn warning(&self, message: &str) {
         if self.color {
-            log_warning(message)
+            log_warning(&self.stderr, message)
         } else {
-            log_warning_no_color(message)
+            log_warning_no_color(&self.stderr, message)
         }
     }
 
     pub fn error(&self, message: &str) {
         if self.color {
-            log_error(message)
+            log_error(&self.stderr, message)
         } else {
-            log_error_no_color(message)
+            log_error_no_color(&self.stderr, message)
         }
     }
 
     pub fn error_rich(&self, content: &str, error: &dyn Error) {
         if self.color {
-            log_error_rich(&self.filename, content, error)
+            log_error_rich(&self.stderr, &self.filename, content, error)
         } else {
-            log_error_rich_no_color(&self.filename, content, error)
+            log_error_rich_no_color(&self.stderr, &self.filename, content, error)
         }
     }
 
@@ -311,20 +352,25 @@ impl Logger {
             return;
         }
         if self.color {
-            log_debug_method_version_out(line)
+            log_debug_method_version_out(&self.stderr, line)
         } else {
-            log_debug_method_version_out_no_color(line)
+            log_debug_method_version_out_no_color(&self.stderr, line)
         }
     }
 
+    /// Adds a `secret` value, redacted from the logs written from now on.
+    pub fn add_secret(&self, secret: &str) {
+        self.stderr.add_secret(secret);
+    }
+
     pub fn capture(&self, name: &str, value: &Value) {
         if self.verbosity.is_none() {
             return;
         }
         if self.color {
-            log_capture(name, value)
+            log_capture(&self.stderr, name, value)
         } else {
-            log_capture_no_color(name, value)
+            log_capture_no_color(&self.stderr, name, value)
         }
     }
 
@@ -333,9 +379,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_test_running(&self.filename, current, total)
+            log_test_running(&self.stderr, &self.filename, current, total)
         } else {
-            log_test_running_no_color(&self.filename, current, total)
+            log_test_running_no_color(&self.stderr, &self.filename, current, total)
         }
     }
 
@@ -343,7 +389,7 @@ impl Logger {
         if !self.progress_bar {
             return;
         }
-        log_test_progress(entry_index, count)
+        log_test_progress(&self.stderr, entry_index, count)
     }
 
     pub fn test_completed(&self, result: &HurlResult) {
@@ -351,9 +397,9 @@ impl Logger {
             return;
         }
         if self.color {
-            log_test_completed(result, &self.filename)
+            log_test_completed(&self.stderr, result, &self.filename)
         } else {
-            log_test_completed_no_color(result, &self.filename)
+            log_test_completed_no_color(&self.stderr, result, &self.filename)
         }
     }
 
@@ -364,149 +410,160 @@ impl Logger {
         // This is the "EL - Erase in Line" sequence. It clears from the cursor
         // to the end of line.
         // https://en.wikipedia.org/wiki/ANSI_escape_code#CSI_sequences
-        eprint!("\x1B[K");
+        self.stderr.eprint("\x1B[K");
     }
 }
 
-fn log_info(message: &str) {
-    eprintln!("{message}");
+fn log_info(stderr: &Stderr, message: &str) {
+    stderr.eprintln(message);
 }
 
-fn log_debug(message: &str) {
+fn log_debug(stderr: &Stderr, message: &str) {
     if message.is_empty() {
-        eprintln!("{}", "*".blue().bold());
+        stderr.eprintln(&"*".blue().bold().to_string());
     } else {
-        eprintln!("{} {}", "*".blue().bold(), message);
+        stderr.eprintln(&format!("{} {}", "*".blue().bold(), message));
     }
 }
 
-fn log_debug_no_color(message: &str) {
+fn log_debug_no_color(stderr: &Stderr, message: &str) {
     if message.is_empty() {
-        eprintln!("*");
+        stderr.eprintln("*");
     } else {
-        eprintln!("* {message}");
+        stderr.eprintln(&format!("* {message}"));
     }
 }
 
-fn log_debug_curl(message: &str) {
+fn log_debug_curl(stderr: &Stderr, message: &str) {
     if message.is_empty() {
-        eprintln!("{}", "**".blue().bold());
+        stderr.eprintln(&"**".blue().bold().to_string());
     } else {
-        eprintln!("{} {}", "**".blue().bold(), message.green());
+        stderr.eprintln(&format!("{} {}", "**".blue().bold(), message.green()));
     }
 }
 
-fn log_debug_curl_no_color(message: &str) {
+fn log_debug_curl_no_color(stderr: &Stderr, message: &str) {
     if message.is_empty() {
-        eprintln!("**");
+        stderr.eprintln("**");
     } else {
-        eprintln!("** {message}");
+        stderr.eprintln(&format!("** {message}"));
     }
 }
 
-fn log_debug_important(message: &str) {
+fn log_debug_important(stderr: &Stderr, message: &str) {
     if message.is_empty() {
-        eprintln!("{}", "*".blue().bold());
+        stderr.eprintln(&"*".blue().bold().to_string());
     } else {
-        eprintln!("{} {}", "*".blue().bold(), message.bold());
+        stderr.eprintln(&format!("{} {}", "*".blue().bold(), message.bold()));
     }
 }
 
-fn log_debug_error(filename: &str, content: &str, error: &dyn Error) {
+fn log_debug_error(stderr: &Stderr, filename: &str, content: &str, error: &dyn Error) {
     let message = error_string(filename, content, error, true);
-    get_lines(&message).iter().for_each(|l| log_debug(l));
+    get_lines(&message)
+        .iter()
+        .for_each(|l| log_debug(stderr, l));
 }
 
-fn log_debug_error_no_color(filename: &str, content: &str, error: &dyn Error) {
+fn log_debug_error_no_color(stderr: &Stderr, filename: &str, content: &str, error: &dyn Error) {
     let message = error_string(filename, content, error, false);
     get_lines(&message)
         .iter()
-        .for_each(|l| log_debug_no_color(l));
+        .for_each(|l| log_debug_no_color(stderr, l));
 }
 
-fn log_debug_header_in(name: &str, value: &str) {
-    eprintln!("< {}: {}", name.cyan().bold(), value)
+fn log_debug_header_in(stderr: &Stderr, name: &str, value: &str) {
+    stderr.eprintln(&format!("< {}: {}", name.cyan().bold(), value))
 }
 
-fn log_debug_header_in_no_color(name: &str, value: &str) {
-    eprintln!("< {name}: {value}")
+fn log_debug_header_in_no_color(stderr: &Stderr, name: &str, value: &str) {
+    stderr.eprintln(&format!("< {name}: {value}"))
 }
 
-fn log_debug_header_out(name: &str, value: &str) {
-    eprintln!("> {}: {}", name.cyan().bold(), value)
+fn log_debug_header_out(stderr: &Stderr, name: &str, value: &str) {
+    stderr.eprintln(&format!("> {}: {}", name.cyan().bold(), value))
 }
 
-fn log_debug_header_out_no_color(name: &str, value: &str) {
-    eprintln!("> {name}: {value}")
+fn log_debug_header_out_no_color(stderr: &Stderr, name: &str, value: &str) {
+    stderr.eprintln(&format!("> {name}: {value}"))
 }
 
-fn log_debug_method_version_out(line: &str) {
-    eprintln!("> {}", line.purple().bold())
+fn log_debug_method_version_out(stderr: &Stderr, line: &str) {
+    stderr.eprintln(&format!("> {}", line.purple().bold()))
 }
 
-fn log_debug_method_version_out_no_color(line: &str) {
-    eprintln!("> {line}")
+fn log_debug_method_version_out_no_color(stderr: &Stderr, line: &str) {
+    stderr.eprintln(&format!("> {line}"))
 }
 
-fn log_debug_status_version_in(line: &str) {
-    eprintln!("< {}", line.green().bold())
+fn log_debug_status_version_in(stderr: &Stderr, line: &str) {
+    stderr.eprintln(&format!("< {}", line.green().bold()))
 }
 
-fn log_debug_status_version_in_no_color(line: &str) {
-    eprintln!("< {line}")
+fn log_debug_status_version_in_no_color(stderr: &Stderr, line: &str) {
+    stderr.eprintln(&format!("< {line}"))
 }
 
-fn log_warning(message: &str) {
-    eprintln!("{}: {}", "warning".yellow().bold(), message.bold());
+fn log_warning(stderr: &Stderr, message: &str) {
+    stderr.eprintln(&format!(
+        "{}: {}",
+        "warning".yellow().bold(),
+        message.bold()
+    ));
 }
 
-fn log_warning_no_color(message: &str) {
-    eprintln!("warning: {message}");
+fn log_warning_no_color(stderr: &Stderr, message: &str) {
+    stderr.eprintln(&format!("warning: {message}"));
 }
 
-fn log_error(message: &str) {
-    eprintln!("{}: {}", "error".red().bold(), message.bold());
+fn log_error(stderr: &Stderr, message: &str) {
+    stderr.eprintln(&format!("{}: {}", "error".red().bold(), message.bold()));
 }
 
-fn log_error_no_color(message: &str) {
-    eprintln!("error: {message}");
+fn log_error_no_color(stderr: &Stderr, message: &str) {
+    stderr.eprintln(&format!("error: {message}"));
 }
 
-fn log_error_rich(filename: &str, content: &str, error: &dyn Error) {
+fn log_error_rich(stderr: &Stderr, filename: &str, content: &str, error: &dyn Error) {
     let message = error_string(filename, content, error, true);
-    eprintln!("{}: {}\n", "error".red().bold(), &message)
+    stderr.eprintln(&format!("{}: {}\n", "error".red().bold(), &message))
 }
 
-fn log_error_rich_no_color(filename: &str, content: &str, error: &dyn Error) {
+fn log_error_rich_no_color(stderr: &Stderr, filename: &str, content: &str, error: &dyn Error) {
     let message = error_string(filename, content, error, false);
-    eprintln!("error: {}\n", &message)
+    stderr.eprintln(&format!("error: {}\n", &message))
 }
 
-fn log_capture(name: &str, value: &Value) {
-    eprintln!("{} {}: {}", "*".blue().bold(), name.yellow().bold(), value)
+fn log_capture(stderr: &Stderr, name: &str, value: &Value) {
+    stderr.eprintln(&format!(
+        "{} {}: {}",
+        "*".blue().bold(),
+        name.yellow().bold(),
+        value
+    ))
 }
 
-fn log_capture_no_color(name: &str, value: &Value) {
-    eprintln!("* {name}: {value}")
+fn log_capture_no_color(stderr: &Stderr, name: &str, value: &Value) {
+    stderr.eprintln(&format!("* {name}: {value}"))
 }
 
-fn log_test_running(filename: &str, current: usize, total: usize) {
-    eprintln!(
+fn log_test_running(stderr: &Stderr, filename: &str, current: usize, total: usize) {
+    stderr.eprintln(&format!(
         "{}: {} [{}/{}]",
         filename.bold(),
         "Running".cyan().bold(),
         current,
         total
-    )
+    ))
 }
 
-fn log_test_running_no_color(filename: &str, current: usize, total: usize) {
-    eprintln!("{filename}: Running [{current}/{total}]")
+fn log_test_running_no_color(stderr: &Stderr, filename: &str, current: usize, total: usize) {
+    stderr.eprintln(&format!("{filename}: Running [{current}/{total}]"))
 }
 
-fn log_test_progress(entry_index: usize, count: usize) {
+fn log_test_progress(stderr: &Stderr, entry_index: usize, count: usize) {
     let progress = progress_string(entry_index, count);
-    eprint!(" {progress}\r");
+    stderr.eprint(&format!(" {progress}\r"));
 }
 
 /// Returns the progress string with the current entry at `entry_index`.
@@ -524,29 +581,29 @@ fn progress_string(entry_index: usize, count: usize) -> String {
     format!("[{completed}>{void}] {entry_index}/{count}")
 }
 
-fn log_test_completed(result: &HurlResult, filename: &str) {
+fn log_test_completed(stderr: &Stderr, result: &HurlResult, filename: &str) {
     let state = if result.success {
         "Success".green().bold()
     } else {
         "Failure".red().bold()
     };
     let count = result.entries.iter().flat_map(|r| &r.calls).count();
-    eprintln!(
+    stderr.eprintln(&format!(
         "{}: {} ({} request(s) in {} ms)",
         filename.bold(),
         state,
         count,
         result.time_in_ms
-    )
+    ))
 }
 
-fn log_test_completed_no_color(result: &HurlResult, filename: &str) {
+fn log_test_completed_no_color(stderr: &Stderr, result: &HurlResult, filename: &str) {
     let state = if result.success { "Success" } else { "Failure" };
     let count = result.entries.iter().flat_map(|r| &r.calls).count();
-    eprintln!(
+    stderr.eprintln(&format!(
         "{}: {} ({} request(s) in {} ms)",
         filename, state, count, result.time_in_ms
-    )
+    ))
 }
 
 /// Returns an `error` as a string, given `lines` of content and a `filename`.
@@ -17,3 +17,5 @@
  */
 pub mod logger;
 pub mod path;
+pub mod redact;
+pub mod term;
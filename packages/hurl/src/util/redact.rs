@@ -0,0 +1,49 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+
+/// Replacement of the secret values.
+pub const REDACTED: &str = "***";
+
+/// Returns `text` where every occurrence of one of the `secrets` is replaced by `***`.
+///
+/// Longest secrets are replaced first, so a secret containing another one is fully redacted.
+pub fn redact(text: &str, secrets: &[String]) -> String {
+    let mut secrets = secrets.iter().filter(|s| !s.is_empty()).collect::<Vec<_>>();
+    secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+    let mut text = text.to_string();
+    for secret in secrets {
+        if text.contains(secret.as_str()) {
+            text = text.replace(secret.as_str(), REDACTED);
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_secrets() {
+        let secrets = vec!["abc".to_string(), "abcdef".to_string(), String::new()];
+        assert_eq!(redact("token=abcdef", &secrets), "token=***");
+        assert_eq!(redact("abc abc", &secrets), "*** ***");
+        assert_eq!(redact("no secret", &secrets), "no secret");
+        assert_eq!(redact("abc", &[]), "abc");
+    }
+}
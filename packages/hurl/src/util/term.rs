@@ -0,0 +1,125 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use std::sync::{Arc, Mutex};
+
+use crate::util::redact::redact;
+
+/// Indicates how messages are written to the terminal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Messages are printed immediately.
+    Immediate,
+    /// Messages are saved to an internal buffer, and can be retrieved with [`Stderr::take_buffer`].
+    Buffered,
+}
+
+/// A standard error abstraction.
+///
+/// When Hurl files are run in parallel, each file writes its logs to a buffered [`Stderr`], so
+/// logs of different files are not interleaved on the terminal. Clones of a [`Stderr`] share
+/// the same buffer, and the same list of secrets: every secret value is replaced by `***` in
+/// the written messages.
+#[derive(Clone, Debug)]
+pub struct Stderr {
+    mode: WriteMode,
+    buffer: Arc<Mutex<String>>,
+    secrets: Arc<Mutex<Vec<String>>>,
+}
+
+impl Default for Stderr {
+    fn default() -> Self {
+        Stderr::new(WriteMode::Immediate)
+    }
+}
+
+impl Stderr {
+    /// Creates a new standard error.
+    pub fn new(mode: WriteMode) -> Self {
+        Stderr {
+            mode,
+            buffer: Arc::new(Mutex::new(String::new())),
+            secrets: Arc::new(Mutex::new(vec![])),
+        }
+    }
+
+    /// Adds a `secret` value, to be redacted from the messages written from now on.
+    pub fn add_secret(&self, secret: &str) {
+        let mut secrets = self.secrets.lock().unwrap();
+        if !secret.is_empty() && !secrets.iter().any(|s| s == secret) {
+            secrets.push(secret.to_string());
+        }
+    }
+
+    /// Returns `message` with its secret values redacted.
+    fn redact(&self, message: &str) -> String {
+        redact(message, &self.secrets.lock().unwrap())
+    }
+
+    /// Prints to the standard error, with a newline.
+    pub fn eprintln(&self, message: &str) {
+        let message = self.redact(message);
+        match self.mode {
+            WriteMode::Immediate => eprintln!("{message}"),
+            WriteMode::Buffered => {
+                let mut buffer = self.buffer.lock().unwrap();
+                buffer.push_str(&message);
+                buffer.push('\n');
+            }
+        }
+    }
+
+    /// Prints to the standard error.
+    pub fn eprint(&self, message: &str) {
+        let message = self.redact(message);
+        match self.mode {
+            WriteMode::Immediate => eprint!("{message}"),
+            WriteMode::Buffered => self.buffer.lock().unwrap().push_str(&message),
+        }
+    }
+
+    /// Returns the content of the buffer, and clears it.
+    ///
+    /// The buffer is always empty in [`WriteMode::Immediate`] mode.
+    pub fn take_buffer(&self) -> String {
+        std::mem::take(&mut self.buffer.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffered_stderr_is_shared_between_clones() {
+        let stderr = Stderr::new(WriteMode::Buffered);
+        let other = stderr.clone();
+        stderr.eprintln("foo");
+        other.eprint("bar");
+        assert_eq!(stderr.take_buffer(), "foo\nbar");
+        assert_eq!(other.take_buffer(), "");
+    }
+
+    #[test]
+    fn secrets_are_redacted() {
+        let stderr = Stderr::new(WriteMode::Buffered);
+        stderr.eprintln("token: 12345");
+        stderr.clone().add_secret("12345");
+        stderr.eprintln("token: 12345");
+        assert_eq!(stderr.take_buffer(), "token: 12345\ntoken: ***\n");
+    }
+}
@@ -37,6 +37,42 @@ impl Entry {
     pub fn source_info(&self) -> SourceInfo {
         self.request.space0.source_info
     }
+
+    /// Returns the name of this entry, defined with a `name` option.
+    pub fn name(&self) -> Option<String> {
+        self.request
+            .options()
+            .into_iter()
+            .rev()
+            .find_map(|option| match option.kind {
+                OptionKind::Name(name) => Some(name),
+                _ => None,
+            })
+    }
+
+    /// Returns the tags of this entry, defined with `tag` options.
+    pub fn tags(&self) -> Vec<String> {
+        self.request
+            .options()
+            .into_iter()
+            .filter_map(|option| match option.kind {
+                OptionKind::Tag(tag) => Some(tag),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the files included by this entry, defined with `include` options.
+    pub fn includes(&self) -> Vec<Filename> {
+        self.request
+            .options()
+            .into_iter()
+            .filter_map(|option| match option.kind {
+                OptionKind::Include(filename) => Some(filename),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -282,6 +318,8 @@ pub struct Capture {
     pub space2: Whitespace,
     pub query: Query,
     pub filters: Vec<(Whitespace, Filter)>,
+    pub space3: Whitespace,
+    pub redact: bool,
     pub line_terminator0: LineTerminator,
 }
 
@@ -424,6 +462,7 @@ pub enum PredicateValue {
     Expression(Expr),
     File(File),
     Hex(Hex),
+    Json(json::Value),
     MultilineString(MultilineString),
     Null,
     Number(Number),
@@ -484,6 +523,18 @@ pub enum PredicateFuncValue {
         space0: Whitespace,
         value: PredicateValue,
     },
+    MatchSnapshot {
+        space0: Whitespace,
+        value: PredicateValue,
+    },
+    MatchSchema {
+        space0: Whitespace,
+        value: PredicateValue,
+    },
+    MatchXmlSchema {
+        space0: Whitespace,
+        value: PredicateValue,
+    },
     IsInteger,
     IsFloat,
     IsBoolean,
@@ -695,16 +746,126 @@ impl SourceInfo {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Expr {
     pub space0: Whitespace,
-    pub variable: Variable,
+    pub kind: ExprKind,
+    pub default: Option<Box<ExprDefault>>,
+    pub filters: Vec<ExprFilter>,
     pub space1: Whitespace,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExprKind {
+    Variable(Variable),
+    Function(Box<Function>),
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Variable {
     pub name: String,
     pub source_info: SourceInfo,
 }
 
+/// A default value of an expression, used when its variable is not defined:
+/// `{{base_url ?? "http://localhost:8000"}}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprDefault {
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub value: VariableValue,
+}
+
+/// A filter applied to the value of an expression, after a pipe: `{{id | urlEncode}}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExprFilter {
+    pub space0: Whitespace,
+    pub space1: Whitespace,
+    pub filter: Filter,
+}
+
+/// A call to a built-in function in a template, like `{{newUuid()}}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Function {
+    pub source_info: SourceInfo,
+    pub value: FunctionValue,
+}
+
+/// The arguments of a function, with the whitespaces around them (`space0` follows the opening
+/// parenthesis).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FunctionValue {
+    Env {
+        space0: Whitespace,
+        name: String,
+        space1: Whitespace,
+    },
+    NewUuid {
+        space0: Whitespace,
+    },
+    Now {
+        space0: Whitespace,
+    },
+    RandomInt {
+        space0: Whitespace,
+        min: i64,
+        space1: Whitespace,
+        space2: Whitespace,
+        max: i64,
+        space3: Whitespace,
+    },
+    RandomString {
+        space0: Whitespace,
+        length: u64,
+        space1: Whitespace,
+    },
+}
+
+impl Expr {
+    /// Returns the position of this expression, without its delimiters and whitespaces.
+    pub fn source_info(&self) -> SourceInfo {
+        match &self.kind {
+            ExprKind::Variable(variable) => variable.source_info,
+            ExprKind::Function(function) => function.source_info,
+        }
+    }
+}
+
+impl FunctionValue {
+    /// Returns the name of this function.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FunctionValue::Env { .. } => "env",
+            FunctionValue::NewUuid { .. } => "newUuid",
+            FunctionValue::Now { .. } => "now",
+            FunctionValue::RandomInt { .. } => "randomInt",
+            FunctionValue::RandomString { .. } => "randomString",
+        }
+    }
+}
+
+impl FilterValue {
+    /// Returns the name of this filter.
+    pub fn name(&self) -> &'static str {
+        match self {
+            FilterValue::Count => "count",
+            FilterValue::DaysAfterNow => "daysAfterNow",
+            FilterValue::DaysBeforeNow => "daysBeforeNow",
+            FilterValue::Decode { .. } => "decode",
+            FilterValue::Format { .. } => "format",
+            FilterValue::HtmlEscape => "htmlEscape",
+            FilterValue::HtmlUnescape => "htmlUnescape",
+            FilterValue::JsonPath { .. } => "jsonpath",
+            FilterValue::Nth { .. } => "nth",
+            FilterValue::Regex { .. } => "regex",
+            FilterValue::Replace { .. } => "replace",
+            FilterValue::Split { .. } => "split",
+            FilterValue::ToDate { .. } => "toDate",
+            FilterValue::ToInt => "toInt",
+            FilterValue::UrlDecode => "urlDecode",
+            FilterValue::UrlEncode => "urlEncode",
+            FilterValue::XPath { .. } => "xpath",
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EntryOption {
     pub line_terminators: Vec<LineTerminator>,
@@ -728,18 +889,30 @@ pub enum OptionKind {
     Http11(BooleanOption),
     Http2(BooleanOption),
     Http3(BooleanOption),
+    Include(Filename),
     Insecure(BooleanOption),
     IpV4(BooleanOption),
     IpV6(BooleanOption),
+    JsonMatch(BooleanOption),
     FollowLocation(BooleanOption),
+    ForEach(Expr),
     MaxRedirect(NaturalOption),
+    Name(String),
+    OnlyIf(ConditionOption),
     Output(Filename),
     PathAsIs(BooleanOption),
     Proxy(Template),
+    Repeat(NaturalOption),
     Resolve(Template),
     Retry(RetryOption),
+    RetryAfter(BooleanOption),
+    RetryBackoff(BooleanOption),
     RetryInterval(NaturalOption),
-    Skip(BooleanOption),
+    RetryJitter(BooleanOption),
+    RetryMaxInterval(NaturalOption),
+    RetryMaxTime(NaturalOption),
+    Skip(ConditionOption),
+    Tag(String),
     Variable(VariableDefinition),
     Verbose(BooleanOption),
     VeryVerbose(BooleanOption),
@@ -756,21 +929,33 @@ impl OptionKind {
             OptionKind::ConnectTo(_) => "connect-to",
             OptionKind::Delay(_) => "delay",
             OptionKind::FollowLocation(_) => "location",
+            OptionKind::ForEach(_) => "for-each",
             OptionKind::Http10(_) => "http1.0",
             OptionKind::Http11(_) => "http1.1",
             OptionKind::Http2(_) => "http2",
             OptionKind::Http3(_) => "http3",
+            OptionKind::Include(_) => "include",
             OptionKind::Insecure(_) => "insecure",
             OptionKind::IpV4(_) => "ipv4",
             OptionKind::IpV6(_) => "ipv6",
+            OptionKind::JsonMatch(_) => "json-match",
             OptionKind::MaxRedirect(_) => "max-redirs",
+            OptionKind::Name(_) => "name",
+            OptionKind::OnlyIf(_) => "only-if",
             OptionKind::Output(_) => "output",
             OptionKind::PathAsIs(_) => "path-as-is",
             OptionKind::Proxy(_) => "proxy",
+            OptionKind::Repeat(_) => "repeat",
             OptionKind::Resolve(_) => "resolve",
             OptionKind::Retry(_) => "retry",
+            OptionKind::RetryAfter(_) => "retry-after",
+            OptionKind::RetryBackoff(_) => "retry-backoff",
             OptionKind::RetryInterval(_) => "retry-interval",
+            OptionKind::RetryJitter(_) => "retry-jitter",
+            OptionKind::RetryMaxInterval(_) => "retry-max-interval",
+            OptionKind::RetryMaxTime(_) => "retry-max-time",
             OptionKind::Skip(_) => "skip",
+            OptionKind::Tag(_) => "tag",
             OptionKind::Variable(_) => "variable",
             OptionKind::Verbose(_) => "verbose",
             OptionKind::VeryVerbose(_) => "very-verbose",
@@ -787,21 +972,33 @@ impl OptionKind {
             OptionKind::ConnectTo(value) => value.to_string(),
             OptionKind::Delay(value) => value.to_string(),
             OptionKind::FollowLocation(value) => value.to_string(),
+            OptionKind::ForEach(expr) => format!("{{{{{expr}}}}}"),
             OptionKind::Http10(value) => value.to_string(),
             OptionKind::Http11(value) => value.to_string(),
             OptionKind::Http2(value) => value.to_string(),
             OptionKind::Http3(value) => value.to_string(),
+            OptionKind::Include(filename) => filename.value.clone(),
             OptionKind::Insecure(value) => value.to_string(),
             OptionKind::IpV4(value) => value.to_string(),
             OptionKind::IpV6(value) => value.to_string(),
+            OptionKind::JsonMatch(value) => value.to_string(),
             OptionKind::MaxRedirect(value) => value.to_string(),
+            OptionKind::Name(value) => value.clone(),
+            OptionKind::OnlyIf(value) => value.to_string(),
             OptionKind::Output(filename) => filename.value.to_string(),
             OptionKind::PathAsIs(value) => value.to_string(),
             OptionKind::Proxy(value) => value.to_string(),
+            OptionKind::Repeat(value) => value.to_string(),
             OptionKind::Resolve(value) => value.to_string(),
             OptionKind::Retry(value) => value.to_string(),
+            OptionKind::RetryAfter(value) => value.to_string(),
+            OptionKind::RetryBackoff(value) => value.to_string(),
             OptionKind::RetryInterval(value) => value.to_string(),
+            OptionKind::RetryJitter(value) => value.to_string(),
+            OptionKind::RetryMaxInterval(value) => value.to_string(),
+            OptionKind::RetryMaxTime(value) => value.to_string(),
             OptionKind::Skip(value) => value.to_string(),
+            OptionKind::Tag(value) => value.clone(),
             OptionKind::Variable(VariableDefinition { name, value, .. }) => {
                 format!("{name}={value}")
             }
@@ -812,6 +1009,7 @@ impl OptionKind {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
 pub enum BooleanOption {
     Literal(bool),
     Expression(Expr),
@@ -829,6 +1027,23 @@ pub enum RetryOption {
     Expression(Expr),
 }
 
+/// A condition of the `skip` and `only-if` options: a boolean, or a predicate on the value of an
+/// expression, like `{{env}} == "prod"`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[allow(clippy::large_enum_variant)]
+pub enum ConditionOption {
+    Literal(bool),
+    Expression(Expr),
+    Predicate(Condition),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+    pub expr: Expr,
+    pub space0: Whitespace,
+    pub predicate: Predicate,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VariableDefinition {
     pub name: String,
@@ -98,7 +98,83 @@ impl fmt::Display for Float {
 
 impl fmt::Display for Expr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.variable.name)
+        write!(f, "{}", self.kind)?;
+        if let Some(default) = &self.default {
+            let value = match &default.value {
+                VariableValue::String(template) => template.to_encoded_string(),
+                value => value.to_string(),
+            };
+            write!(f, " ?? {value}")?;
+        }
+        for expr_filter in self.filters.iter() {
+            write!(f, " | {}", expr_filter.filter.value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExprKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprKind::Variable(variable) => write!(f, "{}", variable.name),
+            ExprKind::Function(function) => write!(f, "{}", function.value),
+        }
+    }
+}
+
+impl fmt::Display for FunctionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.name();
+        match self {
+            FunctionValue::Env { name: variable, .. } => write!(f, "{name}(\"{variable}\")"),
+            FunctionValue::NewUuid { .. } | FunctionValue::Now { .. } => write!(f, "{name}()"),
+            FunctionValue::RandomInt { min, max, .. } => write!(f, "{name}({min}, {max})"),
+            FunctionValue::RandomString { length, .. } => write!(f, "{name}({length})"),
+        }
+    }
+}
+
+impl fmt::Display for FilterValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FilterValue::Count => write!(f, "count"),
+            FilterValue::DaysAfterNow => write!(f, "daysAfterNow"),
+            FilterValue::DaysBeforeNow => write!(f, "daysBeforeNow"),
+            FilterValue::Decode { encoding, .. } => {
+                write!(f, "decode {}", encoding.to_encoded_string())
+            }
+            FilterValue::Format { fmt, .. } => write!(f, "format {}", fmt.to_encoded_string()),
+            FilterValue::HtmlEscape => write!(f, "htmlEscape"),
+            FilterValue::HtmlUnescape => write!(f, "htmlUnescape"),
+            FilterValue::JsonPath { expr, .. } => {
+                write!(f, "jsonpath {}", expr.to_encoded_string())
+            }
+            FilterValue::Nth { n, .. } => write!(f, "nth {n}"),
+            FilterValue::Regex { value, .. } => write!(f, "regex {}", regex_value(value)),
+            FilterValue::Replace {
+                old_value,
+                new_value,
+                ..
+            } => write!(
+                f,
+                "replace {} {}",
+                regex_value(old_value),
+                new_value.to_encoded_string()
+            ),
+            FilterValue::Split { sep, .. } => write!(f, "split {}", sep.to_encoded_string()),
+            FilterValue::ToDate { fmt, .. } => write!(f, "toDate {}", fmt.to_encoded_string()),
+            FilterValue::ToInt => write!(f, "toInt"),
+            FilterValue::UrlDecode => write!(f, "urlDecode"),
+            FilterValue::UrlEncode => write!(f, "urlEncode"),
+            FilterValue::XPath { expr, .. } => write!(f, "xpath {}", expr.to_encoded_string()),
+        }
+    }
+}
+
+fn regex_value(value: &RegexValue) -> String {
+    match value {
+        RegexValue::Template(template) => template.to_encoded_string(),
+        RegexValue::Regex(regex) => format!("/{}/", regex.inner.as_str().replace('/', "\\/")),
     }
 }
 
@@ -178,6 +254,18 @@ impl fmt::Display for BooleanOption {
     }
 }
 
+impl fmt::Display for ConditionOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConditionOption::Literal(v) => write!(f, "{v}"),
+            ConditionOption::Expression(v) => write!(f, "{{{{{v}}}}}"),
+            ConditionOption::Predicate(condition) => {
+                write!(f, "{{{{{}}}}} {}", condition.expr, condition.predicate)
+            }
+        }
+    }
+}
+
 impl fmt::Display for NaturalOption {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -214,6 +302,53 @@ impl fmt::Display for VariableValue {
     }
 }
 
+impl fmt::Display for Predicate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.not {
+            write!(f, "not ")?;
+        }
+        let func = &self.predicate_func.value;
+        write!(f, "{}", func.name())?;
+        match func {
+            PredicateFuncValue::Equal { value, .. }
+            | PredicateFuncValue::NotEqual { value, .. }
+            | PredicateFuncValue::GreaterThan { value, .. }
+            | PredicateFuncValue::GreaterThanOrEqual { value, .. }
+            | PredicateFuncValue::LessThan { value, .. }
+            | PredicateFuncValue::LessThanOrEqual { value, .. }
+            | PredicateFuncValue::StartWith { value, .. }
+            | PredicateFuncValue::EndWith { value, .. }
+            | PredicateFuncValue::Contain { value, .. }
+            | PredicateFuncValue::Include { value, .. }
+            | PredicateFuncValue::Match { value, .. }
+            | PredicateFuncValue::MatchSnapshot { value, .. }
+            | PredicateFuncValue::MatchSchema { value, .. }
+            | PredicateFuncValue::MatchXmlSchema { value, .. } => write!(f, " {value}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for PredicateValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PredicateValue::Base64(value) => write!(f, "base64,{};", value.encoded),
+            PredicateValue::Bool(value) => write!(f, "{value}"),
+            PredicateValue::Expression(value) => write!(f, "{{{{{value}}}}}"),
+            PredicateValue::File(value) => write!(f, "file,{};", value.filename.value),
+            PredicateValue::Hex(value) => write!(f, "{value}"),
+            PredicateValue::Json(value) => write!(f, "{value}"),
+            PredicateValue::MultilineString(value) => write!(f, "```{value}```"),
+            PredicateValue::Null => write!(f, "null"),
+            PredicateValue::Number(value) => write!(f, "{value}"),
+            PredicateValue::Regex(value) => {
+                write!(f, "{}", regex_value(&RegexValue::Regex(value.clone())))
+            }
+            PredicateValue::String(value) => write!(f, "{}", value.to_encoded_string()),
+        }
+    }
+}
+
 impl PredicateFuncValue {
     pub fn name(&self) -> String {
         match self {
@@ -264,6 +399,9 @@ impl PredicateFuncValue {
             PredicateFuncValue::Contain { .. } => "contains".to_string(),
             PredicateFuncValue::Include { .. } => "includes".to_string(),
             PredicateFuncValue::Match { .. } => "matches".to_string(),
+            PredicateFuncValue::MatchSnapshot { .. } => "matchesSnapshot".to_string(),
+            PredicateFuncValue::MatchSchema { .. } => "matchesSchema".to_string(),
+            PredicateFuncValue::MatchXmlSchema { .. } => "matchesXmlSchema".to_string(),
             PredicateFuncValue::IsInteger => "isInteger".to_string(),
             PredicateFuncValue::IsFloat => "isFloat".to_string(),
             PredicateFuncValue::IsBoolean => "isBoolean".to_string(),
@@ -301,10 +439,12 @@ mod tests {
     fn variable_expr() -> Expr {
         Expr {
             space0: whitespace(),
-            variable: Variable {
+            kind: ExprKind::Variable(Variable {
                 name: "name".to_string(),
                 source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-            },
+            }),
+            default: None,
+            filters: vec![],
             space1: whitespace(),
         }
     }
@@ -216,7 +216,7 @@ impl TemplateElement {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Pos, SourceInfo, TemplateElement, Variable, Whitespace};
+    use crate::ast::{ExprKind, Pos, SourceInfo, TemplateElement, Variable, Whitespace};
 
     #[test]
     fn test_to_string() {
@@ -227,10 +227,12 @@ mod tests {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: "x".to_string(),
                     source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(0, 0), Pos::new(0, 0)),
@@ -325,10 +327,12 @@ mod tests {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: "name".to_string(),
                     source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
@@ -345,10 +349,12 @@ mod tests {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
                     },
-                    variable: Variable {
+                    kind: ExprKind::Variable(Variable {
                         name: "name".to_string(),
                         source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
-                    },
+                    }),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 1)),
@@ -222,21 +222,33 @@ impl HtmlFormatter {
             OptionKind::ConnectTo(value) => self.fmt_template(value),
             OptionKind::Delay(value) => self.fmt_natural_option(value),
             OptionKind::FollowLocation(value) => self.fmt_bool_option(value),
+            OptionKind::ForEach(expr) => self.fmt_expr(expr),
             OptionKind::Http10(value) => self.fmt_bool_option(value),
             OptionKind::Http11(value) => self.fmt_bool_option(value),
             OptionKind::Http2(value) => self.fmt_bool_option(value),
             OptionKind::Http3(value) => self.fmt_bool_option(value),
+            OptionKind::Include(filename) => self.fmt_filename(filename),
             OptionKind::Insecure(value) => self.fmt_bool_option(value),
             OptionKind::IpV4(value) => self.fmt_bool_option(value),
             OptionKind::IpV6(value) => self.fmt_bool_option(value),
+            OptionKind::JsonMatch(value) => self.fmt_bool_option(value),
             OptionKind::MaxRedirect(value) => self.fmt_natural_option(value),
+            OptionKind::Name(value) => self.fmt_string(value),
+            OptionKind::OnlyIf(value) => self.fmt_condition_option(value),
             OptionKind::Output(filename) => self.fmt_filename(filename),
             OptionKind::PathAsIs(value) => self.fmt_bool_option(value),
             OptionKind::Proxy(value) => self.fmt_template(value),
+            OptionKind::Repeat(value) => self.fmt_natural_option(value),
             OptionKind::Resolve(value) => self.fmt_template(value),
             OptionKind::Retry(value) => self.fmt_retry_option(value),
+            OptionKind::RetryAfter(value) => self.fmt_bool_option(value),
+            OptionKind::RetryBackoff(value) => self.fmt_bool_option(value),
             OptionKind::RetryInterval(value) => self.fmt_natural_option(value),
-            OptionKind::Skip(value) => self.fmt_bool_option(value),
+            OptionKind::RetryJitter(value) => self.fmt_bool_option(value),
+            OptionKind::RetryMaxInterval(value) => self.fmt_natural_option(value),
+            OptionKind::RetryMaxTime(value) => self.fmt_natural_option(value),
+            OptionKind::Skip(value) => self.fmt_condition_option(value),
+            OptionKind::Tag(value) => self.fmt_string(value),
             OptionKind::Variable(value) => self.fmt_variable_definition(value),
             OptionKind::Verbose(value) => self.fmt_bool_option(value),
             OptionKind::VeryVerbose(value) => self.fmt_bool_option(value),
@@ -341,6 +353,10 @@ impl HtmlFormatter {
             self.fmt_space(space);
             self.fmt_filter(filter);
         }
+        if capture.redact {
+            self.fmt_space(&capture.space3);
+            self.fmt_span("redact", "redact");
+        }
         self.fmt_span_close();
         self.fmt_lt(&capture.line_terminator0);
     }
@@ -518,6 +534,18 @@ impl HtmlFormatter {
                 self.fmt_space(space0);
                 self.fmt_predicate_value(value);
             }
+            PredicateFuncValue::MatchSnapshot { space0, value } => {
+                self.fmt_space(space0);
+                self.fmt_predicate_value(value);
+            }
+            PredicateFuncValue::MatchSchema { space0, value } => {
+                self.fmt_space(space0);
+                self.fmt_predicate_value(value);
+            }
+            PredicateFuncValue::MatchXmlSchema { space0, value } => {
+                self.fmt_space(space0);
+                self.fmt_predicate_value(value);
+            }
             PredicateFuncValue::IsInteger => {}
             PredicateFuncValue::IsFloat => {}
             PredicateFuncValue::IsBoolean => {}
@@ -537,6 +565,7 @@ impl HtmlFormatter {
             PredicateValue::Bool(value) => self.fmt_bool(*value),
             PredicateValue::File(value) => self.fmt_file(value),
             PredicateValue::Hex(value) => self.fmt_hex(value),
+            PredicateValue::Json(value) => self.fmt_json_value(value),
             PredicateValue::Base64(value) => self.fmt_base64(value),
             PredicateValue::Expression(value) => self.fmt_expr(value),
             PredicateValue::Null => self.fmt_span("null", "null"),
@@ -681,6 +710,18 @@ impl HtmlFormatter {
         }
     }
 
+    fn fmt_condition_option(&mut self, value: &ConditionOption) {
+        match value {
+            ConditionOption::Literal(value) => self.fmt_span("boolean", &value.to_string()),
+            ConditionOption::Expression(value) => self.fmt_expr(value),
+            ConditionOption::Predicate(condition) => {
+                self.fmt_expr(&condition.expr);
+                self.fmt_space(&condition.space0);
+                self.fmt_predicate(&condition.predicate);
+            }
+        }
+    }
+
     fn fmt_natural_option(&mut self, value: &NaturalOption) {
         match value {
             NaturalOption::Literal(value) => self.fmt_span("number", &value.to_string()),
@@ -850,7 +891,8 @@ fn escape_xml(s: &str) -> String {
 }
 
 impl Template {
-    fn to_encoded_string(&self) -> String {
+    /// Returns this template with its delimiters and its escaped characters, as in the source.
+    pub(crate) fn to_encoded_string(&self) -> String {
         let mut s = String::new();
         if let Some(d) = self.delimiter {
             s.push(d);
@@ -130,10 +130,12 @@ mod tests {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3)),
                         },
-                        variable: Variable {
+                        kind: ExprKind::Variable(Variable {
                             name: "name".to_string(),
                             source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 7)),
-                        },
+                        }),
+                        default: None,
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 7), Pos::new(1, 7)),
@@ -33,6 +33,7 @@ pub enum ParseError {
     Expecting { value: String },
     FileContentType,
     Filename,
+    Function { name: String },
     GraphQlVariables,
     HexDigit,
     InvalidCookieAttribute,
@@ -42,6 +43,7 @@ pub enum ParseError {
     Method { name: String },
     Multiline,
     OddNumberOfHexDigits,
+    OptionConflict { name: String, other: String },
     Predicate,
     PredicateValue,
     RegexExpr { message: String },
@@ -103,6 +105,7 @@ impl crate::error::Error for Error {
             ParseError::Expecting { .. } => "Parsing literal".to_string(),
             ParseError::FileContentType => "Parsing file content type".to_string(),
             ParseError::Filename => "Parsing filename".to_string(),
+            ParseError::Function { .. } => "Parsing template function".to_string(),
             ParseError::GraphQlVariables => "Parsing GraphQL variables".to_string(),
             ParseError::HexDigit => "Parsing hexadecimal number".to_string(),
             ParseError::InvalidCookieAttribute => "Parsing cookie attribute".to_string(),
@@ -112,6 +115,7 @@ impl crate::error::Error for Error {
             ParseError::Method { .. } => "Parsing method".to_string(),
             ParseError::Multiline => "Parsing multiline".to_string(),
             ParseError::OddNumberOfHexDigits => "Parsing hex bytearray".to_string(),
+            ParseError::OptionConflict { .. } => "Parsing option".to_string(),
             ParseError::Predicate => "Parsing predicate".to_string(),
             ParseError::PredicateValue => "Parsing predicate value".to_string(),
             ParseError::RegexExpr { .. } => "Parsing regex".to_string(),
@@ -138,6 +142,12 @@ impl crate::error::Error for Error {
             ParseError::Expecting { value } => format!("expecting '{value}'"),
             ParseError::FileContentType => "expecting a content type".to_string(),
             ParseError::Filename => "expecting a filename".to_string(),
+            ParseError::Function { name } => {
+                let valid_values = ["env", "newUuid", "now", "randomInt", "randomString"];
+                let default = format!("Valid values are {}", valid_values.join(", "));
+                let did_you_mean = did_you_mean(&valid_values, name.as_str(), &default);
+                format!("the function is not valid. {did_you_mean}")
+            }
             ParseError::GraphQlVariables => {
                 "GraphQL variables is not a valid JSON object".to_string()
             }
@@ -151,6 +161,7 @@ impl crate::error::Error for Error {
                     "compressed",
                     "connect-to",
                     "delay",
+                    "for-each",
                     "insecure",
                     "http1.0",
                     "http1.1",
@@ -158,15 +169,23 @@ impl crate::error::Error for Error {
                     "http3",
                     "ipv4",
                     "ipv6",
+                    "json-match",
                     "key",
                     "location",
                     "max-redirs",
+                    "only-if",
                     "output",
                     "path-as-is",
                     "proxy",
+                    "repeat",
                     "resolve",
                     "retry",
+                    "retry-after",
+                    "retry-backoff",
                     "retry-interval",
+                    "retry-jitter",
+                    "retry-max-interval",
+                    "retry-max-time",
                     "skip",
                     "variable",
                     "verbose",
@@ -198,6 +217,9 @@ impl crate::error::Error for Error {
             ParseError::OddNumberOfHexDigits => {
                 "expecting an even number of hex digits".to_string()
             }
+            ParseError::OptionConflict { name, other } => {
+                format!("the option <{name}> can not be used with the option <{other}>")
+            }
             ParseError::Predicate => "expecting a predicate".to_string(),
             ParseError::PredicateValue => "invalid predicate value".to_string(),
             ParseError::RegexExpr { message } => format!("invalid Regex expression: {message}"),
@@ -16,15 +16,22 @@
  *
  */
 use crate::ast::*;
+use crate::parser::combinators::*;
 use crate::parser::error::*;
+use crate::parser::filter::filter;
+use crate::parser::function;
+use crate::parser::number::number;
 use crate::parser::primitives::*;
 use crate::parser::reader::Reader;
+use crate::parser::string::quoted_template;
 use crate::parser::ParseResult;
 
 pub fn parse(reader: &mut Reader) -> ParseResult<Expr> {
     try_literal("{{", reader)?;
     let space0 = zero_or_more_spaces(reader)?;
-    let variable = variable_name(reader)?;
+    let kind = expr_kind(reader, false)?;
+    let default = expr_default(reader)?;
+    let filters = expr_filters(reader)?;
     let space1 = zero_or_more_spaces(reader)?;
 
     if try_literal("}}}", reader).is_err() {
@@ -33,26 +40,135 @@ pub fn parse(reader: &mut Reader) -> ParseResult<Expr> {
 
     Ok(Expr {
         space0,
-        variable,
+        kind,
+        default,
+        filters,
         space1,
     })
 }
 
 pub fn parse2(reader: &mut Reader) -> ParseResult<Expr> {
+    expr_body(reader, false)
+}
+
+/// Parses the expression of a JSON value, without its enclosing braces.
+///
+/// Contrary to other expressions, `*` is a valid variable name: it's the wildcard placeholder
+/// of JSON match mode, like in `{"id": {{*}}}`.
+pub fn parse_json_value(reader: &mut Reader) -> ParseResult<Expr> {
+    expr_body(reader, true)
+}
+
+fn expr_body(reader: &mut Reader, wildcard: bool) -> ParseResult<Expr> {
     let space0 = zero_or_more_spaces(reader)?;
-    let variable = variable_name(reader)?;
+    let kind = expr_kind(reader, wildcard)?;
+    let default = expr_default(reader)?;
+    let filters = expr_filters(reader)?;
     let space1 = zero_or_more_spaces(reader)?;
 
     Ok(Expr {
         space0,
-        variable,
+        kind,
+        default,
+        filters,
+        space1,
+    })
+}
+
+/// Parses a variable, or a function call if the name is followed by an opening parenthesis.
+/// If `wildcard` is true, the variable can be the `*` placeholder.
+fn expr_kind(reader: &mut Reader, wildcard: bool) -> ParseResult<ExprKind> {
+    let variable = variable_name(reader, wildcard)?;
+    if reader.peek() == Some('(') {
+        let function = function::parse(&variable, reader)?;
+        Ok(ExprKind::Function(Box::new(function)))
+    } else {
+        Ok(ExprKind::Variable(variable))
+    }
+}
+
+/// Parses the default value of an expression, following a `??` operator.
+fn expr_default(reader: &mut Reader) -> ParseResult<Option<Box<ExprDefault>>> {
+    let save = reader.state;
+    let space0 = zero_or_more_spaces(reader)?;
+    if try_literal("??", reader).is_err() {
+        reader.state = save;
+        return Ok(None);
+    }
+    let space1 = zero_or_more_spaces(reader)?;
+    let value = default_value(reader)?;
+    Ok(Some(Box::new(ExprDefault {
+        space0,
         space1,
+        value,
+    })))
+}
+
+fn default_value(reader: &mut Reader) -> ParseResult<VariableValue> {
+    let start = reader.state;
+    choice(
+        &[
+            |p1| match null(p1) {
+                Ok(()) => Ok(VariableValue::Null),
+                Err(e) => Err(e),
+            },
+            |p1| match boolean(p1) {
+                Ok(value) => Ok(VariableValue::Bool(value)),
+                Err(e) => Err(e),
+            },
+            |p1| match number(p1) {
+                Ok(value) => Ok(VariableValue::Number(value)),
+                Err(e) => Err(e),
+            },
+            |p1| match quoted_template(p1) {
+                Ok(value) => Ok(VariableValue::String(value)),
+                Err(e) => Err(e),
+            },
+        ],
+        reader,
+    )
+    .map_err(|_| {
+        // We're inside an expression: a default value is mandatory after `??`.
+        let inner = ParseError::Expecting {
+            value: "default value".to_string(),
+        };
+        Error::new(start.pos, false, inner)
     })
 }
 
-fn variable_name(reader: &mut Reader) -> ParseResult<Variable> {
+/// Parses the chain of filters of an expression, each filter following a pipe.
+fn expr_filters(reader: &mut Reader) -> ParseResult<Vec<ExprFilter>> {
+    let mut filters = vec![];
+    loop {
+        let save = reader.state;
+        let space0 = zero_or_more_spaces(reader)?;
+        if try_literal("|", reader).is_err() {
+            reader.state = save;
+            break;
+        }
+        let space1 = zero_or_more_spaces(reader)?;
+        // We're inside an expression: a filter is mandatory after a pipe.
+        let filter = match filter(reader) {
+            Ok(f) => f,
+            Err(e) => return Err(Error::new(e.pos, false, e.inner)),
+        };
+        filters.push(ExprFilter {
+            space0,
+            space1,
+            filter,
+        });
+    }
+    Ok(filters)
+}
+
+fn variable_name(reader: &mut Reader, wildcard: bool) -> ParseResult<Variable> {
     let start = reader.state;
-    let name = reader.read_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-');
+    // `*` is the wildcard placeholder of JSON match mode: it is only a name on its own.
+    let name = if wildcard && reader.peek() == Some('*') {
+        reader.read_n(1)
+    } else {
+        reader.read_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-')
+    };
     if name.is_empty() {
         return Err(Error::new(start.pos, false, ParseError::TemplateVariable));
     }
@@ -77,10 +193,12 @@ mod tests {
                     value: String::from(" "),
                     source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 4)),
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: String::from("name"),
                     source_info: SourceInfo::new(Pos::new(1, 4), Pos::new(1, 8)),
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 8), Pos::new(1, 8)),
@@ -89,6 +207,103 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_expr_function() {
+        let mut reader = Reader::new("{{newUuid()}}");
+        assert_eq!(
+            parse(&mut reader).unwrap().kind,
+            ExprKind::Function(Box::new(Function {
+                source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 12)),
+                value: FunctionValue::NewUuid {
+                    space0: Whitespace {
+                        value: String::new(),
+                        source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 11)),
+                    }
+                },
+            }))
+        );
+    }
+
+    #[test]
+    fn test_expr_filters() {
+        let mut reader = Reader::new("{{ id | urlEncode | nth 0 }}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(
+            expr.filters
+                .iter()
+                .map(|f| f.filter.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                Filter {
+                    source_info: SourceInfo::new(Pos::new(1, 9), Pos::new(1, 18)),
+                    value: FilterValue::UrlEncode,
+                },
+                Filter {
+                    source_info: SourceInfo::new(Pos::new(1, 21), Pos::new(1, 26)),
+                    value: FilterValue::Nth {
+                        space0: Whitespace {
+                            value: String::from(" "),
+                            source_info: SourceInfo::new(Pos::new(1, 24), Pos::new(1, 25)),
+                        },
+                        n: 0,
+                    },
+                },
+            ]
+        );
+        assert_eq!(expr.space1.value, " ");
+        assert_eq!(expr.to_string(), "id | urlEncode | nth 0");
+    }
+
+    #[test]
+    fn test_expr_filters_error() {
+        let mut reader = Reader::new("{{ id | urlEncode | base64 }}");
+        let error = parse(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos::new(1, 21));
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: String::from("filter")
+            }
+        );
+        assert!(!error.recoverable);
+    }
+
+    #[test]
+    fn test_expr_default() {
+        let mut reader = Reader::new("{{port ?? 8000}}");
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(
+            expr.default,
+            Some(Box::new(ExprDefault {
+                space0: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 7), Pos::new(1, 8)),
+                },
+                space1: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 10), Pos::new(1, 11)),
+                },
+                value: VariableValue::Number(Number::Integer(8000)),
+            }))
+        );
+        assert_eq!(reader.state.cursor, 16);
+
+        let mut reader = Reader::new(r#"{{ name ?? "guest" | urlEncode }}"#);
+        let expr = parse(&mut reader).unwrap();
+        assert_eq!(expr.to_string(), r#"name ?? "guest" | urlEncode"#);
+
+        let mut reader = Reader::new("{{ name ?? }}");
+        let error = parse(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos::new(1, 12));
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: String::from("default value")
+            }
+        );
+        assert!(!error.recoverable);
+    }
+
     #[test]
     fn test_expr_error() {
         let mut reader = Reader::new("{{host>}}");
@@ -121,7 +336,7 @@ mod tests {
     fn test_variable() {
         let mut reader = Reader::new("name");
         assert_eq!(
-            variable_name(&mut reader).unwrap(),
+            variable_name(&mut reader, false).unwrap(),
             Variable {
                 name: String::from("name"),
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 5)),
@@ -130,11 +345,25 @@ mod tests {
 
         let mut reader = Reader::new("my-id");
         assert_eq!(
-            variable_name(&mut reader).unwrap(),
+            variable_name(&mut reader, false).unwrap(),
             Variable {
                 name: String::from("my-id"),
                 source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 6)),
             }
         );
+
+        let mut reader = Reader::new("*}}");
+        let error = variable_name(&mut reader, false).unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 1));
+        assert_eq!(error.inner, ParseError::TemplateVariable);
+
+        let mut reader = Reader::new("*}}");
+        assert_eq!(
+            variable_name(&mut reader, true).unwrap(),
+            Variable {
+                name: String::from("*"),
+                source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 2)),
+            }
+        );
     }
 }
@@ -0,0 +1,193 @@
+/*
+ * Hurl (https://hurl.dev)
+ * Copyright (C) 2023 Orange
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *          http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ *
+ */
+use crate::ast::*;
+use crate::parser::error::*;
+use crate::parser::number::{integer, natural};
+use crate::parser::primitives::*;
+use crate::parser::reader::Reader;
+use crate::parser::string::quoted_oneline_string;
+use crate::parser::ParseResult;
+
+/// Parses the arguments of the function `name`, from its opening parenthesis to its closing one.
+pub fn parse(name: &Variable, reader: &mut Reader) -> ParseResult<Function> {
+    let start = name.source_info.start;
+    literal("(", reader)?;
+    let value = match name.name.as_str() {
+        "env" => {
+            let (space0, name, space1) = argument(reader, quoted_oneline_string)?;
+            FunctionValue::Env {
+                space0,
+                name,
+                space1,
+            }
+        }
+        "newUuid" => {
+            let space0 = zero_or_more_spaces(reader)?;
+            FunctionValue::NewUuid { space0 }
+        }
+        "now" => {
+            let space0 = zero_or_more_spaces(reader)?;
+            FunctionValue::Now { space0 }
+        }
+        "randomInt" => {
+            let (space0, min, space1) = argument(reader, integer)?;
+            literal(",", reader)?;
+            let (space2, max, space3) = argument(reader, integer)?;
+            FunctionValue::RandomInt {
+                space0,
+                min,
+                space1,
+                space2,
+                max,
+                space3,
+            }
+        }
+        "randomString" => {
+            let (space0, length, space1) = argument(reader, natural)?;
+            FunctionValue::RandomString {
+                space0,
+                length,
+                space1,
+            }
+        }
+        _ => {
+            let inner = ParseError::Function {
+                name: name.name.clone(),
+            };
+            return Err(Error::new(start, false, inner));
+        }
+    };
+    literal(")", reader)?;
+    Ok(Function {
+        source_info: SourceInfo::new(start, reader.state.pos),
+        value,
+    })
+}
+
+/// Parses a function argument with the parser `f`, and returns it with the optional spaces
+/// surrounding it.
+fn argument<T>(
+    reader: &mut Reader,
+    f: fn(&mut Reader) -> ParseResult<T>,
+) -> ParseResult<(Whitespace, T, Whitespace)> {
+    let space0 = zero_or_more_spaces(reader)?;
+    let value = match f(reader) {
+        Ok(value) => value,
+        Err(e) => return Err(Error::new(e.pos, false, e.inner)),
+    };
+    let space1 = zero_or_more_spaces(reader)?;
+    Ok((space0, value, space1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn function(s: &str) -> ParseResult<Function> {
+        let mut reader = Reader::new(s);
+        let name = reader.read_while(|c| c.is_alphanumeric());
+        let name = Variable {
+            name,
+            source_info: SourceInfo::new(Pos::new(1, 1), reader.state.pos),
+        };
+        parse(&name, &mut reader)
+    }
+
+    #[test]
+    fn test_function() {
+        assert_eq!(
+            function("now()").unwrap(),
+            Function {
+                source_info: SourceInfo::new(Pos::new(1, 1), Pos::new(1, 6)),
+                value: FunctionValue::Now {
+                    space0: Whitespace {
+                        value: String::new(),
+                        source_info: SourceInfo::new(Pos::new(1, 5), Pos::new(1, 5)),
+                    }
+                },
+            }
+        );
+        assert_eq!(
+            function("randomInt(-1, 100)").unwrap().value,
+            FunctionValue::RandomInt {
+                space0: Whitespace {
+                    value: String::new(),
+                    source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 11)),
+                },
+                min: -1,
+                space1: Whitespace {
+                    value: String::new(),
+                    source_info: SourceInfo::new(Pos::new(1, 13), Pos::new(1, 13)),
+                },
+                space2: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 14), Pos::new(1, 15)),
+                },
+                max: 100,
+                space3: Whitespace {
+                    value: String::new(),
+                    source_info: SourceInfo::new(Pos::new(1, 18), Pos::new(1, 18)),
+                },
+            }
+        );
+        assert_eq!(
+            function("randomString( 12 )").unwrap().value,
+            FunctionValue::RandomString {
+                space0: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 14), Pos::new(1, 15)),
+                },
+                length: 12,
+                space1: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 17), Pos::new(1, 18)),
+                },
+            }
+        );
+        assert!(matches!(
+            function("env(\"HOME\")").unwrap().value,
+            FunctionValue::Env { name, .. } if name == "HOME"
+        ));
+    }
+
+    #[test]
+    fn test_function_error() {
+        let error = function("uuid()").unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 1));
+        assert_eq!(
+            error.inner,
+            ParseError::Function {
+                name: "uuid".to_string()
+            }
+        );
+        assert!(!error.recoverable);
+
+        let error = function("randomInt(1)").unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 12));
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: ",".to_string()
+            }
+        );
+
+        let error = function("randomString(a)").unwrap_err();
+        assert_eq!(error.pos, Pos::new(1, 14));
+        assert!(!error.recoverable);
+    }
+}
@@ -266,11 +266,15 @@ pub fn number_value(reader: &mut Reader) -> ParseResult<JsonValue> {
 }
 
 fn expression_value(reader: &mut Reader) -> ParseResult<JsonValue> {
-    let exp = expr::parse(reader)?;
+    // The closing `}}` is read strictly so that an expression can end an object, like in
+    // `{"id": {{id}}}`.
+    try_literal("{{", reader)?;
+    let exp = expr::parse_json_value(reader)?;
+    literal("}}", reader)?;
     Ok(JsonValue::Expression(exp))
 }
 
-fn list_value(reader: &mut Reader) -> ParseResult<JsonValue> {
+pub fn list_value(reader: &mut Reader) -> ParseResult<JsonValue> {
     try_literal("[", reader)?;
     let space0 = whitespace(reader);
     let mut elements = vec![];
@@ -481,10 +485,12 @@ mod tests {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 15), Pos::new(1, 15)),
                     },
-                    variable: Variable {
+                    kind: ExprKind::Variable(Variable {
                         name: "name".to_string(),
                         source_info: SourceInfo::new(Pos::new(1, 15), Pos::new(1, 19)),
-                    },
+                    }),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 19), Pos::new(1, 19)),
@@ -775,10 +781,12 @@ mod tests {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3))
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: "n".to_string(),
                     source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 4))
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 4), Pos::new(1, 4))
@@ -915,6 +923,13 @@ mod tests {
         );
         assert_eq!(reader.state.cursor, 15);
 
+        let mut reader = Reader::new("{\"a\": {{n}}}");
+        assert!(matches!(
+            object_value(&mut reader).unwrap(),
+            JsonValue::Object { .. }
+        ));
+        assert_eq!(reader.state.cursor, 12);
+
         let mut reader = Reader::new("true");
         let error = object_value(&mut reader).err().unwrap();
         assert_eq!(error.pos, Pos { line: 1, column: 1 });
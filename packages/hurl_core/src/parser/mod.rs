@@ -40,6 +40,7 @@ mod error;
 mod expr;
 mod filename;
 mod filter;
+mod function;
 mod json;
 mod key_string;
 mod multiline;
@@ -20,6 +20,7 @@ use crate::ast::*;
 use crate::parser::combinators::*;
 use crate::parser::error::*;
 use crate::parser::number::{integer, natural, number};
+use crate::parser::predicate::predicate;
 use crate::parser::primitives::*;
 use crate::parser::reader::Reader;
 use crate::parser::string::*;
@@ -45,23 +46,35 @@ pub fn parse(reader: &mut Reader) -> ParseResult<EntryOption> {
         "compressed" => option_compressed(reader)?,
         "connect-to" => option_connect_to(reader)?,
         "delay" => option_delay(reader)?,
+        "for-each" => option_for_each(reader)?,
         "insecure" => option_insecure(reader)?,
         "http1.0" => option_http_10(reader)?,
         "http1.1" => option_http_11(reader)?,
         "http2" => option_http_2(reader)?,
         "http3" => option_http_3(reader)?,
+        "include" => option_include(reader)?,
         "ipv4" => option_ipv4(reader)?,
         "ipv6" => option_ipv6(reader)?,
+        "json-match" => option_json_match(reader)?,
         "key" => option_key(reader)?,
         "location" => option_follow_location(reader)?,
         "max-redirs" => option_max_redirect(reader)?,
+        "name" => option_name(reader)?,
+        "only-if" => option_only_if(reader)?,
         "output" => option_output(reader)?,
         "path-as-is" => option_path_as_is(reader)?,
         "proxy" => option_proxy(reader)?,
+        "repeat" => option_repeat(reader)?,
         "resolve" => option_resolve(reader)?,
         "retry" => option_retry(reader)?,
+        "retry-after" => option_retry_after(reader)?,
+        "retry-backoff" => option_retry_backoff(reader)?,
         "retry-interval" => option_retry_interval(reader)?,
+        "retry-jitter" => option_retry_jitter(reader)?,
+        "retry-max-interval" => option_retry_max_interval(reader)?,
+        "retry-max-time" => option_retry_max_time(reader)?,
         "skip" => option_skip(reader)?,
+        "tag" => option_tag(reader)?,
         "variable" => option_variable(reader)?,
         "verbose" => option_verbose(reader)?,
         "very-verbose" => option_very_verbose(reader)?,
@@ -120,6 +133,16 @@ fn option_follow_location(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::FollowLocation(value))
 }
 
+fn option_for_each(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = expr::parse(reader).map_err(|e| {
+        let inner = ParseError::Expecting {
+            value: "list variable".to_string(),
+        };
+        Error::new(e.pos, false, inner)
+    })?;
+    Ok(OptionKind::ForEach(value))
+}
+
 fn option_http_10(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = nonrecover(boolean_option, reader)?;
     Ok(OptionKind::Http10(value))
@@ -140,6 +163,11 @@ fn option_http_3(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::Http3(value))
 }
 
+fn option_include(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = filename::parse(reader)?;
+    Ok(OptionKind::Include(value))
+}
+
 fn option_insecure(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = nonrecover(boolean_option, reader)?;
     Ok(OptionKind::Insecure(value))
@@ -155,6 +183,11 @@ fn option_ipv6(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::IpV6(value))
 }
 
+fn option_json_match(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(boolean_option, reader)?;
+    Ok(OptionKind::JsonMatch(value))
+}
+
 fn option_key(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = filename::parse(reader)?;
     Ok(OptionKind::ClientKey(value))
@@ -165,6 +198,16 @@ fn option_max_redirect(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::MaxRedirect(value))
 }
 
+fn option_name(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = label("entry name", reader)?;
+    Ok(OptionKind::Name(value))
+}
+
+fn option_only_if(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(condition_option, reader)?;
+    Ok(OptionKind::OnlyIf(value))
+}
+
 fn option_output(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = filename::parse(reader)?;
     Ok(OptionKind::Output(value))
@@ -180,6 +223,11 @@ fn option_proxy(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::Proxy(value))
 }
 
+fn option_repeat(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(natural_option, reader)?;
+    Ok(OptionKind::Repeat(value))
+}
+
 fn option_resolve(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = unquoted_template(reader)?;
     Ok(OptionKind::Resolve(value))
@@ -190,16 +238,46 @@ fn option_retry(reader: &mut Reader) -> ParseResult<OptionKind> {
     Ok(OptionKind::Retry(value))
 }
 
+fn option_retry_after(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(boolean_option, reader)?;
+    Ok(OptionKind::RetryAfter(value))
+}
+
+fn option_retry_backoff(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(boolean_option, reader)?;
+    Ok(OptionKind::RetryBackoff(value))
+}
+
 fn option_retry_interval(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = nonrecover(natural_option, reader)?;
     Ok(OptionKind::RetryInterval(value))
 }
 
-fn option_skip(reader: &mut Reader) -> ParseResult<OptionKind> {
+fn option_retry_jitter(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = nonrecover(boolean_option, reader)?;
+    Ok(OptionKind::RetryJitter(value))
+}
+
+fn option_retry_max_interval(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(natural_option, reader)?;
+    Ok(OptionKind::RetryMaxInterval(value))
+}
+
+fn option_retry_max_time(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(natural_option, reader)?;
+    Ok(OptionKind::RetryMaxTime(value))
+}
+
+fn option_skip(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = nonrecover(condition_option, reader)?;
     Ok(OptionKind::Skip(value))
 }
 
+fn option_tag(reader: &mut Reader) -> ParseResult<OptionKind> {
+    let value = label("tag", reader)?;
+    Ok(OptionKind::Tag(value))
+}
+
 fn option_variable(reader: &mut Reader) -> ParseResult<OptionKind> {
     let value = variable_definition(reader)?;
     Ok(OptionKind::Variable(value))
@@ -249,6 +327,46 @@ fn boolean_option(reader: &mut Reader) -> ParseResult<BooleanOption> {
     }
 }
 
+/// Parses a condition: a boolean, an expression, or an expression followed by a predicate.
+fn condition_option(reader: &mut Reader) -> ParseResult<ConditionOption> {
+    let start = reader.state;
+    if let Ok(v) = boolean(reader) {
+        return Ok(ConditionOption::Literal(v));
+    }
+    reader.state = start;
+    let expr = expr::parse(reader).map_err(|e| {
+        let inner = ParseError::Expecting {
+            value: "true|false|condition".to_string(),
+        };
+        Error::new(e.pos, false, inner)
+    })?;
+    let save = reader.state;
+    let space0 = match one_or_more_spaces(reader) {
+        Ok(space0) => space0,
+        Err(_) => {
+            reader.state = save;
+            return Ok(ConditionOption::Expression(expr));
+        }
+    };
+    // A trailing comment is not a predicate.
+    if matches!(reader.peek(), None | Some('#' | '\n' | '\r')) {
+        reader.state = save;
+        return Ok(ConditionOption::Expression(expr));
+    }
+    match predicate(reader) {
+        Ok(predicate) => Ok(ConditionOption::Predicate(Condition {
+            expr,
+            space0,
+            predicate,
+        })),
+        Err(e) if e.recoverable => {
+            reader.state = save;
+            Ok(ConditionOption::Expression(expr))
+        }
+        Err(e) => Err(e),
+    }
+}
+
 fn natural_option(reader: &mut Reader) -> ParseResult<NaturalOption> {
     let start = reader.state;
     match natural(reader) {
@@ -309,6 +427,20 @@ fn variable_name(reader: &mut Reader) -> ParseResult<String> {
     Ok(name)
 }
 
+/// Parses a label, used for entries names and tags: a non-empty sequence of alphanumeric
+/// characters, `_`, `-` and `.`.
+fn label(kind: &str, reader: &mut Reader) -> ParseResult<String> {
+    let start = reader.state;
+    let label = reader.read_while(|c| c.is_alphanumeric() || *c == '_' || *c == '-' || *c == '.');
+    if label.is_empty() {
+        let inner = ParseError::Expecting {
+            value: kind.to_string(),
+        };
+        return Err(Error::new(start.pos, false, inner));
+    }
+    Ok(label)
+}
+
 fn variable_value(reader: &mut Reader) -> ParseResult<VariableValue> {
     choice(
         &[
@@ -517,6 +649,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_option_name_and_tag() {
+        let mut reader = Reader::new("name: login");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(option.kind, OptionKind::Name("login".to_string()));
+
+        let mut reader = Reader::new("tag: smoke-test.v2 # comment");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(option.kind, OptionKind::Tag("smoke-test.v2".to_string()));
+    }
+
+    #[test]
+    fn test_option_include() {
+        let mut reader = Reader::new("include: common/login.hurl");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(
+            option.kind,
+            OptionKind::Include(Filename {
+                value: "common/login.hurl".to_string(),
+                source_info: SourceInfo::new(Pos::new(1, 10), Pos::new(1, 27)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_option_repeat() {
+        let mut reader = Reader::new("repeat: 10");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(option.kind, OptionKind::Repeat(NaturalOption::Literal(10)));
+
+        let mut reader = Reader::new("for-each: {{ ids }}");
+        let option = parse(&mut reader).unwrap();
+        assert!(matches!(option.kind, OptionKind::ForEach(_)));
+        assert_eq!(option.kind.value_as_str(), "{{ids}}");
+
+        let mut reader = Reader::new("for-each: ids");
+        let error = parse(&mut reader).err().unwrap();
+        assert!(!error.recoverable);
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: "list variable".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_option_condition() {
+        let mut reader = Reader::new("skip: true");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(
+            option.kind,
+            OptionKind::Skip(ConditionOption::Literal(true))
+        );
+
+        let mut reader = Reader::new("skip: {{dry_run}} # comment");
+        let option = parse(&mut reader).unwrap();
+        assert!(matches!(
+            option.kind,
+            OptionKind::Skip(ConditionOption::Expression(_))
+        ));
+        assert_eq!(option.kind.value_as_str(), "{{dry_run}}");
+
+        let mut reader = Reader::new("skip: {{dry_run}}\n# comment");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(option.line_terminator0.newline.value, "\n");
+
+        let mut reader = Reader::new("only-if: {{env}} == \"prod\"");
+        let option = parse(&mut reader).unwrap();
+        let condition = match option.kind {
+            OptionKind::OnlyIf(ConditionOption::Predicate(condition)) => condition,
+            _ => panic!("only-if option with a predicate expected"),
+        };
+        assert_eq!(condition.expr.to_string(), "env");
+        assert_eq!(condition.predicate.to_string(), "== \"prod\"");
+
+        let mut reader = Reader::new("only-if: {{flags}} not contains \"beta\"");
+        let option = parse(&mut reader).unwrap();
+        assert_eq!(
+            option.kind.value_as_str(),
+            "{{flags}} not contains \"beta\""
+        );
+    }
+
+    #[test]
+    fn test_option_condition_error() {
+        let mut reader = Reader::new("only-if: env == \"prod\"");
+        let error = parse(&mut reader).err().unwrap();
+        assert!(!error.recoverable);
+        assert_eq!(error.pos, Pos::new(1, 10));
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: "true|false|condition".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_label_error() {
+        let mut reader = Reader::new("tag: {{tag}}");
+        let error = parse(&mut reader).err().unwrap();
+        assert!(!error.recoverable);
+        assert_eq!(
+            error.inner,
+            ParseError::Expecting {
+                value: "tag".to_string()
+            }
+        );
+    }
+
     #[test]
     fn test_variable_definition() {
         let mut reader = Reader::new("a=1");
@@ -80,6 +80,9 @@ fn predicate_func_value(reader: &mut Reader) -> ParseResult<PredicateFuncValue>
             end_with_predicate,
             contain_predicate,
             include_predicate,
+            match_snapshot_predicate,
+            match_schema_predicate,
+            match_xml_schema_predicate,
             match_predicate,
             integer_predicate,
             float_predicate,
@@ -114,6 +117,10 @@ impl PredicateValue {
     pub fn is_expression(&self) -> bool {
         matches!(self, PredicateValue::Expression(_))
     }
+
+    pub fn is_file(&self) -> bool {
+        matches!(self, PredicateValue::File(_))
+    }
 }
 
 fn equal_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
@@ -295,6 +302,39 @@ fn match_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
     Ok(PredicateFuncValue::Match { space0, value })
 }
 
+fn match_snapshot_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
+    try_literal("matchesSnapshot", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let save = reader.state;
+    let value = predicate_value(reader)?;
+    if !value.is_string() {
+        return Err(Error::new(save.pos, false, ParseError::PredicateValue));
+    }
+    Ok(PredicateFuncValue::MatchSnapshot { space0, value })
+}
+
+fn match_schema_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
+    try_literal("matchesSchema", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let save = reader.state;
+    let value = predicate_value(reader)?;
+    if !value.is_file() {
+        return Err(Error::new(save.pos, false, ParseError::PredicateValue));
+    }
+    Ok(PredicateFuncValue::MatchSchema { space0, value })
+}
+
+fn match_xml_schema_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
+    try_literal("matchesXmlSchema", reader)?;
+    let space0 = one_or_more_spaces(reader)?;
+    let save = reader.state;
+    let value = predicate_value(reader)?;
+    if !value.is_file() {
+        return Err(Error::new(save.pos, false, ParseError::PredicateValue));
+    }
+    Ok(PredicateFuncValue::MatchXmlSchema { space0, value })
+}
+
 fn integer_predicate(reader: &mut Reader) -> ParseResult<PredicateFuncValue> {
     try_literal("isInteger", reader)?;
     Ok(PredicateFuncValue::IsInteger)
@@ -481,10 +521,12 @@ mod tests {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 6), Pos::new(1, 6)),
                     },
-                    variable: Variable {
+                    kind: ExprKind::Variable(Variable {
                         name: "count".to_string(),
                         source_info: SourceInfo::new(Pos::new(1, 6), Pos::new(1, 11)),
-                    },
+                    }),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 11)),
@@ -514,6 +556,73 @@ mod tests {
         assert_eq!(error.inner, ParseError::PredicateValue);
     }
 
+    #[test]
+    fn test_match_snapshot_predicate() {
+        let mut reader = Reader::new("matchesSnapshot \"users.json\"");
+        let result = predicate_func(&mut reader).unwrap();
+        assert_eq!(
+            result.value,
+            PredicateFuncValue::MatchSnapshot {
+                space0: Whitespace {
+                    value: String::from(" "),
+                    source_info: SourceInfo::new(Pos::new(1, 16), Pos::new(1, 17)),
+                },
+                value: PredicateValue::String(Template {
+                    delimiter: Some('"'),
+                    elements: vec![TemplateElement::String {
+                        value: "users.json".to_string(),
+                        encoded: "users.json".to_string(),
+                    }],
+                    source_info: SourceInfo::new(Pos::new(1, 17), Pos::new(1, 29)),
+                }),
+            }
+        );
+
+        let mut reader = Reader::new("matchesSnapshot 2");
+        let error = match_snapshot_predicate(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos::new(1, 17));
+        assert!(!error.recoverable);
+        assert_eq!(error.inner, ParseError::PredicateValue);
+    }
+
+    #[test]
+    fn test_match_schema_predicate() {
+        let mut reader = Reader::new("matchesSchema file,user.json;");
+        let result = match_schema_predicate(&mut reader).unwrap();
+        assert!(matches!(
+            result,
+            PredicateFuncValue::MatchSchema {
+                value: PredicateValue::File(_),
+                ..
+            }
+        ));
+
+        let mut reader = Reader::new("matchesSchema \"user.json\"");
+        let error = match_schema_predicate(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos::new(1, 15));
+        assert!(!error.recoverable);
+        assert_eq!(error.inner, ParseError::PredicateValue);
+    }
+
+    #[test]
+    fn test_match_xml_schema_predicate() {
+        let mut reader = Reader::new("matchesXmlSchema file,users.xsd;");
+        let result = match_xml_schema_predicate(&mut reader).unwrap();
+        assert!(matches!(
+            result,
+            PredicateFuncValue::MatchXmlSchema {
+                value: PredicateValue::File(_),
+                ..
+            }
+        ));
+
+        let mut reader = Reader::new("matchesXmlSchema 1");
+        let error = match_xml_schema_predicate(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos::new(1, 18));
+        assert!(!error.recoverable);
+        assert_eq!(error.inner, ParseError::PredicateValue);
+    }
+
     #[test]
     fn test_date_predicate() {
         let mut reader = Reader::new("isDate");
@@ -22,7 +22,7 @@ use crate::parser::number::number;
 use crate::parser::primitives::*;
 use crate::parser::reader::Reader;
 use crate::parser::string::*;
-use crate::parser::{expr, Error, ParseError, ParseResult};
+use crate::parser::{expr, json, Error, ParseError, ParseResult};
 
 pub fn predicate_value(reader: &mut Reader) -> ParseResult<PredicateValue> {
     choice(
@@ -55,6 +55,14 @@ pub fn predicate_value(reader: &mut Reader) -> ParseResult<PredicateValue> {
                 Ok(value) => Ok(PredicateValue::Expression(value)),
                 Err(e) => Err(e),
             },
+            |p1| match json::object_value(p1) {
+                Ok(value) => Ok(PredicateValue::Json(value)),
+                Err(e) => Err(e),
+            },
+            |p1| match json::list_value(p1) {
+                Ok(value) => Ok(PredicateValue::Json(value)),
+                Err(e) => Err(e),
+            },
             |p1| match quoted_template(p1) {
                 Ok(value) => Ok(PredicateValue::String(value)),
                 Err(e) => Err(e),
@@ -108,6 +116,21 @@ mod tests {
                 encoded: "1.1".to_string(),
             }))
         );
+
+        let mut reader = Reader::new("{\"id\": {{uuid}}, \"tags\": [\"a\"]}");
+        let value = predicate_value(&mut reader).unwrap();
+        assert!(matches!(
+            value,
+            PredicateValue::Json(JsonValue::Object { .. })
+        ));
+        assert_eq!(value.to_string(), "{\"id\": {{uuid}}, \"tags\": [\"a\"]}");
+
+        let mut reader = Reader::new("[1, {{*}}]");
+        let value = predicate_value(&mut reader).unwrap();
+        assert!(matches!(
+            value,
+            PredicateValue::Json(JsonValue::List { .. })
+        ));
     }
 
     #[test]
@@ -636,10 +636,12 @@ mod tests {
                                 value: String::new(),
                                 source_info: SourceInfo::new(Pos::new(1, 18), Pos::new(1, 18)),
                             },
-                            variable: Variable {
+                            kind: ExprKind::Variable(Variable {
                                 name: "name".to_string(),
                                 source_info: SourceInfo::new(Pos::new(1, 18), Pos::new(1, 22)),
-                            },
+                            }),
+                            default: None,
+                            filters: vec![],
                             space1: Whitespace {
                                 value: String::new(),
                                 source_info: SourceInfo::new(Pos::new(1, 22), Pos::new(1, 22)),
@@ -688,10 +690,12 @@ mod tests {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3)),
                         },
-                        variable: Variable {
+                        kind: ExprKind::Variable(Variable {
                             name: "key".to_string(),
                             source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 6))
-                        },
+                        }),
+                        default: None,
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 6), Pos::new(1, 6)),
@@ -150,6 +150,21 @@ fn section_value_asserts(reader: &mut Reader) -> ParseResult<SectionValue> {
 
 fn section_value_options(reader: &mut Reader) -> ParseResult<SectionValue> {
     let options = zero_or_more(option::parse, reader)?;
+
+    // An entry is either repeated a number of times, or once for each element of a list.
+    let repeat = options
+        .iter()
+        .any(|o| matches!(o.kind, OptionKind::Repeat(_)));
+    let for_each = options
+        .iter()
+        .find(|o| matches!(o.kind, OptionKind::ForEach(_)));
+    if let (true, Some(for_each)) = (repeat, for_each) {
+        let inner = ParseError::OptionConflict {
+            name: "for-each".to_string(),
+            other: "repeat".to_string(),
+        };
+        return Err(Error::new(for_each.space0.source_info.end, false, inner));
+    }
     Ok(SectionValue::Options(options))
 }
 
@@ -280,6 +295,7 @@ fn capture(reader: &mut Reader) -> ParseResult<Capture> {
     let space2 = zero_or_more_spaces(reader)?;
     let q = query(reader)?;
     let filters = filters(reader)?;
+    let (space3, redact) = capture_redact(reader)?;
     let line_terminator0 = line_terminator(reader)?;
     Ok(Capture {
         line_terminators,
@@ -289,10 +305,27 @@ fn capture(reader: &mut Reader) -> ParseResult<Capture> {
         space2,
         query: q,
         filters,
+        space3,
+        redact,
         line_terminator0,
     })
 }
 
+/// Parses the optional `redact` keyword of a capture, marking the captured value as secret.
+fn capture_redact(reader: &mut Reader) -> ParseResult<(Whitespace, bool)> {
+    let save = reader.state;
+    let space = zero_or_more_spaces(reader)?;
+    if !space.value.is_empty() && try_literal("redact", reader).is_ok() {
+        return Ok((space, true));
+    }
+    reader.state = save;
+    let space = Whitespace {
+        value: String::new(),
+        source_info: SourceInfo::new(reader.state.pos, reader.state.pos),
+    };
+    Ok((space, false))
+}
+
 fn assert(reader: &mut Reader) -> ParseResult<Assert> {
     let line_terminators = optional_line_terminators(reader)?;
     let space0 = zero_or_more_spaces(reader)?;
@@ -445,6 +478,21 @@ mod tests {
         assert!(!error.recoverable);
     }
 
+    #[test]
+    fn test_options_section_error() {
+        let mut reader = Reader::new("[Options]\nrepeat: 2\nfor-each: {{ids}}\n");
+        let error = request_section(&mut reader).err().unwrap();
+        assert_eq!(error.pos, Pos { line: 3, column: 1 });
+        assert_eq!(
+            error.inner,
+            ParseError::OptionConflict {
+                name: "for-each".to_string(),
+                other: "repeat".to_string(),
+            }
+        );
+        assert!(!error.recoverable);
+    }
+
     #[test]
     fn test_cookie() {
         let mut reader = Reader::new("Foo: Bar");
@@ -623,6 +671,26 @@ mod tests {
         assert_eq!(reader.state.cursor, 43);
     }
 
+    #[test]
+    fn test_capture_redact() {
+        let mut reader = Reader::new("token: header \"X-Token\" redact\n");
+        let capture0 = capture(&mut reader).unwrap();
+        assert!(capture0.redact);
+        assert_eq!(
+            capture0.space3,
+            Whitespace {
+                value: String::from(" "),
+                source_info: SourceInfo::new(Pos::new(1, 24), Pos::new(1, 25)),
+            }
+        );
+        assert_eq!(reader.state.cursor, 31);
+
+        let mut reader = Reader::new("token: header \"X-Token\"\n");
+        let capture0 = capture(&mut reader).unwrap();
+        assert!(!capture0.redact);
+        assert_eq!(capture0.space3.value, "");
+    }
+
     #[test]
     fn test_capture_with_filter_error() {
         let mut reader = Reader::new("token: header \"Location\" regex ");
@@ -329,10 +329,12 @@ mod tests {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 14), Pos::new(1, 14)),
                         },
-                        variable: Variable {
+                        kind: ExprKind::Variable(Variable {
                             name: "name".to_string(),
                             source_info: SourceInfo::new(Pos::new(1, 14), Pos::new(1, 18)),
-                        },
+                        }),
+                        default: None,
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 18), Pos::new(1, 18)),
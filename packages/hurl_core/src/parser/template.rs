@@ -90,7 +90,9 @@ pub fn templatize(encoded_string: EncodedString) -> ParseResult<Vec<TemplateElem
 
             State::FirstCloseBracket => {
                 if s.as_str() == "}" {
-                    let mut reader = Reader::new(encoded.as_str());
+                    // The expression is parsed from its unescaped value, so that quotes can be
+                    // escaped in a quoted string: `"{{name ?? \"guest\"}}"`.
+                    let mut reader = Reader::new(value.as_str());
                     reader.state = ReaderState {
                         cursor: 0,
                         pos: expression_start.unwrap(),
@@ -138,7 +140,7 @@ pub fn templatize(encoded_string: EncodedString) -> ParseResult<Vec<TemplateElem
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ast::{Expr, Variable, Whitespace};
+    use crate::ast::{Expr, ExprKind, Variable, Whitespace};
 
     #[test]
     fn test_templatize_empty_string() {
@@ -237,10 +239,12 @@ mod tests {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 11)),
                     },
-                    variable: Variable {
+                    kind: ExprKind::Variable(Variable {
                         name: "name".to_string(),
                         source_info: SourceInfo::new(Pos::new(1, 11), Pos::new(1, 15)),
-                    },
+                    }),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info: SourceInfo::new(Pos::new(1, 15), Pos::new(1, 15)),
@@ -274,10 +278,12 @@ mod tests {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 3)),
                 },
-                variable: Variable {
+                kind: ExprKind::Variable(Variable {
                     name: "x".to_string(),
                     source_info: SourceInfo::new(Pos::new(1, 3), Pos::new(1, 4)),
-                },
+                }),
+                default: None,
+                filters: vec![],
                 space1: Whitespace {
                     value: String::new(),
                     source_info: SourceInfo::new(Pos::new(1, 4), Pos::new(1, 4)),
@@ -182,10 +182,12 @@ mod tests {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 10), Pos::new(1, 10)),
                         },
-                        variable: Variable {
+                        kind: ExprKind::Variable(Variable {
                             name: String::from("host"),
                             source_info: SourceInfo::new(Pos::new(1, 10), Pos::new(1, 14)),
-                        },
+                        }),
+                        default: None,
+                        filters: vec![],
                         space1: Whitespace {
                             value: String::new(),
                             source_info: SourceInfo::new(Pos::new(1, 14), Pos::new(1, 14)),
@@ -288,21 +288,33 @@ impl ToJson for EntryOption {
             OptionKind::ConnectTo(value) => JValue::String(value.to_string()),
             OptionKind::Delay(value) => value.to_json(),
             OptionKind::FollowLocation(value) => value.to_json(),
+            OptionKind::ForEach(value) => value.to_json(),
             OptionKind::Http10(value) => value.to_json(),
             OptionKind::Http11(value) => value.to_json(),
             OptionKind::Http2(value) => value.to_json(),
             OptionKind::Http3(value) => value.to_json(),
+            OptionKind::Include(filename) => JValue::String(filename.value.clone()),
             OptionKind::Insecure(value) => value.to_json(),
             OptionKind::IpV4(value) => value.to_json(),
             OptionKind::IpV6(value) => value.to_json(),
+            OptionKind::JsonMatch(value) => value.to_json(),
             OptionKind::MaxRedirect(value) => value.to_json(),
+            OptionKind::Name(value) => JValue::String(value.clone()),
+            OptionKind::OnlyIf(value) => value.to_json(),
             OptionKind::Output(filename) => JValue::String(filename.value.clone()),
             OptionKind::PathAsIs(value) => value.to_json(),
             OptionKind::Proxy(value) => JValue::String(value.to_string()),
+            OptionKind::Repeat(value) => value.to_json(),
             OptionKind::Resolve(value) => JValue::String(value.to_string()),
             OptionKind::Retry(value) => value.to_json(),
+            OptionKind::RetryAfter(value) => value.to_json(),
+            OptionKind::RetryBackoff(value) => value.to_json(),
             OptionKind::RetryInterval(value) => value.to_json(),
+            OptionKind::RetryJitter(value) => value.to_json(),
+            OptionKind::RetryMaxInterval(value) => value.to_json(),
+            OptionKind::RetryMaxTime(value) => value.to_json(),
             OptionKind::Skip(value) => value.to_json(),
+            OptionKind::Tag(value) => JValue::String(value.clone()),
             OptionKind::Variable(value) => {
                 JValue::String(format!("{}={}", value.name, value.value))
             }
@@ -324,6 +336,19 @@ impl ToJson for BooleanOption {
     }
 }
 
+impl ToJson for ConditionOption {
+    fn to_json(&self) -> JValue {
+        match self {
+            ConditionOption::Literal(value) => JValue::Boolean(*value),
+            ConditionOption::Expression(expr) => expr.to_json(),
+            ConditionOption::Predicate(condition) => JValue::Object(vec![
+                ("expr".to_string(), condition.expr.to_json()),
+                ("predicate".to_string(), condition.predicate.to_json()),
+            ]),
+        }
+    }
+}
+
 impl ToJson for NaturalOption {
     fn to_json(&self) -> JValue {
         match self {
@@ -362,6 +387,9 @@ impl ToJson for Capture {
             let filters = JValue::List(self.filters.iter().map(|(_, f)| f.to_json()).collect());
             attributes.push(("filters".to_string(), filters));
         }
+        if self.redact {
+            attributes.push(("redact".to_string(), JValue::Boolean(true)));
+        }
         JValue::Object(attributes)
     }
 }
@@ -536,6 +564,21 @@ impl ToJson for Predicate {
                 attributes.push(("type".to_string(), JValue::String("match".to_string())));
                 add_predicate_value(&mut attributes, value);
             }
+            PredicateFuncValue::MatchSnapshot { value, .. } => {
+                let kind = JValue::String("match-snapshot".to_string());
+                attributes.push(("type".to_string(), kind));
+                add_predicate_value(&mut attributes, value);
+            }
+            PredicateFuncValue::MatchSchema { value, .. } => {
+                let kind = JValue::String("match-schema".to_string());
+                attributes.push(("type".to_string(), kind));
+                add_predicate_value(&mut attributes, value);
+            }
+            PredicateFuncValue::MatchXmlSchema { value, .. } => {
+                let kind = JValue::String("match-xml-schema".to_string());
+                attributes.push(("type".to_string(), kind));
+                add_predicate_value(&mut attributes, value);
+            }
             PredicateFuncValue::IsInteger => {
                 attributes.push(("type".to_string(), JValue::String("isInteger".to_string())));
             }
@@ -593,6 +636,7 @@ fn json_predicate_value(predicate_value: PredicateValue) -> (JValue, Option<Stri
             (JValue::String(base64_string), Some("base64".to_string()))
         }
         PredicateValue::Expression(value) => (JValue::String(value.to_string()), None),
+        PredicateValue::Json(value) => (value.to_json(), Some("json".to_string())),
         PredicateValue::Regex(value) => {
             (JValue::String(value.to_string()), Some("regex".to_string()))
         }
@@ -892,6 +936,8 @@ pub mod tests {
             space2: whitespace(),
             query: header_query(),
             filters: vec![],
+            space3: whitespace(),
+            redact: false,
             line_terminator0: line_terminator(),
         }
     }
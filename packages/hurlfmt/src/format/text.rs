@@ -123,3 +123,19 @@ pub fn format_token(token: Token, color: bool) -> String {
         Token::Lang(value) => value,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::parser;
+
+    use super::*;
+
+    #[test]
+    fn format_keeps_function_whitespaces() {
+        let content = "GET http://localhost:8000/{{ randomString( 12 ) }}/{{randomInt(1 ,  5)}}\n\
+            x-env: {{env( \"HOME\" )}}\n\
+            x-date: {{now( )}}\n";
+        let hurl_file = parser::parse_hurl_file(content).unwrap();
+        assert_eq!(format(hurl_file, false), content);
+    }
+}
@@ -363,6 +363,10 @@ impl Tokenizable for Capture {
             tokens.append(&mut space.tokenize());
             tokens.append(&mut filter.tokenize());
         }
+        if self.redact {
+            tokens.append(&mut self.space3.tokenize());
+            tokens.push(Token::Keyword(String::from("redact")));
+        }
         tokens.append(&mut self.line_terminator0.tokenize());
         tokens
     }
@@ -576,6 +580,21 @@ impl Tokenizable for PredicateFuncValue {
                 tokens.append(&mut space0.tokenize());
                 tokens.append(&mut value.tokenize());
             }
+            PredicateFuncValue::MatchSnapshot { space0, value } => {
+                tokens.push(Token::PredicateType(self.name()));
+                tokens.append(&mut space0.tokenize());
+                tokens.append(&mut value.tokenize());
+            }
+            PredicateFuncValue::MatchSchema { space0, value } => {
+                tokens.push(Token::PredicateType(self.name()));
+                tokens.append(&mut space0.tokenize());
+                tokens.append(&mut value.tokenize());
+            }
+            PredicateFuncValue::MatchXmlSchema { space0, value } => {
+                tokens.push(Token::PredicateType(self.name()));
+                tokens.append(&mut space0.tokenize());
+                tokens.append(&mut value.tokenize());
+            }
 
             PredicateFuncValue::IsInteger => {
                 tokens.push(Token::PredicateType(self.name()));
@@ -616,6 +635,7 @@ impl Tokenizable for PredicateValue {
             PredicateValue::Number(value) => vec![Token::Number(value.to_string())],
             PredicateValue::File(value) => value.tokenize(),
             PredicateValue::Hex(value) => vec![Token::String(value.to_string())],
+            PredicateValue::Json(value) => value.tokenize(),
             PredicateValue::Base64(value) => value.tokenize(),
             PredicateValue::Expression(value) => value.tokenize(),
             PredicateValue::Regex(value) => value.tokenize(),
@@ -732,13 +752,86 @@ impl Tokenizable for Expr {
     fn tokenize(&self) -> Vec<Token> {
         let mut tokens: Vec<Token> = vec![Token::CodeDelimiter(String::from("{{"))];
         tokens.append(&mut self.space0.tokenize());
-        tokens.push(Token::CodeVariable(self.variable.name.clone()));
+        tokens.append(&mut self.kind.tokenize());
+        if let Some(default) = &self.default {
+            tokens.append(&mut default.space0.tokenize());
+            tokens.push(Token::CodeDelimiter(String::from("??")));
+            tokens.append(&mut default.space1.tokenize());
+            tokens.append(&mut default.value.tokenize());
+        }
+        for expr_filter in self.filters.iter() {
+            tokens.append(&mut expr_filter.space0.tokenize());
+            tokens.push(Token::CodeDelimiter(String::from("|")));
+            tokens.append(&mut expr_filter.space1.tokenize());
+            tokens.append(&mut expr_filter.filter.tokenize());
+        }
         tokens.append(&mut self.space1.tokenize());
         tokens.push(Token::CodeDelimiter(String::from("}}")));
         tokens
     }
 }
 
+impl Tokenizable for ExprKind {
+    fn tokenize(&self) -> Vec<Token> {
+        match self {
+            ExprKind::Variable(variable) => vec![Token::CodeVariable(variable.name.clone())],
+            ExprKind::Function(function) => function.tokenize(),
+        }
+    }
+}
+
+impl Tokenizable for Function {
+    fn tokenize(&self) -> Vec<Token> {
+        let mut tokens: Vec<Token> = vec![
+            Token::CodeVariable(self.value.name().to_string()),
+            Token::CodeDelimiter(String::from("(")),
+        ];
+        match &self.value {
+            FunctionValue::Env {
+                space0,
+                name,
+                space1,
+            } => {
+                tokens.append(&mut space0.tokenize());
+                tokens.push(Token::StringDelimiter(String::from("\"")));
+                tokens.push(Token::String(name.clone()));
+                tokens.push(Token::StringDelimiter(String::from("\"")));
+                tokens.append(&mut space1.tokenize());
+            }
+            FunctionValue::NewUuid { space0 } | FunctionValue::Now { space0 } => {
+                tokens.append(&mut space0.tokenize());
+            }
+            FunctionValue::RandomInt {
+                space0,
+                min,
+                space1,
+                space2,
+                max,
+                space3,
+            } => {
+                tokens.append(&mut space0.tokenize());
+                tokens.push(Token::Number(min.to_string()));
+                tokens.append(&mut space1.tokenize());
+                tokens.push(Token::CodeDelimiter(String::from(",")));
+                tokens.append(&mut space2.tokenize());
+                tokens.push(Token::Number(max.to_string()));
+                tokens.append(&mut space3.tokenize());
+            }
+            FunctionValue::RandomString {
+                space0,
+                length,
+                space1,
+            } => {
+                tokens.append(&mut space0.tokenize());
+                tokens.push(Token::Number(length.to_string()));
+                tokens.append(&mut space1.tokenize());
+            }
+        }
+        tokens.push(Token::CodeDelimiter(String::from(")")));
+        tokens
+    }
+}
+
 impl Tokenizable for Regex {
     fn tokenize(&self) -> Vec<Token> {
         let s = str::replace(self.inner.as_str(), "/", "\\/");
@@ -884,21 +977,33 @@ impl Tokenizable for OptionKind {
             OptionKind::ConnectTo(value) => value.tokenize(),
             OptionKind::Delay(value) => value.tokenize(),
             OptionKind::FollowLocation(value) => value.tokenize(),
+            OptionKind::ForEach(value) => value.tokenize(),
             OptionKind::Http10(value) => value.tokenize(),
             OptionKind::Http11(value) => value.tokenize(),
             OptionKind::Http2(value) => value.tokenize(),
             OptionKind::Http3(value) => value.tokenize(),
+            OptionKind::Include(filename) => filename.tokenize(),
             OptionKind::Insecure(value) => value.tokenize(),
             OptionKind::IpV4(value) => value.tokenize(),
             OptionKind::IpV6(value) => value.tokenize(),
+            OptionKind::JsonMatch(value) => value.tokenize(),
             OptionKind::MaxRedirect(value) => value.tokenize(),
+            OptionKind::Name(value) => vec![Token::String(value.clone())],
+            OptionKind::OnlyIf(value) => value.tokenize(),
             OptionKind::Output(filename) => filename.tokenize(),
             OptionKind::PathAsIs(value) => value.tokenize(),
             OptionKind::Proxy(value) => value.tokenize(),
+            OptionKind::Repeat(value) => value.tokenize(),
             OptionKind::Resolve(value) => value.tokenize(),
             OptionKind::Retry(value) => value.tokenize(),
+            OptionKind::RetryAfter(value) => value.tokenize(),
+            OptionKind::RetryBackoff(value) => value.tokenize(),
             OptionKind::RetryInterval(value) => value.tokenize(),
+            OptionKind::RetryJitter(value) => value.tokenize(),
+            OptionKind::RetryMaxInterval(value) => value.tokenize(),
+            OptionKind::RetryMaxTime(value) => value.tokenize(),
             OptionKind::Skip(value) => value.tokenize(),
+            OptionKind::Tag(value) => vec![Token::String(value.clone())],
             OptionKind::Variable(value) => value.tokenize(),
             OptionKind::Verbose(value) => value.tokenize(),
             OptionKind::VeryVerbose(value) => value.tokenize(),
@@ -915,6 +1020,21 @@ impl Tokenizable for BooleanOption {
     }
 }
 
+impl Tokenizable for ConditionOption {
+    fn tokenize(&self) -> Vec<Token> {
+        match self {
+            ConditionOption::Literal(value) => vec![Token::Boolean(value.to_string())],
+            ConditionOption::Expression(expr) => expr.tokenize(),
+            ConditionOption::Predicate(condition) => {
+                let mut tokens = condition.expr.tokenize();
+                tokens.append(&mut condition.space0.tokenize());
+                tokens.append(&mut condition.predicate.tokenize());
+                tokens
+            }
+        }
+    }
+}
+
 impl Tokenizable for NaturalOption {
     fn tokenize(&self) -> Vec<Token> {
         match self {
@@ -221,6 +221,12 @@ fn lint_capture(capture: &Capture) -> Capture {
         space2: one_whitespace(),
         query: lint_query(&capture.query),
         filters,
+        space3: if capture.redact {
+            one_whitespace()
+        } else {
+            empty_whitespace()
+        },
+        redact: capture.redact,
         line_terminator0: lint_line_terminator(&capture.line_terminator0),
     }
 }
@@ -382,6 +388,18 @@ fn lint_predicate_func_value(predicate_func_value: &PredicateFuncValue) -> Predi
             space0: one_whitespace(),
             value: lint_predicate_value(value),
         },
+        PredicateFuncValue::MatchSnapshot { value, .. } => PredicateFuncValue::MatchSnapshot {
+            space0: one_whitespace(),
+            value: lint_predicate_value(value),
+        },
+        PredicateFuncValue::MatchSchema { value, .. } => PredicateFuncValue::MatchSchema {
+            space0: one_whitespace(),
+            value: lint_predicate_value(value),
+        },
+        PredicateFuncValue::MatchXmlSchema { value, .. } => PredicateFuncValue::MatchXmlSchema {
+            space0: one_whitespace(),
+            value: lint_predicate_value(value),
+        },
         PredicateFuncValue::StartWith { value, .. } => PredicateFuncValue::StartWith {
             space0: one_whitespace(),
             value: lint_predicate_value(value),
@@ -412,6 +430,7 @@ fn lint_predicate_value(predicate_value: &PredicateValue) -> PredicateValue {
         PredicateValue::Number(value) => PredicateValue::Number(value.clone()),
         PredicateValue::File(value) => PredicateValue::File(lint_file(value)),
         PredicateValue::Hex(value) => PredicateValue::Hex(lint_hex(value)),
+        PredicateValue::Json(value) => PredicateValue::Json(value.clone()),
         PredicateValue::Base64(value) => PredicateValue::Base64(lint_base64(value)),
         PredicateValue::Expression(value) => PredicateValue::Expression(value.clone()),
         PredicateValue::Regex(value) => PredicateValue::Regex(value.clone()),
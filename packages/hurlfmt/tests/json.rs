@@ -84,7 +84,9 @@ fn value_string() -> BoxedStrategy<JsonValue> {
                         value: String::new(),
                         source_info
                     },
-                    variable,
+                    kind: ExprKind::Variable(variable),
+                    default: None,
+                    filters: vec![],
                     space1: Whitespace {
                         value: String::new(),
                         source_info